@@ -0,0 +1,130 @@
+//! Persistence of sweeps and single runs so they can be resumed.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::evolution::Evolution;
+use crate::one_max_genetic_algorithm::GaState;
+use crate::results::Results;
+use crate::sweep::ExperimentConfig;
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_json<T: Serialize>(value: &T, path: &Path) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    // Write to a temporary file first so an interrupted save never corrupts the checkpoint
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// A completed sweep cell and its position in the grid.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SweepCell {
+    pub mutation_index: usize,
+    pub crossover_index: usize,
+    pub mutation_rate: f64,
+    pub crossover_rate: f64,
+    pub results: Results,
+}
+
+/// Completed cells of a sweep along with the config that produced them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SweepCheckpoint {
+    pub config: ExperimentConfig,
+    pub cells: Vec<SweepCell>,
+}
+
+impl SweepCheckpoint {
+    /// Empty checkpoint for the given sweep.
+    pub fn new(config: ExperimentConfig) -> Self {
+        SweepCheckpoint {
+            config,
+            cells: Vec::new(),
+        }
+    }
+
+    /// Reads a checkpoint written by [`SweepCheckpoint::save`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        read_json(path)
+    }
+
+    /// Writes the checkpoint atomically, replacing any previous file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_json(self, path)
+    }
+
+    /// Whether the cells were produced by the same experiment config. A config without a seed
+    /// matches any seed, and resuming it continues with the seed of the checkpoint.
+    pub fn is_compatible(&self, config: &ExperimentConfig) -> bool {
+        config
+            .seed
+            .is_none_or(|seed| self.config.seed == Some(seed))
+            && self.config
+                == ExperimentConfig {
+                    seed: self.config.seed,
+                    ..config.clone()
+                }
+    }
+
+    /// Results of the cell at the given grid position, if it was completed.
+    pub fn find(&self, mutation_index: usize, crossover_index: usize) -> Option<&Results> {
+        self.cells
+            .iter()
+            .find(|cell| {
+                cell.mutation_index == mutation_index && cell.crossover_index == crossover_index
+            })
+            .map(|cell| &cell.results)
+    }
+
+    /// Records a completed cell.
+    pub fn add_cell(&mut self, cell: SweepCell) {
+        self.cells.push(cell);
+    }
+}
+
+/// Writes a snapshot of a single run.
+pub fn save_run_state(state: &GaState, path: &Path) -> io::Result<()> {
+    write_json(state, path)
+}
+
+/// Reads a snapshot written by [`save_run_state`].
+pub fn load_run_state(path: &Path) -> io::Result<GaState> {
+    read_json(path)
+}
+
+/// Runs the evolution to completion, snapshotting its state every `checkpoint_every` generations
+/// and once more when the run finishes. A run restored with [`load_run_state`] and
+/// [`Evolution::from_state`] continues exactly where it stopped. Fails with
+/// [`io::ErrorKind::InvalidInput`] before running when the evaluator has a
+/// [stateful penalty](crate::constraints::Penalty::is_stateful), which the snapshot would miss.
+pub fn genetic_algorithm_with_checkpoints(
+    evolution: &mut Evolution,
+    verbose: bool,
+    checkpoint_every: u32,
+    path: &Path,
+) -> io::Result<(u32, f64, f64)> {
+    if evolution.evaluator().has_stateful_penalty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a run with a stateful penalty cannot be resumed from a snapshot",
+        ));
+    }
+    let checkpoint_every = checkpoint_every.max(1);
+    let mut save_result = Ok(());
+    let outcome = evolution.run(verbose, |evolution, _| {
+        if evolution.is_finished() || evolution.generation() % checkpoint_every == 0 {
+            save_result = save_run_state(evolution.state(), path);
+        }
+        save_result.is_ok()
+    });
+    save_result.map(|_| outcome)
+}
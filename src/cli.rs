@@ -0,0 +1,233 @@
+use std::path::PathBuf;
+
+use one_max_genetic_algorithm_rust::logging::{LogConfig, LogFormat, LogSink};
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::Jobs;
+
+const DEFAULT_CHECKPOINT_PATH: &str = "sweep_checkpoint.json";
+const DEFAULT_REPORT_PATH: &str = "sweep_report.html";
+const DEFAULT_LOG_LEVEL: &str = "warn";
+
+/// What the binary writes to stdout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The banner, the best configuration and the paths of the written files.
+    Text,
+    /// A single JSON document at the end, everything else goes to stderr.
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CliOptions {
+    pub resume: bool,
+    pub checkpoint_path: PathBuf,
+    pub solver: Solver,
+    /// Genome lengths of a scaling analysis to run instead of the sweep.
+    pub scaling: Option<Vec<usize>>,
+    /// Show the live terminal dashboard instead of the progress bar.
+    pub dashboard: bool,
+    /// Directory to write the SVG charts of the sweep to.
+    pub plots: Option<PathBuf>,
+    /// HTML report written at the end of the sweep.
+    pub report_path: PathBuf,
+    /// Log filter used when `RUST_LOG` is not set.
+    pub log_level: String,
+    /// File the log is also written to as text.
+    pub log_file: Option<PathBuf>,
+    /// File the log is also written to as JSON lines.
+    pub log_json: Option<PathBuf>,
+    pub format: OutputFormat,
+    /// Seed of the sweep or of the first scaling run, random when `None`.
+    pub seed: Option<u64>,
+    /// Runs executed at the same time.
+    pub jobs: Jobs,
+    /// Pin the runs of the sweep to CPU cores.
+    pub pin_cores: bool,
+}
+
+impl Default for CliOptions {
+    fn default() -> Self {
+        CliOptions {
+            resume: false,
+            checkpoint_path: PathBuf::from(DEFAULT_CHECKPOINT_PATH),
+            solver: Solver::Ga,
+            scaling: None,
+            dashboard: false,
+            plots: None,
+            report_path: PathBuf::from(DEFAULT_REPORT_PATH),
+            log_level: DEFAULT_LOG_LEVEL.to_string(),
+            log_file: None,
+            log_json: None,
+            format: OutputFormat::Text,
+            seed: None,
+            jobs: Jobs::default(),
+            pin_cores: false,
+        }
+    }
+}
+
+impl CliOptions {
+    /// Logging configuration of the options. `rust_log`, the value of `RUST_LOG`, takes precedence
+    /// over `--log-level`, and stderr is left out under the dashboard, which owns the terminal.
+    pub fn log_config(&self, rust_log: Option<String>) -> LogConfig {
+        let mut sinks = Vec::new();
+        if !self.dashboard {
+            sinks.push(LogSink::Stderr);
+        }
+        if let Some(path) = &self.log_file {
+            sinks.push(LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Text,
+            });
+        }
+        if let Some(path) = &self.log_json {
+            sinks.push(LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Json,
+            });
+        }
+        LogConfig {
+            filter: rust_log.unwrap_or_else(|| self.log_level.clone()),
+            sinks,
+        }
+    }
+}
+
+pub fn usage() -> String {
+    format!(
+        "Usage: one-max-genetic-algorithm-rust [OPTIONS]
+
+Options:
+    --dashboard            Show a live full-screen dashboard of the sweep, q or Esc aborts it
+    --resume               Skip the sweep cells already stored in the checkpoint file
+    --checkpoint <PATH>    Checkpoint file for the sweep (default: {DEFAULT_CHECKPOINT_PATH})
+    --format <FORMAT>      Output on stdout: text (default) or json, a single document with
+                           the config, seed, best rates, their results and the timing
+    --jobs <JOBS>          Runs executed at the same time: a number, all (one per CPU) or
+                           all-N (all CPUs but N) (default: {})
+    --pin-cores            Pin every concurrent run of the sweep to its own CPU core, for
+                           stable timings
+    --log-level <LEVEL>    Log filter: error, warn, info, debug or trace, or RUST_LOG
+                           directives (default: {DEFAULT_LOG_LEVEL}, RUST_LOG takes precedence)
+    --log-file <PATH>      Also write the log to PATH as text
+    --log-json <PATH>      Also write the log to PATH as JSON lines
+    --plots <DIR>          Write SVG charts of the sweep to DIR: convergence of the best
+                           cell, score heatmap and box plots of the generations run
+    --report <PATH>        HTML report of the sweep (default: {DEFAULT_REPORT_PATH})
+    --solver <SOLVER>      Algorithm to sweep (default: ga): ga, umda, cga, pbil, rls,
+                           1+1-ea, 1+lambda-ea, mu+1-ea, hc-first, hc-best, sa or nsga2
+    --seed <SEED>          Seed the runs are derived from, to reproduce a sweep (default: random)
+    --scaling <LENGTHS>    Instead of the sweep, measure the evaluations the solver needs
+                           to reach the optimum at each of the comma separated genome
+                           lengths and fit a complexity model (e.g. 10,20,40,80)
+    -h, --help             Print this help",
+        Jobs::default()
+    )
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<CliOptions>, String> {
+    let mut options = CliOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--resume" => options.resume = true,
+            "--pin-cores" => options.pin_cores = true,
+            "--dashboard" => options.dashboard = true,
+            "--checkpoint" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "--checkpoint requires a path".to_string())?;
+                options.checkpoint_path = PathBuf::from(path);
+            }
+            "--format" => {
+                let format = args
+                    .next()
+                    .ok_or_else(|| "--format requires text or json".to_string())?;
+                options.format = match format.to_ascii_lowercase().as_str() {
+                    "text" => OutputFormat::Text,
+                    "json" => OutputFormat::Json,
+                    _ => {
+                        return Err(format!(
+                            "unknown format \"{format}\", expected text or json"
+                        ))
+                    }
+                };
+            }
+            "--seed" => {
+                let seed = args
+                    .next()
+                    .ok_or_else(|| "--seed requires a number".to_string())?;
+                options.seed = Some(
+                    seed.parse()
+                        .map_err(|_| format!("invalid seed \"{seed}\""))?,
+                );
+            }
+            "--jobs" => {
+                let jobs = args
+                    .next()
+                    .ok_or_else(|| "--jobs requires a number, all or all-N".to_string())?;
+                options.jobs = jobs.parse()?;
+            }
+            "--log-level" => {
+                options.log_level = args
+                    .next()
+                    .ok_or_else(|| "--log-level requires a level".to_string())?;
+            }
+            "--log-file" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "--log-file requires a path".to_string())?;
+                options.log_file = Some(PathBuf::from(path));
+            }
+            "--log-json" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "--log-json requires a path".to_string())?;
+                options.log_json = Some(PathBuf::from(path));
+            }
+            "--plots" => {
+                let directory = args
+                    .next()
+                    .ok_or_else(|| "--plots requires a directory".to_string())?;
+                options.plots = Some(PathBuf::from(directory));
+            }
+            "--report" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "--report requires a path".to_string())?;
+                options.report_path = PathBuf::from(path);
+            }
+            "--solver" => {
+                let solver = args
+                    .next()
+                    .ok_or_else(|| "--solver requires a name".to_string())?;
+                options.solver = solver.parse()?;
+            }
+            "--scaling" => {
+                let lengths = args
+                    .next()
+                    .ok_or_else(|| "--scaling requires genome lengths".to_string())?;
+                options.scaling = Some(parse_genome_lengths(&lengths)?);
+            }
+            "-h" | "--help" => return Ok(None), // The caller prints the usage
+            unknown => return Err(format!("Unknown argument: {unknown}")),
+        }
+    }
+    if options.dashboard && options.format == OutputFormat::Json {
+        // Both would draw on stdout
+        return Err("--dashboard cannot be combined with --format json".to_string());
+    }
+    Ok(Some(options))
+}
+
+/// Parses a comma separated list of positive genome lengths.
+fn parse_genome_lengths(lengths: &str) -> Result<Vec<usize>, String> {
+    lengths
+        .split(',')
+        .map(|length| match length.trim().parse::<usize>() {
+            Ok(length) if length > 0 => Ok(length),
+            _ => Err(format!("invalid genome length \"{length}\" in --scaling")),
+        })
+        .collect()
+}
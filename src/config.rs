@@ -0,0 +1,542 @@
+//! Validated configuration of a single run, built with [`GaConfigBuilder`].
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//!
+//! let mut config = GaConfig::builder()
+//!     .genome_length(20)
+//!     .mutation_rate(0.01)
+//!     .crossover_rate(0.6)
+//!     .seed(42)
+//!     .build()
+//!     .unwrap();
+//! let outcome = config.run();
+//! assert!(outcome.best_fitness > 0.5);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng};
+use tracing::{debug, info, info_span};
+
+use crate::constraints::{Penalty, Repair};
+use crate::diversity::Diversity;
+use crate::evolution::Evolution;
+use crate::fitness::{CacheStats, Evaluator, Fitness, OneMax};
+use crate::one_max_genetic_algorithm::{get_best_fitness, GaParameters, GaState, GenerationStats};
+use crate::operators::{Crossover, Mutation, Operators, Replacement, Selection};
+use crate::restart::{Restart, RestartEvent};
+use crate::termination::{Stagnation, StopReason, Termination};
+
+pub const DEFAULT_POPULATION_SIZE: usize = 50;
+pub const DEFAULT_GENOME_LENGTH: usize = 35;
+pub const DEFAULT_MAX_GENERATIONS: u32 = 400;
+pub const DEFAULT_MUTATION_RATE: f64 = 0.01;
+pub const DEFAULT_CROSSOVER_RATE: f64 = 0.6;
+pub const DEFAULT_SELECT_PARENT_MODE: &str = "tournament";
+pub const DEFAULT_TARGET_GENERATION_FITNESS: f64 = 0.998;
+
+/// Reason a [`GaConfigBuilder`] was rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    ZeroPopulation,
+    /// One-point crossover needs at least two genes.
+    GenomeTooShort(usize),
+    ZeroGenerations,
+    /// A probability outside of [0, 1].
+    RateOutOfRange {
+        name: &'static str,
+        value: f64,
+    },
+    TargetFitnessOutOfRange(f64),
+    UnknownSelectParentMode(String),
+    ZeroEvaluationThreads,
+    /// Restarts need at least one generation without improvement to trigger.
+    ZeroRestartStagnation,
+    /// A sweep or scaling analysis without a single run per configuration.
+    ZeroRuns,
+    /// A list of values to sweep over, named by the field, is empty.
+    EmptyValues(&'static str),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroPopulation => write!(f, "population size must be at least 1"),
+            ConfigError::GenomeTooShort(length) => {
+                write!(f, "genome length must be at least 2, got {length}")
+            }
+            ConfigError::ZeroGenerations => write!(f, "max generations must be at least 1"),
+            ConfigError::RateOutOfRange { name, value } => {
+                write!(f, "{name} must be between 0 and 1, got {value}")
+            }
+            ConfigError::TargetFitnessOutOfRange(value) => {
+                write!(
+                    f,
+                    "target generation fitness must be between 0 and 1, got {value}"
+                )
+            }
+            ConfigError::UnknownSelectParentMode(mode) => write!(
+                f,
+                "unknown parent selection mode \"{mode}\", expected tournament or roulette"
+            ),
+            ConfigError::ZeroEvaluationThreads => {
+                write!(f, "evaluation threads must be at least 1")
+            }
+            ConfigError::ZeroRestartStagnation => {
+                write!(f, "restart stagnation generations must be at least 1")
+            }
+            ConfigError::ZeroRuns => write!(f, "runs per configuration must be at least 1"),
+            ConfigError::EmptyValues(name) => write!(f, "{name} must not be empty"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Outcome of [`GaConfig::run`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunOutcome {
+    /// Generation the run was solved in, or the number of generations run otherwise.
+    pub generation: u32,
+    pub generation_fitness: f64,
+    pub best_fitness: f64,
+    pub best_genome: Vec<u8>,
+    pub solved: bool,
+    pub stop_reason: StopReason,
+    /// Number of fitness function calls made by the run. Cache hits are not counted.
+    pub evaluations: u64,
+    /// Hits and misses of the fitness cache, if it was enabled.
+    pub cache_stats: Option<CacheStats>,
+    /// Diversity of the population after every generation.
+    pub diversity: Vec<Diversity>,
+    /// Every restart of the run, in order.
+    pub restarts: Vec<RestartEvent>,
+    /// Seed the run was started with, so it can be reproduced.
+    pub seed: u64,
+}
+
+/// Gets notified about the progress of a run.
+pub trait Observer: Send {
+    fn on_generation(&mut self, state: &GaState, stats: &GenerationStats);
+
+    fn on_finish(&mut self, _outcome: &RunOutcome) {}
+}
+
+/// A validated run configuration. Build it with [`GaConfig::builder`].
+pub struct GaConfig {
+    parameters: GaParameters,
+    verbose: bool,
+    seed: Option<u64>,
+    fitness: Arc<dyn Fitness>,
+    fitness_cache_capacity: Option<usize>,
+    evaluation_threads: usize,
+    operators: Arc<Operators>,
+    observers: Vec<Box<dyn Observer>>,
+    terminations: Vec<Box<dyn Termination>>,
+    restart: Option<RestartPolicy>,
+    penalty: Option<PenaltyFactory>,
+}
+
+type PenaltyFactory = Arc<dyn Fn() -> Box<dyn Penalty> + Send + Sync>;
+
+/// Restarts the run with `strategy` once `stagnation` triggers.
+struct RestartPolicy {
+    stagnation: Stagnation,
+    strategy: Box<dyn Restart>,
+}
+
+impl GaConfig {
+    pub fn builder() -> GaConfigBuilder {
+        GaConfigBuilder::default()
+    }
+
+    pub fn parameters(&self) -> &GaParameters {
+        &self.parameters
+    }
+
+    /// Starts a step-wise run with the configured operators. Observers and termination criteria
+    /// only apply to [`GaConfig::run`].
+    pub fn evolution(&self) -> Evolution {
+        let seed = self.seed.unwrap_or_else(|| thread_rng().gen());
+        Evolution::with_evaluator(
+            self.parameters.clone(),
+            seed,
+            Arc::clone(&self.operators),
+            self.evaluator(),
+        )
+        .expect("parameters are validated when the config is built")
+    }
+
+    fn evaluator(&self) -> Evaluator {
+        let mut evaluator =
+            Evaluator::new(Arc::clone(&self.fitness)).with_threads(self.evaluation_threads);
+        if let Some(capacity) = self.fitness_cache_capacity {
+            evaluator = evaluator.with_cache(capacity);
+        }
+        if let Some(penalty) = &self.penalty {
+            // Every run starts from a fresh copy of the penalty
+            evaluator = evaluator.with_penalty(penalty());
+        }
+        evaluator
+    }
+
+    /// Runs the genetic algorithm once. Every call starts a new run, from the configured seed if
+    /// there is one.
+    pub fn run(&mut self) -> RunOutcome {
+        let seed = self.seed.unwrap_or_else(|| thread_rng().gen());
+        let _span = info_span!("run", seed).entered();
+        let mut evolution = Evolution::with_evaluator(
+            self.parameters.clone(),
+            seed,
+            Arc::clone(&self.operators),
+            self.evaluator(),
+        )
+        .expect("parameters are validated when the config is built");
+        let observers = &mut self.observers;
+        let terminations = &mut self.terminations;
+        terminations
+            .iter_mut()
+            .for_each(|criterion| criterion.reset());
+        let mut termination_reason = None;
+        let mut diversity = Vec::new();
+        let mut restarts = Vec::new();
+        let restart = &mut self.restart;
+        if let Some(policy) = restart.as_mut() {
+            policy.stagnation.reset();
+        }
+        let verbose = self.verbose;
+
+        let (generation, generation_fitness, best_fitness) =
+            evolution.run(self.verbose, |evolution, stats| {
+                diversity.push(evolution.diversity());
+                for observer in observers.iter_mut() {
+                    observer.on_generation(evolution.state(), stats);
+                }
+                // Every criterion is checked so stateful ones see every generation
+                for criterion in terminations.iter_mut() {
+                    let reason = criterion.check(evolution.state(), stats);
+                    termination_reason = termination_reason.take().or(reason);
+                }
+                if termination_reason.is_some() {
+                    return false;
+                }
+                if let Some(policy) = restart.as_mut() {
+                    let stagnated = policy.stagnation.check(evolution.state(), stats).is_some();
+                    if stagnated && !evolution.is_finished() {
+                        let event = evolution.restart(policy.strategy.as_mut());
+                        if verbose {
+                            info!(%event, "restart");
+                        } else {
+                            debug!(%event, "restart");
+                        }
+                        restarts.push(event);
+                        policy.stagnation.reset();
+                    }
+                }
+                true
+            });
+
+        let state = evolution.state();
+        let outcome = RunOutcome {
+            generation,
+            generation_fitness,
+            best_fitness,
+            best_genome: best_genome(state, evolution.evaluator()),
+            solved: state.solved,
+            // The built-in stop reasons win over the configured criteria when both apply
+            stop_reason: evolution
+                .stop_reason()
+                .or(termination_reason)
+                .unwrap_or(StopReason::MaxGenerations),
+            evaluations: state.evaluations,
+            cache_stats: evolution.cache_stats(),
+            diversity,
+            restarts,
+            seed,
+        };
+        for observer in self.observers.iter_mut() {
+            observer.on_finish(&outcome);
+        }
+        outcome
+    }
+}
+
+fn best_genome(state: &GaState, evaluator: &Evaluator) -> Vec<u8> {
+    let population = if state.solved || state.best_population.is_empty() {
+        &state.population
+    } else {
+        &state.best_population
+    };
+    let fitness_values: Vec<f64> = population
+        .iter()
+        .map(|genome| evaluator.evaluate(genome))
+        .collect();
+    let best_fitness = get_best_fitness(&fitness_values);
+    fitness_values
+        .iter()
+        .position(|&x| x == best_fitness)
+        .map(|index| population[index].clone())
+        .unwrap_or_default()
+}
+
+/// Builder of [`GaConfig`]. Every setting has a default, so only the ones that differ need to be
+/// set.
+pub struct GaConfigBuilder {
+    parameters: GaParameters,
+    verbose: bool,
+    seed: Option<u64>,
+    fitness: Arc<dyn Fitness>,
+    fitness_cache_capacity: Option<usize>,
+    evaluation_threads: usize,
+    selection: Option<Box<dyn Selection>>,
+    crossover: Option<Box<dyn Crossover>>,
+    mutation: Option<Box<dyn Mutation>>,
+    replacement: Option<Box<dyn Replacement>>,
+    observers: Vec<Box<dyn Observer>>,
+    terminations: Vec<Box<dyn Termination>>,
+    restart: Option<(u32, Box<dyn Restart>)>,
+    penalty: Option<PenaltyFactory>,
+    repair: Option<Box<dyn Repair>>,
+}
+
+impl Default for GaConfigBuilder {
+    fn default() -> Self {
+        GaConfigBuilder {
+            parameters: GaParameters {
+                population_size: DEFAULT_POPULATION_SIZE,
+                genome_length: DEFAULT_GENOME_LENGTH,
+                max_generations: DEFAULT_MAX_GENERATIONS,
+                mutation_rate: DEFAULT_MUTATION_RATE,
+                crossover_rate: DEFAULT_CROSSOVER_RATE,
+                select_parent_mode: DEFAULT_SELECT_PARENT_MODE.to_string(),
+                target_generation_fitness: DEFAULT_TARGET_GENERATION_FITNESS,
+            },
+            verbose: false,
+            seed: None,
+            fitness: Arc::new(OneMax),
+            fitness_cache_capacity: None,
+            evaluation_threads: 1,
+            selection: None,
+            crossover: None,
+            mutation: None,
+            replacement: None,
+            observers: Vec::new(),
+            terminations: Vec::new(),
+            restart: None,
+            penalty: None,
+            repair: None,
+        }
+    }
+}
+
+impl GaConfigBuilder {
+    pub fn population_size(mut self, population_size: usize) -> Self {
+        self.parameters.population_size = population_size;
+        self
+    }
+
+    pub fn genome_length(mut self, genome_length: usize) -> Self {
+        self.parameters.genome_length = genome_length;
+        self
+    }
+
+    pub fn max_generations(mut self, max_generations: u32) -> Self {
+        self.parameters.max_generations = max_generations;
+        self
+    }
+
+    pub fn mutation_rate(mut self, mutation_rate: f64) -> Self {
+        self.parameters.mutation_rate = mutation_rate;
+        self
+    }
+
+    pub fn crossover_rate(mut self, crossover_rate: f64) -> Self {
+        self.parameters.crossover_rate = crossover_rate;
+        self
+    }
+
+    /// `"tournament"` or `"roulette"`. Ignored when a custom [`Selection`] is set.
+    pub fn select_parent_mode(mut self, select_parent_mode: &str) -> Self {
+        self.parameters.select_parent_mode = select_parent_mode.to_string();
+        self
+    }
+
+    pub fn target_generation_fitness(mut self, target_generation_fitness: f64) -> Self {
+        self.parameters.target_generation_fitness = target_generation_fitness;
+        self
+    }
+
+    /// Logs every generation at info level, instead of debug level, along with its diversity.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Makes every run reproducible. Without a seed each run draws a random one.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Replaces the One-Max fitness function.
+    pub fn fitness<F: Fitness + 'static>(mut self, fitness: F) -> Self {
+        self.fitness = Arc::new(fitness);
+        self
+    }
+
+    /// Caches the fitness of up to `capacity` distinct genomes per run, so duplicates are not
+    /// evaluated again. Hits and misses are reported in [`RunOutcome::cache_stats`].
+    pub fn fitness_cache(mut self, capacity: usize) -> Self {
+        self.fitness_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Evaluates every generation on up to `threads` threads. This is independent of the threads
+    /// used by a sweep, which runs whole runs in parallel. Defaults to 1.
+    pub fn evaluation_threads(mut self, threads: usize) -> Self {
+        self.evaluation_threads = threads;
+        self
+    }
+
+    /// Replaces the selection derived from `select_parent_mode`.
+    pub fn selection<S: Selection + 'static>(mut self, selection: S) -> Self {
+        self.selection = Some(Box::new(selection));
+        self
+    }
+
+    /// Replaces the one-point crossover driven by `crossover_rate`.
+    pub fn crossover<C: Crossover + 'static>(mut self, crossover: C) -> Self {
+        self.crossover = Some(Box::new(crossover));
+        self
+    }
+
+    /// Replaces the bit flip mutation driven by `mutation_rate`.
+    pub fn mutation<M: Mutation + 'static>(mut self, mutation: M) -> Self {
+        self.mutation = Some(Box::new(mutation));
+        self
+    }
+
+    /// Replaces the generational replacement, for example with one of the niching methods of
+    /// [`crate::niching`].
+    pub fn replacement<R: Replacement + 'static>(mut self, replacement: R) -> Self {
+        self.replacement = Some(Box::new(replacement));
+        self
+    }
+
+    /// Subtracts `penalty` of the [`Fitness::violation`] from every fitness value. Every run
+    /// starts from a copy of it.
+    pub fn penalty<P: Penalty + Clone + Sync + 'static>(mut self, penalty: P) -> Self {
+        self.penalty = Some(Arc::new(move || Box::new(penalty.clone())));
+        self
+    }
+
+    /// Runs `repair` on every child after crossover and after mutation.
+    pub fn repair<R: Repair + 'static>(mut self, repair: R) -> Self {
+        self.repair = Some(Box::new(repair));
+        self
+    }
+
+    pub fn observer<O: Observer + 'static>(mut self, observer: O) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Stops the run as soon as the criterion does, on top of the generation limit and the target
+    /// fitness. Adding several criteria stops on the first one, like [`AnyOf`](crate::termination::AnyOf).
+    pub fn termination<T: Termination + 'static>(mut self, termination: T) -> Self {
+        self.terminations.push(Box::new(termination));
+        self
+    }
+
+    /// Stops the run after any generation for which `should_stop` returns true.
+    pub fn terminate_when<F>(self, should_stop: F) -> Self
+    where
+        F: FnMut(&GaState, &GenerationStats) -> bool + Send + 'static,
+    {
+        self.termination(should_stop)
+    }
+
+    /// Restarts the run with `restart` whenever the best fitness did not improve for
+    /// `generations` generations.
+    pub fn restart_on_stagnation<R: Restart + 'static>(
+        mut self,
+        generations: u32,
+        restart: R,
+    ) -> Self {
+        self.restart = Some((generations, Box::new(restart)));
+        self
+    }
+
+    /// Validates the settings and builds the config.
+    pub fn build(self) -> Result<GaConfig, ConfigError> {
+        validate_parameters(&self.parameters)?;
+        if self.evaluation_threads == 0 {
+            return Err(ConfigError::ZeroEvaluationThreads);
+        }
+        if matches!(self.restart, Some((0, _))) {
+            return Err(ConfigError::ZeroRestartStagnation);
+        }
+
+        let defaults = Operators::from_parameters(&self.parameters);
+        let operators = Operators {
+            selection: self.selection.unwrap_or(defaults.selection),
+            crossover: self.crossover.unwrap_or(defaults.crossover),
+            mutation: self.mutation.unwrap_or(defaults.mutation),
+            replacement: self.replacement.unwrap_or(defaults.replacement),
+            repair: self.repair,
+        };
+        Ok(GaConfig {
+            parameters: self.parameters,
+            verbose: self.verbose,
+            seed: self.seed,
+            fitness: self.fitness,
+            fitness_cache_capacity: self.fitness_cache_capacity,
+            evaluation_threads: self.evaluation_threads,
+            operators: Arc::new(operators),
+            observers: self.observers,
+            terminations: self.terminations,
+            restart: self.restart.map(|(generations, strategy)| RestartPolicy {
+                stagnation: Stagnation::new(generations),
+                strategy,
+            }),
+            penalty: self.penalty,
+        })
+    }
+}
+
+/// Checks the parameters of a run, as done by [`GaConfigBuilder::build`].
+pub fn validate_parameters(parameters: &GaParameters) -> Result<(), ConfigError> {
+    if parameters.population_size == 0 {
+        return Err(ConfigError::ZeroPopulation);
+    }
+    if parameters.genome_length < 2 {
+        return Err(ConfigError::GenomeTooShort(parameters.genome_length));
+    }
+    if parameters.max_generations == 0 {
+        return Err(ConfigError::ZeroGenerations);
+    }
+    for (name, value) in [
+        ("mutation rate", parameters.mutation_rate),
+        ("crossover rate", parameters.crossover_rate),
+    ] {
+        // Written this way so NaN is rejected too
+        if !(0.0..=1.0).contains(&value) {
+            return Err(ConfigError::RateOutOfRange { name, value });
+        }
+    }
+    if !(0.0..=1.0).contains(&parameters.target_generation_fitness) {
+        return Err(ConfigError::TargetFitnessOutOfRange(
+            parameters.target_generation_fitness,
+        ));
+    }
+    let mode = parameters.select_parent_mode.to_lowercase();
+    if mode != "tournament" && mode != "roulette" {
+        return Err(ConfigError::UnknownSelectParentMode(
+            parameters.select_parent_mode.clone(),
+        ));
+    }
+    Ok(())
+}
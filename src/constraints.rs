@@ -0,0 +1,194 @@
+//! Constraint handling: penalties, feasibility based selection and repair.
+//!
+//! Constrained problems report how far a genome is from feasible through
+//! [`Fitness::violation`]. A run can then either penalise the violation, with a [`Penalty`] set
+//! through [`crate::config::GaConfigBuilder::penalty`], compare individuals with Deb's
+//! feasibility rules in a [`FeasibilityTournament`], or fix infeasible genomes with a [`Repair`]
+//! run after crossover and mutation.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use rand::seq::index::sample;
+use rand::RngCore;
+
+use crate::fitness::Fitness;
+use crate::operators::Selection;
+
+/// Turns a constraint violation into an amount subtracted from the fitness.
+pub trait Penalty: Send {
+    fn penalty(&self, violation: f64) -> f64;
+
+    /// Called after every generation with whether its best individual was feasible.
+    fn update(&mut self, _best_is_feasible: bool) {}
+
+    /// Whether [`Penalty::update`] changes the penalty. A run snapshot does not capture that
+    /// state, so [`genetic_algorithm_with_checkpoints`](crate::checkpoint::genetic_algorithm_with_checkpoints)
+    /// refuses stateful penalties. Defaults to true; penalties that ignore updates return false.
+    fn is_stateful(&self) -> bool {
+        true
+    }
+}
+
+/// A penalty proportional to the violation.
+#[derive(Clone, Debug)]
+pub struct StaticPenalty {
+    pub coefficient: f64,
+}
+
+impl Penalty for StaticPenalty {
+    fn penalty(&self, violation: f64) -> f64 {
+        self.coefficient * violation
+    }
+
+    fn is_stateful(&self) -> bool {
+        false
+    }
+}
+
+/// Joines and Houck's penalty `(coefficient * t)^alpha * violation^beta`, which grows with the
+/// generation `t` so late generations are pushed towards feasibility.
+#[derive(Clone, Debug)]
+pub struct DynamicPenalty {
+    pub coefficient: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    generation: u32,
+}
+
+impl DynamicPenalty {
+    pub fn new(coefficient: f64, alpha: f64, beta: f64) -> Self {
+        DynamicPenalty {
+            coefficient,
+            alpha,
+            beta,
+            generation: 1,
+        }
+    }
+}
+
+impl Penalty for DynamicPenalty {
+    fn penalty(&self, violation: f64) -> f64 {
+        (self.coefficient * self.generation as f64).powf(self.alpha) * violation.powf(self.beta)
+    }
+
+    fn update(&mut self, _best_is_feasible: bool) {
+        self.generation += 1;
+    }
+}
+
+/// Hadj-Alouane and Bean's adaptive penalty: the coefficient is divided by `decrease` when the
+/// best individual was feasible for the last `window` generations, multiplied by `increase`
+/// when it was infeasible for all of them, and kept otherwise.
+#[derive(Clone, Debug)]
+pub struct AdaptivePenalty {
+    pub coefficient: f64,
+    pub decrease: f64,
+    pub increase: f64,
+    pub window: usize,
+    history: VecDeque<bool>,
+}
+
+impl AdaptivePenalty {
+    /// Adaptive penalty dividing by 1.5 and multiplying by 2.0.
+    pub fn new(coefficient: f64, window: usize) -> Self {
+        AdaptivePenalty {
+            coefficient,
+            decrease: 1.5,
+            increase: 2.0,
+            window: window.max(1),
+            history: VecDeque::new(),
+        }
+    }
+}
+
+impl Penalty for AdaptivePenalty {
+    fn penalty(&self, violation: f64) -> f64 {
+        self.coefficient * violation
+    }
+
+    fn update(&mut self, best_is_feasible: bool) {
+        self.history.push_back(best_is_feasible);
+        if self.history.len() > self.window {
+            self.history.pop_front();
+        }
+        if self.history.len() < self.window {
+            return;
+        }
+        if self.history.iter().all(|&feasible| feasible) {
+            self.coefficient /= self.decrease;
+        } else if self.history.iter().all(|&feasible| !feasible) {
+            self.coefficient *= self.increase;
+        }
+    }
+}
+
+/// Tournament selection with Deb's feasibility rules: a feasible individual beats an infeasible
+/// one, two feasible individuals compare their fitness and two infeasible ones their violation.
+pub struct FeasibilityTournament {
+    pub tournament_size: usize,
+    fitness: Arc<dyn Fitness>,
+}
+
+impl FeasibilityTournament {
+    /// Tournament measuring violations with `fitness`, which should be the run's fitness.
+    pub fn new(tournament_size: usize, fitness: Arc<dyn Fitness>) -> Self {
+        FeasibilityTournament {
+            tournament_size,
+            fitness,
+        }
+    }
+}
+
+impl Selection for FeasibilityTournament {
+    fn select(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8> {
+        let index = self.select_index(population, fitness_values, rng).unwrap();
+        population[index].clone()
+    }
+
+    fn select_index(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let tournament_size = self.tournament_size.clamp(1, population.len());
+        sample(rng, population.len(), tournament_size)
+            .iter()
+            .map(|index| (index, self.fitness.violation(&population[index])))
+            .reduce(|(winner, winner_violation), (index, violation)| {
+                let index_wins = match (winner_violation <= 0.0, violation <= 0.0) {
+                    (true, true) => fitness_values[index] > fitness_values[winner],
+                    (true, false) => false,
+                    (false, true) => true,
+                    (false, false) => violation < winner_violation,
+                };
+                if index_wins {
+                    (index, violation)
+                } else {
+                    (winner, winner_violation)
+                }
+            })
+            .map(|(index, _)| index)
+    }
+}
+
+/// Fixes an infeasible genome in place after crossover and after mutation.
+pub trait Repair: Send + Sync {
+    /// Returns whether the genome was changed.
+    fn repair(&self, genome: &mut [u8], rng: &mut dyn RngCore) -> bool;
+}
+
+impl<F> Repair for F
+where
+    F: Fn(&mut [u8], &mut dyn RngCore) -> bool + Send + Sync,
+{
+    fn repair(&self, genome: &mut [u8], rng: &mut dyn RngCore) -> bool {
+        self(genome, rng)
+    }
+}
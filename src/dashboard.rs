@@ -0,0 +1,513 @@
+//! Live full-screen terminal dashboard of a sweep.
+//!
+//! [`Dashboard`] is a [`SweepObserver`] that keeps the status of every worker thread, the
+//! convergence of the runs of the current cell, the mutation × crossover score grid and the best
+//! configuration so far. [`spawn`] draws it on a terminal from its own thread until the returned
+//! [`DashboardTerminal`] is finished, and turns `q`, `Esc` or `Ctrl-C` into a graceful abort: the
+//! running generations end, the unfinished cell is discarded and the completed ones stay in the
+//! checkpoint.
+//!
+//! ```no_run
+//! use std::io;
+//! use std::sync::Arc;
+//!
+//! use one_max_genetic_algorithm_rust::dashboard::{self, Dashboard};
+//! use one_max_genetic_algorithm_rust::sweep::{
+//!     process_genetic_algorithm_observed, ExperimentConfig, SweepObserver, Workers,
+//! };
+//!
+//! # fn sweep(config: ExperimentConfig) -> io::Result<()> {
+//! let dashboard = Arc::new(Dashboard::new(&config));
+//! let terminal = dashboard::spawn(Arc::clone(&dashboard), io::stdout())?;
+//! let observer: Arc<dyn SweepObserver> = dashboard;
+//! let outcome =
+//!     process_genetic_algorithm_observed(&config, None, Some(observer), &Workers::default());
+//! terminal.finish()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::one_max_genetic_algorithm::GenerationStats;
+use crate::plots::score_rgb;
+use crate::solver::Solver;
+use crate::sweep::{ExperimentConfig, SweepObserver};
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What a worker thread is doing.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WorkerStatus {
+    #[default]
+    Idle,
+    Running {
+        generation: u32,
+        best_fitness: f64,
+        generation_fitness: f64,
+    },
+    Finished {
+        generation: u32,
+        best_fitness: f64,
+    },
+}
+
+/// Best cell so far.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BestCell {
+    pub mutation_rate: f64,
+    pub crossover_rate: f64,
+    pub score: f64,
+}
+
+/// Text with an optional background color, the unit [`DashboardState::lines`] are made of.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub background: Option<Color>,
+}
+
+impl Span {
+    fn plain(text: impl Into<String>) -> Self {
+        Span {
+            text: text.into(),
+            background: None,
+        }
+    }
+}
+
+/// Everything the dashboard shows.
+#[derive(Clone, Debug)]
+pub struct DashboardState {
+    pub solver: Solver,
+    pub max_generations: u32,
+    pub mutation_rates: Vec<f64>,
+    pub crossover_rates: Vec<f64>,
+    /// Score of every finished cell, indexed by mutation then crossover rate.
+    pub scores: Vec<Vec<Option<f64>>>,
+    pub current_cell: Option<(usize, usize)>,
+    pub workers: Vec<WorkerStatus>,
+    pub best: Option<BestCell>,
+    pub aborting: bool,
+    /// Sums of the best and mean fitness and the number of runs that reached every generation of
+    /// the current cell.
+    convergence: Vec<(f64, f64, usize)>,
+    started: Instant,
+}
+
+impl DashboardState {
+    pub fn new(config: &ExperimentConfig) -> Self {
+        DashboardState {
+            solver: config.solver,
+            max_generations: config.generations,
+            mutation_rates: config.mutation_rate_values.clone(),
+            crossover_rates: config.crossover_rate_values.clone(),
+            scores: vec![
+                vec![None; config.crossover_rate_values.len()];
+                config.mutation_rate_values.len()
+            ],
+            current_cell: None,
+            workers: Vec::new(),
+            best: None,
+            aborting: false,
+            convergence: Vec::new(),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn finished_cells(&self) -> usize {
+        self.scores
+            .iter()
+            .flatten()
+            .filter(|score| score.is_some())
+            .count()
+    }
+
+    /// Best and mean fitness per generation, averaged over the runs of the current cell.
+    pub fn convergence(&self) -> (Vec<f64>, Vec<f64>) {
+        self.convergence
+            .iter()
+            .filter(|(_, _, count)| *count > 0)
+            .map(|&(best, mean, count)| (best / count as f64, mean / count as f64))
+            .unzip()
+    }
+
+    fn worker_mut(&mut self, worker: usize) -> &mut WorkerStatus {
+        if self.workers.len() <= worker {
+            self.workers.resize(worker + 1, WorkerStatus::Idle);
+        }
+        &mut self.workers[worker]
+    }
+
+    /// Lines of the dashboard for a terminal `width` columns wide.
+    pub fn lines(&self, width: usize) -> Vec<Vec<Span>> {
+        let mut lines = Vec::new();
+        let elapsed = self.started.elapsed().as_secs();
+        let total_cells = self.mutation_rates.len() * self.crossover_rates.len();
+        lines.push(vec![Span::plain(format!(
+            "One-Max sweep · solver {} · cells {}/{} · elapsed {:02}:{:02}:{:02} · q/Esc: abort",
+            self.solver,
+            self.finished_cells(),
+            total_cells,
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60
+        ))]);
+        if self.aborting {
+            lines.push(vec![Span::plain(
+                "Aborting: waiting for the running generations to finish...",
+            )]);
+        }
+
+        lines.push(vec![]);
+        lines.push(vec![Span::plain("Threads")]);
+        if self.workers.is_empty() {
+            lines.push(vec![Span::plain("  waiting for the first run")]);
+        }
+        let bar_width = width.saturating_sub(60).clamp(10, 40);
+        for (worker, status) in self.workers.iter().enumerate() {
+            let text = match *status {
+                WorkerStatus::Idle => format!("  #{worker:<3} idle"),
+                WorkerStatus::Running {
+                    generation,
+                    best_fitness,
+                    generation_fitness,
+                } => format!(
+                    "  #{worker:<3} gen {generation:>6}/{} {} best {best_fitness:.4} mean {generation_fitness:.4}",
+                    self.max_generations,
+                    progress_bar(generation as f64 / self.max_generations.max(1) as f64, bar_width)
+                ),
+                WorkerStatus::Finished {
+                    generation,
+                    best_fitness,
+                } => format!(
+                    "  #{worker:<3} done after {generation} generations, best {best_fitness:.4}"
+                ),
+            };
+            lines.push(vec![Span::plain(text)]);
+        }
+
+        lines.push(vec![]);
+        match self.current_cell {
+            Some((m, i)) => lines.push(vec![Span::plain(format!(
+                "Convergence of the current cell (mutation {:.4}, crossover {:.4})",
+                self.mutation_rates[m], self.crossover_rates[i]
+            ))]),
+            None => lines.push(vec![Span::plain("Convergence of the current cell")]),
+        }
+        let (best, mean) = self.convergence();
+        let low = mean
+            .iter()
+            .chain(&best)
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let high = mean
+            .iter()
+            .chain(&best)
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let spark_width = width.saturating_sub(16).max(10);
+        for (label, values) in [("best", &best), ("mean", &mean)] {
+            let last = values
+                .last()
+                .map_or("   -  ".to_string(), |v| format!("{v:.4}"));
+            lines.push(vec![Span::plain(format!(
+                "  {label} {last} {}",
+                sparkline(values, spark_width, (low, high))
+            ))]);
+        }
+
+        lines.push(vec![]);
+        lines.push(vec![Span::plain(
+            "Scores (rows: mutation rate, columns: crossover rate)",
+        )]);
+        let mut header = format!("  {:>8}", "");
+        for crossover_rate in &self.crossover_rates {
+            header.push_str(&format!(" {crossover_rate:>6.3}"));
+        }
+        lines.push(vec![Span::plain(header)]);
+        for (m, mutation_rate) in self.mutation_rates.iter().enumerate() {
+            let mut line = vec![Span::plain(format!("  {mutation_rate:>8.4}"))];
+            for (i, score) in self.scores[m].iter().enumerate() {
+                line.push(Span::plain(" "));
+                let running = self.current_cell == Some((m, i)) && score.is_none();
+                line.push(match score {
+                    Some(score) => Span {
+                        text: format!("{score:>6.3}"),
+                        background: Some(score_color(*score)),
+                    },
+                    None if running => Span::plain("  ... "),
+                    None => Span::plain("     ·"),
+                });
+            }
+            lines.push(line);
+        }
+
+        lines.push(vec![]);
+        lines.push(vec![Span::plain(match self.best {
+            Some(best) => format!(
+                "Best: mutation rate {:.4}, crossover rate {:.4}, score {:.4}",
+                best.mutation_rate, best.crossover_rate, best.score
+            ),
+            None => "Best: no cell finished yet".to_string(),
+        })]);
+        lines
+    }
+}
+
+/// Shared state of a live sweep, updated by the sweep threads and read by the drawing thread.
+pub struct Dashboard {
+    state: Mutex<DashboardState>,
+    abort: AtomicBool,
+}
+
+impl Dashboard {
+    pub fn new(config: &ExperimentConfig) -> Self {
+        Dashboard {
+            state: Mutex::new(DashboardState::new(config)),
+            abort: AtomicBool::new(false),
+        }
+    }
+
+    /// Copy of the current state.
+    pub fn snapshot(&self) -> DashboardState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Asks the sweep to stop as soon as the running generations end.
+    pub fn abort(&self) {
+        self.abort.store(true, Ordering::SeqCst);
+        self.state.lock().unwrap().aborting = true;
+    }
+}
+
+impl SweepObserver for Dashboard {
+    fn cell_started(
+        &self,
+        mutation_index: usize,
+        crossover_index: usize,
+        _mutation_rate: f64,
+        _crossover_rate: f64,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.current_cell = Some((mutation_index, crossover_index));
+        state.convergence.clear();
+    }
+
+    fn run_started(&self, worker: usize) {
+        *self.state.lock().unwrap().worker_mut(worker) = WorkerStatus::Running {
+            generation: 0,
+            best_fitness: 0.0,
+            generation_fitness: 0.0,
+        };
+    }
+
+    fn generation(&self, worker: usize, stats: &GenerationStats) {
+        let mut state = self.state.lock().unwrap();
+        *state.worker_mut(worker) = WorkerStatus::Running {
+            generation: stats.generation,
+            best_fitness: stats.best_fitness,
+            generation_fitness: stats.generation_fitness,
+        };
+        let index = stats.generation as usize;
+        if state.convergence.len() <= index {
+            state.convergence.resize(index + 1, (0.0, 0.0, 0));
+        }
+        let (best, mean, count) = &mut state.convergence[index];
+        *best += stats.best_fitness;
+        *mean += stats.generation_fitness;
+        *count += 1;
+    }
+
+    fn run_finished(&self, worker: usize) {
+        let mut state = self.state.lock().unwrap();
+        let status = state.worker_mut(worker);
+        if let WorkerStatus::Running {
+            generation,
+            best_fitness,
+            ..
+        } = *status
+        {
+            *status = WorkerStatus::Finished {
+                generation,
+                best_fitness,
+            };
+        }
+    }
+
+    fn cell_finished(&self, mutation_index: usize, crossover_index: usize, score: f64) {
+        let mut state = self.state.lock().unwrap();
+        state.scores[mutation_index][crossover_index] = Some(score);
+        // Ties go to the later cell, like in the sweep itself
+        if state.best.is_none_or(|best| score >= best.score) {
+            state.best = Some(BestCell {
+                mutation_rate: state.mutation_rates[mutation_index],
+                crossover_rate: state.crossover_rates[crossover_index],
+                score,
+            });
+        }
+    }
+
+    fn should_abort(&self) -> bool {
+        self.abort.load(Ordering::SeqCst)
+    }
+}
+
+/// Draws `values` as `width` block characters scaled to `range`. Longer series are averaged into
+/// `width` buckets.
+pub fn sparkline(values: &[f64], width: usize, range: (f64, f64)) -> String {
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let buckets = values.len().min(width);
+    let (low, high) = range;
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * values.len() / buckets;
+            let end = ((bucket + 1) * values.len() / buckets).max(start + 1);
+            let value = values[start..end].iter().sum::<f64>() / (end - start) as f64;
+            let level = if high > low {
+                ((value - low) / (high - low) * (SPARK_LEVELS.len() - 1) as f64).round() as usize
+            } else {
+                SPARK_LEVELS.len() / 2
+            };
+            SPARK_LEVELS[level.min(SPARK_LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
+fn progress_bar(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(width - filled))
+}
+
+/// Heatmap color of a cell score, from red at 0 through yellow to green at 1.
+pub fn score_color(score: f64) -> Color {
+    let (r, g, b) = score_rgb(score);
+    Color::Rgb { r, g, b }
+}
+
+fn draw<W: Write>(out: &mut W, state: &DashboardState) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    queue!(out, MoveTo(0, 0))?;
+    for (row, line) in state
+        .lines(width as usize)
+        .iter()
+        .take(height as usize)
+        .enumerate()
+    {
+        queue!(out, MoveTo(0, row as u16))?;
+        let mut remaining = width as usize;
+        for span in line {
+            let text: String = span.text.chars().take(remaining).collect();
+            remaining -= text.chars().count();
+            match span.background {
+                Some(color) => queue!(
+                    out,
+                    SetBackgroundColor(color),
+                    SetForegroundColor(Color::Black),
+                    Print(text),
+                    ResetColor
+                )?,
+                None => queue!(out, Print(text))?,
+            }
+        }
+        queue!(out, Clear(ClearType::UntilNewLine))?;
+    }
+    queue!(out, Clear(ClearType::FromCursorDown))?;
+    out.flush()
+}
+
+/// Reads the pending key presses, returning whether one of them asks to abort.
+fn abort_requested() -> io::Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            let ctrl_c =
+                key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if key.kind == KeyEventKind::Press
+                && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+            {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn run_terminal<W: Write>(dashboard: &Dashboard, out: &mut W, stop: &AtomicBool) -> io::Result<()> {
+    loop {
+        draw(out, &dashboard.snapshot())?;
+        if stop.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        if event::poll(REFRESH_INTERVAL)? && abort_requested()? {
+            dashboard.abort();
+        }
+    }
+}
+
+/// The drawing thread started by [`spawn`]. Dropping it restores the terminal as well.
+pub struct DashboardTerminal {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl DashboardTerminal {
+    /// Draws the final state, restores the terminal and reports any error of the drawing thread.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.stop_thread()
+    }
+
+    fn stop_thread(&mut self) -> io::Result<()> {
+        self.stop.store(true, Ordering::SeqCst);
+        match self.thread.take() {
+            Some(thread) => thread
+                .join()
+                .unwrap_or_else(|_| Err(io::Error::other("the dashboard thread panicked"))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for DashboardTerminal {
+    fn drop(&mut self) {
+        let _ = self.stop_thread();
+    }
+}
+
+/// Switches `out` to a full-screen dashboard of `dashboard`, redrawn ten times per second from
+/// its own thread until [`DashboardTerminal::finish`].
+pub fn spawn<W: Write + Send + 'static>(
+    dashboard: Arc<Dashboard>,
+    mut out: W,
+) -> io::Result<DashboardTerminal> {
+    terminal::enable_raw_mode()?;
+    if let Err(e) = execute!(out, EnterAlternateScreen, Hide) {
+        let _ = terminal::disable_raw_mode();
+        return Err(e);
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    let thread = thread::spawn(move || {
+        let result = run_terminal(&dashboard, &mut out, &thread_stop);
+        // Restore the terminal even if drawing failed
+        let restored = execute!(out, Show, LeaveAlternateScreen);
+        let raw_mode = terminal::disable_raw_mode();
+        result.and(restored).and(raw_mode)
+    });
+    Ok(DashboardTerminal {
+        stop,
+        thread: Some(thread),
+    })
+}
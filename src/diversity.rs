@@ -0,0 +1,124 @@
+//! Population diversity measures, to spot premature convergence.
+//!
+//! All measures work on the per-locus allele frequencies, so they cost a single pass over the
+//! population except for the count of unique genomes.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Diversity of one population.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Diversity {
+    /// Average number of differing genes over every pair of genomes.
+    pub mean_hamming_distance: f64,
+    /// Average binary entropy of the loci in bits, from 0.0 (every genome agrees on every gene)
+    /// to 1.0 (every locus is split half and half).
+    pub mean_locus_entropy: f64,
+    pub unique_genomes: usize,
+    /// Standard deviation of the frequency of ones over the loci.
+    pub allele_frequency_spread: f64,
+}
+
+impl fmt::Display for Diversity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Hamming {:.2}, Entropy {:.3}, Unique {}, Allele Spread {:.3}",
+            self.mean_hamming_distance,
+            self.mean_locus_entropy,
+            self.unique_genomes,
+            self.allele_frequency_spread
+        )
+    }
+}
+
+/// Number of positions at which the genomes differ. Extra genes of the longer genome are ignored.
+pub fn hamming_distance(genome1: &[u8], genome2: &[u8]) -> usize {
+    genome1
+        .iter()
+        .zip(genome2)
+        .filter(|(gene1, gene2)| gene1 != gene2)
+        .count()
+}
+
+/// Share of ones at every locus. Genomes shorter than the first one are ignored past their end.
+pub fn allele_frequencies(population: &[Vec<u8>]) -> Vec<f64> {
+    let genome_length = population.first().map_or(0, Vec::len);
+    let mut ones = vec![0usize; genome_length];
+    for genome in population {
+        for (count, &gene) in ones.iter_mut().zip(genome) {
+            *count += gene as usize;
+        }
+    }
+    ones.iter()
+        .map(|&count| count as f64 / population.len() as f64)
+        .collect()
+}
+
+/// Binary entropy in bits of every locus.
+pub fn locus_entropy(population: &[Vec<u8>]) -> Vec<f64> {
+    allele_frequencies(population)
+        .into_iter()
+        .map(binary_entropy)
+        .collect()
+}
+
+fn binary_entropy(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    -(p * p.log2() + (1.0 - p) * (1.0 - p).log2())
+}
+
+/// Computes every diversity measure of the population.
+pub fn population_diversity(population: &[Vec<u8>]) -> Diversity {
+    if population.is_empty() {
+        return Diversity::default();
+    }
+    let frequencies = allele_frequencies(population);
+    let loci = frequencies.len() as f64;
+    let size = population.len() as f64;
+
+    // A locus with k ones contributes k * (n - k) differing pairs
+    let pairs = size * (size - 1.0) / 2.0;
+    let mean_hamming_distance = if pairs > 0.0 {
+        frequencies
+            .iter()
+            .map(|&p| {
+                let ones = (p * size).round();
+                ones * (size - ones)
+            })
+            .sum::<f64>()
+            / pairs
+    } else {
+        0.0
+    };
+
+    let (mean_locus_entropy, allele_frequency_spread) = if frequencies.is_empty() {
+        (0.0, 0.0)
+    } else {
+        let mean_entropy = frequencies.iter().map(|&p| binary_entropy(p)).sum::<f64>() / loci;
+        let mean_frequency = frequencies.iter().sum::<f64>() / loci;
+        let variance = frequencies
+            .iter()
+            .map(|&p| (p - mean_frequency).powi(2))
+            .sum::<f64>()
+            / loci;
+        (mean_entropy, variance.sqrt())
+    };
+
+    let unique_genomes = population
+        .iter()
+        .map(Vec::as_slice)
+        .collect::<HashSet<&[u8]>>()
+        .len();
+
+    Diversity {
+        mean_hamming_distance,
+        mean_locus_entropy,
+        unique_genomes,
+        allele_frequency_spread,
+    }
+}
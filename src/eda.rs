@@ -0,0 +1,309 @@
+//! Estimation-of-distribution algorithms: UMDA, the compact GA and PBIL.
+//!
+//! Instead of recombining individuals, these sample every generation from a vector of per-gene
+//! probabilities of a one and learn that vector from the fittest samples. They implement
+//! [`Replacement`], so they run through [`GaConfig`](crate::config::GaConfig),
+//! [`Evolution`](crate::evolution::Evolution), the termination criteria and
+//! [`Results`](crate::Results) exactly like the genetic algorithm, whose selection, crossover and
+//! mutation operators they ignore. The probability vector is kept in
+//! [`GaState::model`](crate::GaState::model).
+//!
+//! UMDA and the compact GA keep every probability within `[1/n, 1 - 1/n]`, so their samples never
+//! all agree and the population mean stays below a target generation fitness close to 1.0. They
+//! count a run as solved as soon as one sample is optimal, see
+//! [`Replacement::solved_by_best_genome`].
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//! use one_max_genetic_algorithm_rust::eda::Umda;
+//!
+//! let outcome = GaConfig::builder()
+//!     .genome_length(20)
+//!     .replacement(Umda::new(0.5))
+//!     .seed(1)
+//!     .build()
+//!     .unwrap()
+//!     .run();
+//! assert!(outcome.best_fitness > 0.5);
+//! ```
+
+use rand::{Rng, RngCore};
+
+use crate::diversity::allele_frequencies;
+use crate::fitness::Evaluator;
+use crate::operators::{Operators, Replacement};
+
+/// Draws a genome with gene `i` set with probability `model[i]`.
+pub fn sample_genome(model: &[f64], rng: &mut dyn RngCore) -> Vec<u8> {
+    model.iter().map(|&p| rng.gen_bool(p) as u8).collect()
+}
+
+/// Keeps every probability within `[1/n, 1 - 1/n]` so no gene gets fixed for good.
+fn clamp_to_margins(model: &mut [f64]) {
+    if model.len() < 2 {
+        return;
+    }
+    let margin = 1.0 / model.len() as f64;
+    for p in model.iter_mut() {
+        *p = p.clamp(margin, 1.0 - margin);
+    }
+}
+
+/// Estimates the model from the population when it is empty, at the start of a run or after the
+/// population was replaced.
+fn ensure_model(model: &mut Vec<f64>, population: &[Vec<u8>]) {
+    if model.is_empty() {
+        *model = allele_frequencies(population);
+        clamp_to_margins(model);
+    }
+}
+
+fn sample_population(
+    model: &[f64],
+    population_size: usize,
+    evaluator: &mut Evaluator,
+    evaluations: &mut u64,
+    rng: &mut dyn RngCore,
+) -> (Vec<Vec<u8>>, Vec<f64>) {
+    let population: Vec<Vec<u8>> = (0..population_size)
+        .map(|_| sample_genome(model, rng))
+        .collect();
+    let fitness_values = evaluator.evaluate_population(&population, evaluations);
+    (population, fitness_values)
+}
+
+/// Indices of the `count` fittest individuals, fittest first.
+fn fittest(fitness_values: &[f64], count: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..fitness_values.len()).collect();
+    indices.sort_by(|&a, &b| fitness_values[b].total_cmp(&fitness_values[a]));
+    indices.truncate(count.max(1));
+    indices
+}
+
+/// Univariate marginal distribution algorithm: the model is the allele frequencies of the best
+/// `selection_ratio` of the population, from which the whole next population is sampled.
+#[derive(Clone, Debug)]
+pub struct Umda {
+    pub selection_ratio: f64,
+}
+
+impl Umda {
+    pub fn new(selection_ratio: f64) -> Self {
+        Umda { selection_ratio }
+    }
+}
+
+impl Replacement for Umda {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        self.replace_with_model(
+            &mut Vec::new(),
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+        )
+    }
+
+    fn replace_with_model(
+        &self,
+        model: &mut Vec<f64>,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let selected_count = (self.selection_ratio * population.len() as f64).round() as usize;
+        let selected: Vec<Vec<u8>> = fittest(fitness_values, selected_count)
+            .into_iter()
+            .map(|index| population[index].clone())
+            .collect();
+        *model = allele_frequencies(&selected);
+        clamp_to_margins(model);
+        sample_population(model, population_size, evaluator, evaluations, rng)
+    }
+}
+
+/// Compact genetic algorithm: every pair of samples shifts the model by
+/// `1 / virtual_population_size` towards the fitter one at every gene where they differ. One
+/// generation samples `population_size` individuals, so generations cost as many evaluations as
+/// those of the genetic algorithm.
+#[derive(Clone, Debug)]
+pub struct CompactGa {
+    pub virtual_population_size: usize,
+}
+
+impl CompactGa {
+    pub fn new(virtual_population_size: usize) -> Self {
+        CompactGa {
+            virtual_population_size,
+        }
+    }
+}
+
+impl Replacement for CompactGa {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        self.replace_with_model(
+            &mut Vec::new(),
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+        )
+    }
+
+    fn replace_with_model(
+        &self,
+        model: &mut Vec<f64>,
+        population_size: usize,
+        population: &[Vec<u8>],
+        _fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        ensure_model(model, population);
+        let step = 1.0 / self.virtual_population_size.max(1) as f64;
+        let mut new_population = Vec::with_capacity(population_size);
+        let mut new_fitness_values = Vec::with_capacity(population_size);
+
+        while new_population.len() < population_size {
+            // An odd population size ends with a single sample that does not update the model
+            let pair_size = (population_size - new_population.len()).min(2);
+            let (pair, pair_fitness) =
+                sample_population(model, pair_size, evaluator, evaluations, rng);
+            if let [first, second] = pair.as_slice() {
+                let (winner, loser) = if pair_fitness[0] >= pair_fitness[1] {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                for ((p, &winner_gene), &loser_gene) in model.iter_mut().zip(winner).zip(loser) {
+                    if winner_gene != loser_gene {
+                        *p += if winner_gene == 1 { step } else { -step };
+                    }
+                }
+                clamp_to_margins(model);
+            }
+            new_population.extend(pair);
+            new_fitness_values.extend(pair_fitness);
+        }
+        (new_population, new_fitness_values)
+    }
+}
+
+/// Population-based incremental learning: the model moves towards the mean of the `best_count`
+/// fittest samples of every generation by `learning_rate`, then every probability is shifted by
+/// `mutation_shift` towards a random bit with probability `mutation_probability`.
+#[derive(Clone, Debug)]
+pub struct Pbil {
+    pub learning_rate: f64,
+    pub best_count: usize,
+    pub mutation_probability: f64,
+    pub mutation_shift: f64,
+}
+
+impl Pbil {
+    /// PBIL learning from the best sample, with Baluja's mutation probability of 0.02 and shift
+    /// of 0.05.
+    pub fn new(learning_rate: f64) -> Self {
+        Pbil {
+            learning_rate,
+            best_count: 1,
+            mutation_probability: 0.02,
+            mutation_shift: 0.05,
+        }
+    }
+}
+
+impl Replacement for Pbil {
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        self.replace_with_model(
+            &mut Vec::new(),
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+        )
+    }
+
+    fn replace_with_model(
+        &self,
+        model: &mut Vec<f64>,
+        population_size: usize,
+        population: &[Vec<u8>],
+        _fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        ensure_model(model, population);
+        let (new_population, new_fitness_values) =
+            sample_population(model, population_size, evaluator, evaluations, rng);
+
+        let best: Vec<Vec<u8>> = fittest(&new_fitness_values, self.best_count)
+            .into_iter()
+            .filter_map(|index| new_population.get(index).cloned())
+            .collect();
+        if !best.is_empty() {
+            for (p, frequency) in model.iter_mut().zip(allele_frequencies(&best)) {
+                *p = (1.0 - self.learning_rate) * *p + self.learning_rate * frequency;
+            }
+        }
+        for p in model.iter_mut() {
+            if rng.gen_bool(self.mutation_probability.clamp(0.0, 1.0)) {
+                let bit = rng.gen_bool(0.5) as u8 as f64;
+                *p = (1.0 - self.mutation_shift) * *p + self.mutation_shift * bit;
+            }
+        }
+        (new_population, new_fitness_values)
+    }
+}
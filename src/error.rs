@@ -0,0 +1,53 @@
+//! Crate-level error type.
+//!
+//! Invalid parameters are rejected with a [`ConfigError`] before anything runs, and operations that
+//! also touch the file system, like a checkpointed sweep, return an [`Error`] wrapping it.
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::ConfigError;
+
+/// Everything that can go wrong in the crate.
+#[derive(Debug)]
+pub enum Error {
+    /// Parameters rejected by up-front validation.
+    Config(ConfigError),
+    /// A checkpoint could not be read or written.
+    Checkpoint { path: PathBuf, source: io::Error },
+    /// The checkpoint was created by a sweep with a different experiment config.
+    IncompatibleCheckpoint(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(error) => write!(f, "invalid configuration: {error}"),
+            Error::Checkpoint { path, source } => {
+                write!(f, "could not access checkpoint {}: {source}", path.display())
+            }
+            Error::IncompatibleCheckpoint(path) => write!(
+                f,
+                "checkpoint {} was created with a different experiment config. Remove it or run without --resume.",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Config(error) => Some(error),
+            Error::Checkpoint { source, .. } => Some(source),
+            Error::IncompatibleCheckpoint(_) => None,
+        }
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(error: ConfigError) -> Self {
+        Error::Config(error)
+    }
+}
@@ -0,0 +1,259 @@
+//! Step-wise execution of a run.
+//!
+//! [`Evolution`] owns the population and the rest of the run state and advances one generation
+//! per [`Iterator::next`] call, so callers can inspect or modify the population between steps and
+//! stop whenever they like.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::evolution::Evolution;
+//! use one_max_genetic_algorithm_rust::GaParameters;
+//!
+//! let parameters = GaParameters {
+//!     population_size: 20,
+//!     genome_length: 10,
+//!     max_generations: 100,
+//!     mutation_rate: 0.01,
+//!     crossover_rate: 0.6,
+//!     select_parent_mode: "tournament".to_string(),
+//!     target_generation_fitness: 0.9,
+//! };
+//! let mut evolution = Evolution::new(parameters, 42).unwrap();
+//! for stats in evolution.by_ref().take(5) {
+//!     println!("{}: {}", stats.generation, stats.best_fitness);
+//! }
+//! assert!(evolution.generation() <= 5);
+//! ```
+
+use std::sync::Arc;
+
+use tracing::{debug, info};
+
+use crate::config::{validate_parameters, ConfigError};
+use crate::diversity::{population_diversity, Diversity};
+use crate::fitness::{CacheStats, Evaluator};
+use crate::one_max_genetic_algorithm::{
+    get_best_fitness, log_best_values, GaParameters, GaState, GenerationStats,
+};
+use crate::operators::Operators;
+use crate::restart::{Restart, RestartEvent};
+use crate::termination::StopReason;
+
+/// A run that advances one generation per iteration until it is solved or out of generations.
+pub struct Evolution {
+    state: GaState,
+    operators: Arc<Operators>,
+    evaluator: Evaluator,
+}
+
+impl Evolution {
+    /// Starts a run with the default operators of the parameters.
+    pub fn new(parameters: GaParameters, seed: u64) -> Result<Self, ConfigError> {
+        let operators = Arc::new(Operators::from_parameters(&parameters));
+        Evolution::with_operators(parameters, seed, operators)
+    }
+
+    /// Starts a One-Max run with the given operators.
+    pub fn with_operators(
+        parameters: GaParameters,
+        seed: u64,
+        operators: Arc<Operators>,
+    ) -> Result<Self, ConfigError> {
+        Evolution::with_evaluator(parameters, seed, operators, Evaluator::default())
+    }
+
+    /// Starts a run with the given operators and fitness evaluator. The parameters are checked
+    /// with [`validate_parameters`] before the initial population is created.
+    pub fn with_evaluator(
+        parameters: GaParameters,
+        seed: u64,
+        operators: Arc<Operators>,
+        mut evaluator: Evaluator,
+    ) -> Result<Self, ConfigError> {
+        validate_parameters(&parameters)?;
+        Ok(Evolution {
+            state: GaState::with_evaluator(parameters, seed, &mut evaluator),
+            operators,
+            evaluator,
+        })
+    }
+
+    /// Continues a One-Max run from a snapshot with the default operators of its parameters.
+    pub fn from_state(state: GaState) -> Self {
+        let operators = Arc::new(Operators::from_parameters(&state.parameters));
+        Evolution::from_state_with(state, operators, Evaluator::default())
+    }
+
+    /// Continues a run from a snapshot with the given operators and fitness evaluator.
+    pub fn from_state_with(
+        state: GaState,
+        operators: Arc<Operators>,
+        evaluator: Evaluator,
+    ) -> Self {
+        Evolution {
+            state,
+            operators,
+            evaluator,
+        }
+    }
+
+    pub fn state(&self) -> &GaState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> GaState {
+        self.state
+    }
+
+    /// Index of the next generation to run.
+    pub fn generation(&self) -> u32 {
+        self.state.generation
+    }
+
+    pub fn population(&self) -> &[Vec<u8>] {
+        &self.state.population
+    }
+
+    pub fn fitness_values(&self) -> &[f64] {
+        &self.state.fitness_values
+    }
+
+    /// Replaces the population and evaluates it. The next generation is bred from it, its size
+    /// becomes the population size of the following generations, and estimation-of-distribution
+    /// algorithms rebuild their model from it. An empty population is rejected.
+    pub fn set_population(&mut self, population: Vec<Vec<u8>>) -> Result<(), ConfigError> {
+        if population.is_empty() {
+            return Err(ConfigError::ZeroPopulation);
+        }
+        self.replace_population(population);
+        Ok(())
+    }
+
+    fn replace_population(&mut self, population: Vec<Vec<u8>>) {
+        self.state.parameters.population_size = population.len();
+        self.state.model.clear();
+        self.state.fitness_values = self
+            .evaluator
+            .evaluate_population(&population, &mut self.state.evaluations);
+        self.state.population = population;
+    }
+
+    /// Diversity of the current population.
+    pub fn diversity(&self) -> Diversity {
+        population_diversity(&self.state.population)
+    }
+
+    /// Replaces the population with the one built by `restart`. Its size becomes the population
+    /// size of the following generations.
+    pub fn restart(&mut self, restart: &mut dyn Restart) -> RestartEvent {
+        let best_fitness = get_best_fitness(&self.state.fitness_values);
+        let population = self.state.restart_population(restart);
+        self.replace_population(population);
+        RestartEvent {
+            generation: self.state.generation,
+            strategy: restart.name().to_string(),
+            best_fitness,
+            population_size: self.state.parameters.population_size,
+        }
+    }
+
+    pub fn evaluator(&self) -> &Evaluator {
+        &self.evaluator
+    }
+
+    /// Hits and misses of the fitness cache, if the evaluator has one.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.evaluator.cache_stats()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.is_finished()
+    }
+
+    /// Why the run stopped on its own, or `None` while it can still continue.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        if self.state.solved {
+            Some(StopReason::Solved)
+        } else if self.state.is_finished() {
+            Some(StopReason::MaxGenerations)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `(generation, generation_fitness, best_fitness)` as reported by `genetic_algorithm`.
+    pub fn outcome(&self) -> (u32, f64, f64) {
+        self.state.outcome()
+    }
+
+    /// Runs the remaining generations, or until `on_generation` returns false, and returns the
+    /// outcome. Every generation is logged at debug level, or at info level along with the
+    /// population diversity and the best genome at the end when `verbose` is set.
+    pub fn run<F: FnMut(&mut Evolution, &GenerationStats) -> bool>(
+        &mut self,
+        verbose: bool,
+        mut on_generation: F,
+    ) -> (u32, f64, f64) {
+        while let Some(stats) = self.next() {
+            if verbose {
+                info!(
+                    generation = stats.generation,
+                    best_fitness = stats.best_fitness,
+                    generation_fitness = stats.generation_fitness,
+                    diversity = %self.diversity(),
+                    "generation"
+                );
+            } else {
+                debug!(
+                    generation = stats.generation,
+                    best_fitness = stats.best_fitness,
+                    generation_fitness = stats.generation_fitness,
+                    "generation"
+                );
+            }
+            if !on_generation(self, &stats) {
+                break;
+            }
+        }
+
+        if verbose {
+            self.log_summary();
+        }
+        self.outcome()
+    }
+
+    fn log_summary(&self) {
+        let state = &self.state;
+        if state.solved {
+            let (generation, generation_fitness, _) = state.outcome();
+            info!(generation, "ideal solution found");
+            log_best_values(&state.fitness_values, &state.population, generation_fitness);
+        } else if !state.best_population.is_empty() {
+            info!(
+                generations = state.generation,
+                best_generation = state.best_generation,
+                "no ideal solution found, best generation kept"
+            );
+            let best_fitness_values: Vec<f64> = state
+                .best_population
+                .iter()
+                .map(|genome| self.evaluator.evaluate(genome))
+                .collect();
+            log_best_values(
+                &best_fitness_values,
+                &state.best_population,
+                state.best_generation_fitness,
+            );
+        }
+    }
+}
+
+impl Iterator for Evolution {
+    type Item = GenerationStats;
+
+    fn next(&mut self) -> Option<GenerationStats> {
+        if self.state.is_finished() {
+            return None;
+        }
+        Some(self.state.step_with(&self.operators, &mut self.evaluator))
+    }
+}
@@ -0,0 +1,446 @@
+//! Fitness functions and how a population gets evaluated.
+//!
+//! [`Fitness`] is the problem being optimised, One-Max by default. [`Evaluator`] evaluates whole
+//! populations with it, can skip duplicate genomes through a bounded LRU [`FitnessCache`] and can
+//! spread the evaluations of a population over several threads. Fitness functions that are sums
+//! over the genes, like One-Max, can also derive the fitness of a child from its parents through
+//! [`Fitness::evaluate_change`] instead of evaluating it from scratch.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::thread;
+
+use crate::constraints::Penalty;
+use crate::one_max_genetic_algorithm::{get_genome_fitness, get_target_fitness};
+
+/// How a child genome was derived from genomes of known fitness, as reported by the operators.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change<'a> {
+    /// The child is `parent` with the genes at `flipped` inverted.
+    Flips {
+        parent: &'a [u8],
+        parent_fitness: f64,
+        flipped: &'a [usize],
+    },
+    /// The child is `first[..point]` followed by `second[point..]`.
+    OnePointCrossover {
+        first: &'a [u8],
+        first_fitness: f64,
+        second: &'a [u8],
+        second_fitness: f64,
+        point: usize,
+    },
+}
+
+/// A fitness function over bitstring genomes. Higher is better.
+pub trait Fitness: Send + Sync {
+    fn evaluate(&self, genome: &[u8]) -> f64;
+
+    /// Fitness of an optimal genome. A run is solved once its best genome reaches it.
+    fn target(&self) -> f64 {
+        get_target_fitness()
+    }
+
+    /// Total amount by which the genome violates the problem's constraints, 0.0 when it is
+    /// feasible. Unconstrained problems keep the default of 0.0.
+    fn violation(&self, _genome: &[u8]) -> f64 {
+        0.0
+    }
+
+    /// Fitness of a child derived through `change`, computed from the parents' fitness without a
+    /// full evaluation. Must equal what [`Fitness::evaluate`] returns for the child. `None`, the
+    /// default, falls back to a full evaluation, and is also what a change that does not fit its
+    /// genomes, like a crossover point past their end, should get instead of a panic.
+    fn evaluate_change(&self, _change: &Change<'_>) -> Option<f64> {
+        None
+    }
+}
+
+/// The share of ones in the genome.
+pub struct OneMax;
+
+impl OneMax {
+    fn ones(genome: &[u8]) -> i64 {
+        genome.iter().map(|&gene| gene as i64).sum()
+    }
+
+    // Works on the count of ones so repeated changes do not accumulate rounding errors
+    fn ones_from_fitness(fitness: f64, length: usize) -> i64 {
+        (fitness * length as f64).round() as i64
+    }
+}
+
+impl Fitness for OneMax {
+    fn evaluate(&self, genome: &[u8]) -> f64 {
+        get_genome_fitness(genome)
+    }
+
+    fn evaluate_change(&self, change: &Change<'_>) -> Option<f64> {
+        let (length, ones) = match *change {
+            Change::Flips {
+                parent,
+                parent_fitness,
+                flipped,
+            } => {
+                // A change reported by a faulty operator is evaluated in full instead
+                if flipped.iter().any(|&position| position >= parent.len()) {
+                    return None;
+                }
+                let ones = OneMax::ones_from_fitness(parent_fitness, parent.len());
+                let delta: i64 = flipped
+                    .iter()
+                    .map(|&position| 1 - 2 * parent[position] as i64)
+                    .sum();
+                (parent.len(), ones + delta)
+            }
+            Change::OnePointCrossover {
+                first,
+                first_fitness,
+                second,
+                second_fitness,
+                point,
+            } => {
+                let length = first.len();
+                if point > length || second.len() != length {
+                    return None;
+                }
+                // Swap the shorter of the two segments
+                let ones = if point <= length - point {
+                    OneMax::ones_from_fitness(second_fitness, length)
+                        - OneMax::ones(&second[..point])
+                        + OneMax::ones(&first[..point])
+                } else {
+                    OneMax::ones_from_fitness(first_fitness, length) - OneMax::ones(&first[point..])
+                        + OneMax::ones(&second[point..])
+                };
+                (length, ones)
+            }
+        };
+        if length == 0 {
+            return Some(0.0);
+        }
+        Some(ones as f64 / length as f64)
+    }
+}
+
+/// Hit and miss counters of a [`FitnessCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups answered by the cache, from 0.0 to 1.0.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+/// Bounded least recently used cache of fitness values keyed by genome hash.
+pub struct FitnessCache {
+    capacity: usize,
+    // Genome hash -> (fitness, last use)
+    entries: HashMap<u64, (f64, u64)>,
+    // Last use -> genome hash, oldest first
+    recency: BTreeMap<u64, u64>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl FitnessCache {
+    pub fn new(capacity: usize) -> Self {
+        FitnessCache {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    fn genome_hash(genome: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        genome.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether the genome is cached, without counting a lookup or marking it as used.
+    pub fn contains(&self, genome: &[u8]) -> bool {
+        self.entries
+            .contains_key(&FitnessCache::genome_hash(genome))
+    }
+
+    /// Cached fitness of the genome, marking it as recently used.
+    pub fn get(&mut self, genome: &[u8]) -> Option<f64> {
+        let key = FitnessCache::genome_hash(genome);
+        self.clock += 1;
+        match self.entries.get_mut(&key) {
+            Some((fitness, last_use)) => {
+                self.recency.remove(last_use);
+                *last_use = self.clock;
+                self.recency.insert(self.clock, key);
+                self.stats.hits += 1;
+                Some(*fitness)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores the fitness of the genome, evicting the least recently used entry when full.
+    pub fn insert(&mut self, genome: &[u8], fitness: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = FitnessCache::genome_hash(genome);
+        self.clock += 1;
+        if let Some((_, last_use)) = self.entries.insert(key, (fitness, self.clock)) {
+            self.recency.remove(&last_use);
+        } else if self.entries.len() > self.capacity {
+            if let Some((_, oldest_key)) = self.recency.pop_first() {
+                self.entries.remove(&oldest_key);
+            }
+        }
+        self.recency.insert(self.clock, key);
+    }
+}
+
+/// Evaluates populations with a fitness function, optionally through a cache and on several
+/// threads.
+pub struct Evaluator {
+    fitness: Arc<dyn Fitness>,
+    cache: Option<FitnessCache>,
+    threads: usize,
+    penalty: Option<Box<dyn Penalty>>,
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        Evaluator::new(Arc::new(OneMax))
+    }
+}
+
+impl Evaluator {
+    pub fn new(fitness: Arc<dyn Fitness>) -> Self {
+        Evaluator {
+            fitness,
+            cache: None,
+            threads: 1,
+            penalty: None,
+        }
+    }
+
+    /// Caches up to `capacity` fitness values. Worth it for expensive fitness functions, since
+    /// late in a run most of the population consists of identical genomes.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(FitnessCache::new(capacity));
+        self
+    }
+
+    /// Evaluates populations on up to `threads` scoped threads. This is independent of the
+    /// threads of a sweep and only pays off when the fitness function is expensive. Values below
+    /// 1 are treated as 1.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Subtracts `penalty` of the constraint violation from every fitness value. Children are
+    /// then always evaluated in full, since derived values would miss the penalty. The cache
+    /// keeps unpenalised values, so penalties that change over the run stay correct.
+    pub fn with_penalty(mut self, penalty: Box<dyn Penalty>) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub fn fitness(&self) -> &dyn Fitness {
+        self.fitness.as_ref()
+    }
+
+    pub fn target(&self) -> f64 {
+        self.fitness.target()
+    }
+
+    /// Constraint violation of `genome`, 0.0 when it is feasible.
+    pub fn violation(&self, genome: &[u8]) -> f64 {
+        self.fitness.violation(genome)
+    }
+
+    /// Penalised fitness of a single genome, without the cache or counting an evaluation.
+    pub fn evaluate(&self, genome: &[u8]) -> f64 {
+        let genome_fitness = self.fitness.evaluate(genome);
+        match &self.penalty {
+            Some(penalty) => genome_fitness - penalty.penalty(self.fitness.violation(genome)),
+            None => genome_fitness,
+        }
+    }
+
+    /// Whether the penalty, if any, changes from one generation to the next.
+    pub fn has_stateful_penalty(&self) -> bool {
+        self.penalty
+            .as_ref()
+            .is_some_and(|penalty| penalty.is_stateful())
+    }
+
+    /// Tells the penalty whether the best individual of the generation just evaluated is
+    /// feasible. Called once per generation.
+    pub fn update_penalty(&mut self, population: &[Vec<u8>], fitness_values: &[f64]) {
+        let Some(penalty) = self.penalty.as_mut() else {
+            return;
+        };
+        let best = fitness_values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index);
+        if let Some(best) = best {
+            penalty.update(self.fitness.violation(&population[best]) <= 0.0);
+        }
+    }
+
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(FitnessCache::stats)
+    }
+
+    /// Fitness of every genome, in population order. The number of fitness function calls is
+    /// added to `evaluations`; cache hits are not counted. The values, counts and cache
+    /// statistics are the same whatever the number of threads.
+    pub fn evaluate_population(
+        &mut self,
+        population: &[Vec<u8>],
+        evaluations: &mut u64,
+    ) -> Vec<f64> {
+        let genomes: Vec<&[u8]> = population.iter().map(Vec::as_slice).collect();
+        self.evaluate_genomes(&genomes, evaluations)
+    }
+
+    /// Same as [`Evaluator::evaluate_population`] for a bred population, reusing the fitness of
+    /// the children already derived through [`Fitness::evaluate_change`]. Derived values bypass
+    /// the cache but still count as evaluations.
+    pub fn evaluate_offspring(
+        &mut self,
+        population: &[Vec<u8>],
+        known_fitness: &[Option<f64>],
+        evaluations: &mut u64,
+    ) -> Vec<f64> {
+        if self.penalty.is_some() {
+            return self.evaluate_population(population, evaluations);
+        }
+        let unknown: Vec<&[u8]> = population
+            .iter()
+            .zip(known_fitness)
+            .filter(|(_, known)| known.is_none())
+            .map(|(genome, _)| genome.as_slice())
+            .collect();
+        *evaluations += (population.len() - unknown.len()) as u64;
+        let mut unknown_fitness = self.evaluate_genomes(&unknown, evaluations).into_iter();
+        known_fitness
+            .iter()
+            .map(|known| known.unwrap_or_else(|| unknown_fitness.next().unwrap()))
+            .collect()
+    }
+
+    fn evaluate_genomes(&mut self, population: &[&[u8]], evaluations: &mut u64) -> Vec<f64> {
+        let mut fitness_values = self.unpenalised_fitness(population, evaluations);
+        if let Some(penalty) = &self.penalty {
+            for (genome_fitness, genome) in fitness_values.iter_mut().zip(population) {
+                *genome_fitness -= penalty.penalty(self.fitness.violation(genome));
+            }
+        }
+        fitness_values
+    }
+
+    fn unpenalised_fitness(&mut self, population: &[&[u8]], evaluations: &mut u64) -> Vec<f64> {
+        let fitness = self.fitness.as_ref();
+        let threads = self.threads;
+        match self.cache.as_mut() {
+            None => {
+                *evaluations += population.len() as u64;
+                evaluate_genomes(fitness, population, threads)
+            }
+            Some(cache) => {
+                // Evaluate the distinct uncached genomes up front, then go through the cache in
+                // population order so hits, misses and evictions match a sequential evaluation
+                let mut pending: HashMap<&[u8], f64> = HashMap::new();
+                if threads > 1 {
+                    let mut genomes: Vec<&[u8]> = population
+                        .iter()
+                        .copied()
+                        .filter(|genome| !cache.contains(genome))
+                        .collect();
+                    genomes.sort_unstable();
+                    genomes.dedup();
+                    let values = evaluate_genomes(fitness, &genomes, threads);
+                    pending = genomes.into_iter().zip(values).collect();
+                }
+                population
+                    .iter()
+                    .map(|genome| {
+                        cache.get(genome).unwrap_or_else(|| {
+                            *evaluations += 1;
+                            let genome_fitness = match pending.get(genome) {
+                                Some(&genome_fitness) => genome_fitness,
+                                None => fitness.evaluate(genome),
+                            };
+                            cache.insert(genome, genome_fitness);
+                            genome_fitness
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Fitness of the genomes in order, split into contiguous chunks over up to `threads` threads.
+fn evaluate_genomes(fitness: &dyn Fitness, genomes: &[&[u8]], threads: usize) -> Vec<f64> {
+    if threads <= 1 || genomes.len() < 2 {
+        return genomes
+            .iter()
+            .map(|genome| fitness.evaluate(genome))
+            .collect();
+    }
+    let chunk_size = genomes.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = genomes
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|genome| fitness.evaluate(genome))
+                        .collect::<Vec<f64>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("fitness evaluation thread panicked"))
+            .collect()
+    })
+}
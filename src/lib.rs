@@ -0,0 +1,77 @@
+//! Genetic algorithm for the One Max problem.
+//!
+//! The crate is organised as follows:
+//!
+//! - [`one_max_genetic_algorithm`]: the operators (initialisation, selection, crossover,
+//!   mutation), [`genetic_algorithm`] itself and the resumable [`GaState`] it runs on.
+//! - [`config`]: [`GaConfig`](config::GaConfig), a validated builder-style alternative to the
+//!   positional arguments of [`genetic_algorithm`].
+//! - [`constraints`]: constraint violation penalties, feasibility tournaments and repair.
+//! - [`dashboard`]: the live terminal dashboard of a sweep.
+//! - [`diversity`]: population diversity measures.
+//! - [`error`]: [`Error`], the crate-level error type.
+//! - [`eda`]: the UMDA, compact GA and PBIL estimation-of-distribution algorithms.
+//! - [`evolution`]: [`Evolution`](evolution::Evolution), which advances a run one generation at
+//!   a time as an iterator.
+//! - [`fitness`]: the [`Fitness`](fitness::Fitness) trait, One-Max, and population evaluation with
+//!   an optional LRU cache.
+//! - [`local_search`]: randomized local search, the (1+1), (1+λ) and (μ+1) EAs, hill climbing
+//!   and simulated annealing baselines.
+//! - [`logging`]: structured logging of runs and sweeps to stderr, text or JSON lines files.
+//! - [`multi_objective`]: NSGA-II, multi-objective benchmarks and the hypervolume metric.
+//! - [`niching`]: fitness sharing, crowding and restricted tournament selection.
+//! - [`operators`]: the selection, crossover and mutation traits and their built-in versions.
+//! - [`plots`]: SVG convergence curves, score heatmaps and box plots of a sweep.
+//! - [`problems`]: the 0/1 knapsack and set cover benchmarks and their instance loaders.
+//! - [`report`]: the self-contained HTML report of a sweep.
+//! - [`restart`]: restart strategies for stagnating runs.
+//! - [`results`]: [`Results`], the aggregation of several runs into a single score.
+//! - [`scaling`]: runtime scaling analysis over genome lengths and complexity model fitting.
+//! - [`solver`]: the algorithm families a sweep can run.
+//! - [`sweep`]: the multithreaded grid sweep over mutation and crossover rates.
+//! - [`termination`]: composable termination criteria and the reason a run stopped.
+//! - [`checkpoint`]: persistence of sweeps and single runs.
+//! - [`utils`]: helpers to build parameter grids and split work into thread batches.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::{genetic_algorithm, Results};
+//!
+//! let mut results = Results::new(100, 1.0);
+//! let (generation, generation_fitness, best_fitness) =
+//!     genetic_algorithm(20, 10, 100, 0.01, 0.6, "tournament", 0.9, false).unwrap();
+//! results.add_result(generation, generation_fitness, best_fitness);
+//! assert!(results.get_score() > 0.0);
+//! ```
+
+pub mod checkpoint;
+pub mod config;
+pub mod constraints;
+pub mod dashboard;
+pub mod diversity;
+pub mod eda;
+pub mod error;
+pub mod evolution;
+pub mod fitness;
+pub mod local_search;
+pub mod logging;
+pub mod multi_objective;
+pub mod niching;
+pub mod one_max_genetic_algorithm;
+pub mod operators;
+pub mod plots;
+pub mod problems;
+pub mod report;
+pub mod restart;
+pub mod results;
+pub mod scaling;
+pub mod solver;
+pub mod sweep;
+pub mod termination;
+pub mod utils;
+
+pub use config::{ConfigError, GaConfig, RunOutcome};
+pub use error::Error;
+pub use evolution::Evolution;
+pub use one_max_genetic_algorithm::{genetic_algorithm, GaParameters, GaState};
+pub use results::Results;
+pub use sweep::{process_genetic_algorithm, ExperimentConfig};
@@ -0,0 +1,493 @@
+//! Single-trajectory and small-population baselines: randomized local search, the (1+1),
+//! (1+λ) and (μ+1) EAs, hill climbing and simulated annealing.
+//!
+//! Like the algorithms of [`crate::eda`] they implement [`Replacement`], so they run through the
+//! same configs, terminations and [`Results`](crate::Results) as the genetic algorithm. Every
+//! generation spends at least `population_size` evaluations, the budget of a GA generation. The
+//! trajectory algorithms report the current solution after each of those evaluations as the
+//! population, the last one being the solution the next generation continues from, while the
+//! (μ+1) EA keeps a population of `population_size` individuals.
+//!
+//! The mean fitness of a trajectory generation averages solutions the search has already left, so
+//! it lags behind the current one and reads as a worse `generation_fitness` than a GA population
+//! of the same quality. Trajectory runs are therefore solved as soon as the search reaches the
+//! optimum, see [`Replacement::solved_by_best_genome`], and hill climbing stays there instead of
+//! restarting.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//! use one_max_genetic_algorithm_rust::local_search::OnePlusOneEa;
+//!
+//! let outcome = GaConfig::builder()
+//!     .genome_length(20)
+//!     .replacement(OnePlusOneEa::new())
+//!     .seed(1)
+//!     .build()
+//!     .unwrap()
+//!     .run();
+//! assert!(outcome.best_fitness > 0.5);
+//! ```
+
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use crate::fitness::Evaluator;
+use crate::one_max_genetic_algorithm::{init_population_with_rng, mutate_with_rng};
+use crate::operators::{Operators, Replacement};
+
+/// Current solutions after every evaluation of a generation.
+struct Trajectory<'a> {
+    evaluator: &'a mut Evaluator,
+    evaluations: &'a mut u64,
+    population: Vec<Vec<u8>>,
+    fitness_values: Vec<f64>,
+}
+
+impl<'a> Trajectory<'a> {
+    fn new(evaluator: &'a mut Evaluator, evaluations: &'a mut u64) -> Self {
+        Trajectory {
+            evaluator,
+            evaluations,
+            population: Vec::new(),
+            fitness_values: Vec::new(),
+        }
+    }
+
+    fn evaluate(&mut self, genome: &[u8]) -> f64 {
+        self.evaluator
+            .evaluate_population(&[genome.to_vec()], self.evaluations)[0]
+    }
+
+    /// Records the current solution once per evaluation spent since the last call.
+    fn record(&mut self, current: &[u8], current_fitness: f64, evaluations: usize) {
+        for _ in 0..evaluations.max(1) {
+            self.population.push(current.to_vec());
+            self.fitness_values.push(current_fitness);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.population.len()
+    }
+
+    /// Keeps the last `population_size` entries, so the current solution stays last.
+    fn finish(mut self, population_size: usize) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let excess = self.population.len().saturating_sub(population_size);
+        self.population.drain(..excess);
+        self.fitness_values.drain(..excess);
+        (self.population, self.fitness_values)
+    }
+}
+
+/// The solution a trajectory algorithm continues from: the last individual of the population.
+fn current(population: &[Vec<u8>], fitness_values: &[f64]) -> (Vec<u8>, f64) {
+    match (population.last(), fitness_values.last()) {
+        (Some(genome), Some(&genome_fitness)) => (genome.clone(), genome_fitness),
+        _ => (Vec::new(), f64::NEG_INFINITY),
+    }
+}
+
+/// Flips every gene with probability `mutation_strength / n`.
+fn standard_bit_mutation(genome: &[u8], mutation_strength: f64, rng: &mut dyn RngCore) -> Vec<u8> {
+    let rate = (mutation_strength / genome.len().max(1) as f64).min(1.0);
+    mutate_with_rng(genome, rate, rng)
+}
+
+fn flip(genome: &[u8], index: usize) -> Vec<u8> {
+    let mut neighbour = genome.to_vec();
+    neighbour[index] = 1 - neighbour[index];
+    neighbour
+}
+
+/// Runs `iteration` until the generation's evaluation budget is spent. Every iteration moves the
+/// current solution and returns how many evaluations it used.
+fn run_trajectory<F>(
+    population_size: usize,
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    evaluator: &mut Evaluator,
+    evaluations: &mut u64,
+    mut iteration: F,
+) -> (Vec<Vec<u8>>, Vec<f64>)
+where
+    F: FnMut(&mut Trajectory, &mut Vec<u8>, &mut f64) -> usize,
+{
+    let (mut genome, mut genome_fitness) = current(population, fitness_values);
+    let mut trajectory = Trajectory::new(evaluator, evaluations);
+    if genome.is_empty() {
+        return trajectory.finish(population_size);
+    }
+    while trajectory.len() < population_size.max(1) {
+        let used = iteration(&mut trajectory, &mut genome, &mut genome_fitness);
+        trajectory.record(&genome, genome_fitness, used);
+    }
+    trajectory.finish(population_size)
+}
+
+/// Randomized local search: flips one random gene and keeps the result unless it is worse.
+#[derive(Clone, Debug, Default)]
+pub struct RandomizedLocalSearch;
+
+impl Replacement for RandomizedLocalSearch {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        run_trajectory(
+            population_size,
+            population,
+            fitness_values,
+            evaluator,
+            evaluations,
+            |trajectory, genome, genome_fitness| {
+                let neighbour = flip(genome, rng.gen_range(0..genome.len()));
+                let neighbour_fitness = trajectory.evaluate(&neighbour);
+                if neighbour_fitness >= *genome_fitness {
+                    (*genome, *genome_fitness) = (neighbour, neighbour_fitness);
+                }
+                1
+            },
+        )
+    }
+}
+
+/// The (1+1) EA: standard bit mutation with rate `mutation_strength / n`, keeping the offspring
+/// unless it is worse.
+#[derive(Clone, Debug)]
+pub struct OnePlusOneEa {
+    pub mutation_strength: f64,
+}
+
+impl OnePlusOneEa {
+    /// The textbook (1+1) EA, with a mutation rate of 1/n.
+    pub fn new() -> Self {
+        OnePlusOneEa {
+            mutation_strength: 1.0,
+        }
+    }
+}
+
+impl Default for OnePlusOneEa {
+    fn default() -> Self {
+        OnePlusOneEa::new()
+    }
+}
+
+impl Replacement for OnePlusOneEa {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        OnePlusLambdaEa {
+            lambda: 1,
+            mutation_strength: self.mutation_strength,
+        }
+        .replace(
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+        )
+    }
+}
+
+/// The (1+λ) EA: `lambda` offspring by standard bit mutation per iteration, the best of which
+/// replaces the parent unless it is worse.
+#[derive(Clone, Debug)]
+pub struct OnePlusLambdaEa {
+    pub lambda: usize,
+    pub mutation_strength: f64,
+}
+
+impl OnePlusLambdaEa {
+    pub fn new(lambda: usize) -> Self {
+        OnePlusLambdaEa {
+            lambda,
+            mutation_strength: 1.0,
+        }
+    }
+}
+
+impl Replacement for OnePlusLambdaEa {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let lambda = self.lambda.max(1);
+        run_trajectory(
+            population_size,
+            population,
+            fitness_values,
+            evaluator,
+            evaluations,
+            |trajectory, genome, genome_fitness| {
+                let offspring: Vec<Vec<u8>> = (0..lambda)
+                    .map(|_| standard_bit_mutation(genome, self.mutation_strength, rng))
+                    .collect();
+                let offspring_fitness = trajectory
+                    .evaluator
+                    .evaluate_population(&offspring, trajectory.evaluations);
+                let best = (0..lambda)
+                    .max_by(|&a, &b| offspring_fitness[a].total_cmp(&offspring_fitness[b]))
+                    .unwrap();
+                if offspring_fitness[best] >= *genome_fitness {
+                    *genome = offspring[best].clone();
+                    *genome_fitness = offspring_fitness[best];
+                }
+                lambda
+            },
+        )
+    }
+}
+
+/// The (μ+1) EA with μ the population size: every iteration mutates a uniformly chosen parent by
+/// standard bit mutation, and the offspring replaces a worst individual unless it is worse. A
+/// generation runs μ iterations.
+#[derive(Clone, Debug)]
+pub struct MuPlusOneEa {
+    pub mutation_strength: f64,
+}
+
+impl MuPlusOneEa {
+    pub fn new() -> Self {
+        MuPlusOneEa {
+            mutation_strength: 1.0,
+        }
+    }
+}
+
+impl Default for MuPlusOneEa {
+    fn default() -> Self {
+        MuPlusOneEa::new()
+    }
+}
+
+impl Replacement for MuPlusOneEa {
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let mut population = population.to_vec();
+        let mut fitness_values = fitness_values.to_vec();
+        if population.is_empty() {
+            return (population, fitness_values);
+        }
+        for _ in 0..population_size.max(1) {
+            let parent = &population[rng.gen_range(0..population.len())];
+            let offspring = standard_bit_mutation(parent, self.mutation_strength, rng);
+            let offspring_fitness =
+                evaluator.evaluate_population(std::slice::from_ref(&offspring), evaluations)[0];
+            let worst = (0..population.len())
+                .min_by(|&a, &b| fitness_values[a].total_cmp(&fitness_values[b]))
+                .unwrap();
+            if offspring_fitness >= fitness_values[worst] {
+                population[worst] = offspring;
+                fitness_values[worst] = offspring_fitness;
+            }
+        }
+        (population, fitness_values)
+    }
+}
+
+/// Hill climbing over the one-gene-flip neighbourhood. A local optimum is left by restarting from
+/// a random genome, unless it reaches the evaluator's target.
+#[derive(Clone, Debug)]
+pub struct HillClimbing {
+    pub strategy: ImprovementStrategy,
+}
+
+/// Which improving neighbour a [`HillClimbing`] moves to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImprovementStrategy {
+    /// The first strictly better neighbour, scanning the neighbours in random order.
+    First,
+    /// The best neighbour, after evaluating all of them.
+    Best,
+}
+
+impl HillClimbing {
+    pub fn first_improvement() -> Self {
+        HillClimbing {
+            strategy: ImprovementStrategy::First,
+        }
+    }
+
+    pub fn best_improvement() -> Self {
+        HillClimbing {
+            strategy: ImprovementStrategy::Best,
+        }
+    }
+}
+
+impl Replacement for HillClimbing {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        run_trajectory(
+            population_size,
+            population,
+            fitness_values,
+            evaluator,
+            evaluations,
+            |trajectory, genome, genome_fitness| {
+                let mut order: Vec<usize> = (0..genome.len()).collect();
+                order.shuffle(rng);
+                let mut best: Option<(Vec<u8>, f64)> = None;
+                let mut used = 0;
+                for index in order {
+                    let neighbour = flip(genome, index);
+                    let neighbour_fitness = trajectory.evaluate(&neighbour);
+                    used += 1;
+                    let best_fitness = best.as_ref().map_or(*genome_fitness, |(_, f)| *f);
+                    if neighbour_fitness > best_fitness {
+                        best = Some((neighbour, neighbour_fitness));
+                        if self.strategy == ImprovementStrategy::First {
+                            break;
+                        }
+                    }
+                }
+                match best {
+                    Some((neighbour, neighbour_fitness)) => {
+                        (*genome, *genome_fitness) = (neighbour, neighbour_fitness);
+                    }
+                    None if *genome_fitness >= trajectory.evaluator.target() => {}
+                    None => {
+                        *genome = init_population_with_rng(1, genome.len(), rng).remove(0);
+                        *genome_fitness = trajectory.evaluate(genome);
+                        used += 1;
+                    }
+                }
+                used
+            },
+        )
+    }
+}
+
+/// How the temperature of a [`SimulatedAnnealing`] decreases with the number of evaluations `t`
+/// spent by the run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoolingSchedule {
+    /// `T0 * alpha^t`.
+    Geometric { alpha: f64 },
+    /// `T0 - rate * t`, never below zero.
+    Linear { rate: f64 },
+    /// `T0 / ln(e + t)`.
+    Logarithmic,
+}
+
+impl CoolingSchedule {
+    pub fn temperature(&self, initial_temperature: f64, t: u64) -> f64 {
+        let t = t as f64;
+        match *self {
+            CoolingSchedule::Geometric { alpha } => initial_temperature * alpha.powf(t),
+            CoolingSchedule::Linear { rate } => (initial_temperature - rate * t).max(0.0),
+            CoolingSchedule::Logarithmic => initial_temperature / (std::f64::consts::E + t).ln(),
+        }
+    }
+}
+
+/// Simulated annealing: flips one random gene and accepts a worse result with probability
+/// `exp(-loss / T)`, the temperature following `schedule`.
+#[derive(Clone, Debug)]
+pub struct SimulatedAnnealing {
+    pub initial_temperature: f64,
+    pub schedule: CoolingSchedule,
+}
+
+impl SimulatedAnnealing {
+    pub fn new(initial_temperature: f64, schedule: CoolingSchedule) -> Self {
+        SimulatedAnnealing {
+            initial_temperature,
+            schedule,
+        }
+    }
+}
+
+impl Replacement for SimulatedAnnealing {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        run_trajectory(
+            population_size,
+            population,
+            fitness_values,
+            evaluator,
+            evaluations,
+            |trajectory, genome, genome_fitness| {
+                let neighbour = flip(genome, rng.gen_range(0..genome.len()));
+                let neighbour_fitness = trajectory.evaluate(&neighbour);
+                let temperature = self
+                    .schedule
+                    .temperature(self.initial_temperature, *trajectory.evaluations);
+                let loss = *genome_fitness - neighbour_fitness;
+                let accept = loss <= 0.0
+                    || (temperature > 0.0 && rng.gen_bool((-loss / temperature).exp().min(1.0)));
+                if accept {
+                    (*genome, *genome_fitness) = (neighbour, neighbour_fitness);
+                }
+                1
+            },
+        )
+    }
+}
@@ -0,0 +1,116 @@
+//! Levelled, structured logging of runs and sweeps.
+//!
+//! The library never writes to stdout. Runs, sweep cells and whole sweeps are [`tracing`] spans
+//! carrying their parameters and seed, and generations, restarts and finished runs are events
+//! inside them. [`init`] installs a subscriber that sends them to any number of [`LogSink`]s, each
+//! filtered with the same `RUST_LOG`-style directives.
+//!
+//! ```no_run
+//! use one_max_genetic_algorithm_rust::logging::{init, LogConfig, LogFormat, LogSink};
+//!
+//! init(&LogConfig {
+//!     filter: "info".to_string(),
+//!     sinks: vec![
+//!         LogSink::Stderr,
+//!         LogSink::File {
+//!             path: "run.jsonl".into(),
+//!             format: LogFormat::Json,
+//!         },
+//!     ],
+//! })
+//! .unwrap();
+//! ```
+
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tracing::Subscriber;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::Registry;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+/// How events are written to a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable lines, one per event.
+    Text,
+    /// One JSON object per event, with the fields of the spans it happened in.
+    Json,
+}
+
+/// Destination of the log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogSink {
+    /// Human readable lines on stderr, coloured when it is a terminal.
+    Stderr,
+    /// File created, or truncated, when the subscriber is built.
+    File { path: PathBuf, format: LogFormat },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogConfig {
+    /// Directives in the `RUST_LOG` syntax, like `warn` or `info,one_max_genetic_algorithm_rust::sweep=debug`.
+    pub filter: String,
+    pub sinks: Vec<LogSink>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            filter: "warn".to_string(),
+            sinks: vec![LogSink::Stderr],
+        }
+    }
+}
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+fn filter(directives: &str) -> io::Result<EnvFilter> {
+    EnvFilter::try_new(directives).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid log filter {directives:?}: {e}"),
+        )
+    })
+}
+
+fn sink_layer(sink: &LogSink, directives: &str) -> io::Result<BoxedLayer> {
+    let layer = match sink {
+        LogSink::Stderr => fmt::layer()
+            .with_writer(io::stderr)
+            .with_filter(filter(directives)?)
+            .boxed(),
+        LogSink::File { path, format } => {
+            let file = Mutex::new(File::create(path)?);
+            let layer = fmt::layer().with_ansi(false).with_writer(file);
+            match format {
+                LogFormat::Text => layer.with_filter(filter(directives)?).boxed(),
+                LogFormat::Json => layer.json().with_filter(filter(directives)?).boxed(),
+            }
+        }
+    };
+    Ok(layer)
+}
+
+/// Subscriber writing to every sink of `config`, for callers that install it themselves, for
+/// instance with [`tracing::subscriber::with_default`].
+pub fn subscriber(config: &LogConfig) -> io::Result<impl Subscriber + Send + Sync> {
+    let layers = config
+        .sinks
+        .iter()
+        .map(|sink| sink_layer(sink, &config.filter))
+        .collect::<io::Result<Vec<_>>>()?;
+    Ok(tracing_subscriber::registry().with(layers))
+}
+
+/// Installs the [`subscriber`] of `config` as the global default. Fails when a sink cannot be
+/// opened, the filter is invalid or a global subscriber is already set.
+pub fn init(config: &LogConfig) -> io::Result<()> {
+    subscriber(config)?
+        .try_init()
+        .map_err(|e| io::Error::new(io::ErrorKind::AlreadyExists, e))
+}
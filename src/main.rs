@@ -1,17 +1,24 @@
-use std::sync::{Arc, Mutex};
-use std::thread;
-extern crate num_cpus;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Instant;
+use std::{env, io, process};
 
-use tqdm::pbar;
+use rand::{thread_rng, Rng};
+use serde_json::json;
 
-mod utils;
-use utils::{distribute_run_times, generate_equally_spaced_values};
+use one_max_genetic_algorithm_rust::dashboard::{self, Dashboard};
+use one_max_genetic_algorithm_rust::logging;
+use one_max_genetic_algorithm_rust::plots::write_sweep_plots;
+use one_max_genetic_algorithm_rust::report::{json_report, write_html_report};
 
-mod one_max_genetic_algorithm;
-use one_max_genetic_algorithm::genetic_algorithm;
+use one_max_genetic_algorithm_rust::scaling::{run_scaling, ScalingConfig};
+use one_max_genetic_algorithm_rust::sweep::{
+    process_genetic_algorithm_observed, CheckpointOptions, ExperimentConfig, SweepObserver, Workers,
+};
+use one_max_genetic_algorithm_rust::utils::generate_equally_spaced_values;
 
-mod results;
-use results::Results;
+mod cli;
+use cli::{parse_args, usage, OutputFormat};
 
 // Constants
 const RUN_TIMES: usize = 8;
@@ -25,118 +32,229 @@ const MUTATION_RATE_MIN: f64 = 0.001;
 const MUTATION_RATE_MAX: f64 = 0.01;
 const CROSSOVER_RATE_MIN: f64 = 0.1;
 const CROSSOVER_RATE_MAX: f64 = 0.6;
+const SCALING_RUN_TIMES: usize = 20;
+const SCALING_GENERATIONS: u32 = 20_000; // Runs of the scaling analysis only stop at the optimum, so they get a much larger budget.
 
-pub fn process_genetic_algorithm(mutation_rate_values: &[f64], crossover_rate_values: &[f64]) {
-    let max_threads: usize = num_cpus::get() - 2; // Use max_cpu count - 2 for maximum concurrent threads
-                                                  // Calculate the number of threads of each iteration based on MAX_THREADS
-    let distributed_run_times: Vec<usize> = distribute_run_times(max_threads, RUN_TIMES);
-
-    let total_iterations = mutation_rate_values.len() * crossover_rate_values.len();
-    let mut pbar = pbar(Some(total_iterations));
-
-    let mut best_mutation_rate: f64 = 0.0;
-    let mut best_crossover_rate: f64 = 0.0;
-    let mut prev_best_score: f64 = 0.0;
-
-    let mut best_result: Results = Results::new(GENERATIONS, 1.0);
-    let mut score: f64;
-
-    for &mutation_rate in mutation_rate_values {
-        let mut prev_local_score: f64 = 0.0;
-
-        for (i, &crossover_rate) in crossover_rate_values.iter().enumerate() {
-            let result = Arc::new(Mutex::new(Results::new(GENERATIONS, 1.0)));
-            let distributed_run_times_clone = distributed_run_times.clone();
-            // Outer loop to control the number of iterations based on MAX_THREADS
-            for local_run_times in distributed_run_times_clone {
-                let mut handles = vec![];
-
-                for _ in 0..local_run_times {
-                    let result_clone = Arc::clone(&result);
-                    let handle = thread::spawn(move || {
-                        let (generation, generation_fitness, best_fitness) = genetic_algorithm(
-                            POPULATION_SIZE,
-                            GENOME_LENGTH,
-                            GENERATIONS,
-                            mutation_rate,
-                            crossover_rate,
-                            SELECT_PARENT_MODE,
-                            TARGET_GENERATION_FITNESS,
-                            false,
-                        );
-                        let mut result = result_clone.lock().unwrap();
-                        result.add_result(generation, generation_fitness, best_fitness);
-                    });
-
-                    handles.push(handle);
-                }
-                for handle in handles {
-                    handle.join().unwrap();
-                }
-            }
-
-            let result = result.lock().unwrap();
-            score = result.get_score();
+/// Prints a human readable message to stdout, or to stderr when stdout is kept for JSON.
+fn status(format: OutputFormat, message: impl Display) {
+    match format {
+        OutputFormat::Text => println!("{message}"),
+        OutputFormat::Json => eprintln!("{message}"),
+    }
+}
 
-            // General score check
-            if score >= prev_best_score {
-                best_mutation_rate = mutation_rate;
-                best_crossover_rate = crossover_rate;
-                best_result = result.clone();
-                prev_best_score = score;
-            }
+fn print_json(document: &serde_json::Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(document).expect("JSON values always serialize")
+    );
+}
 
-            if prev_local_score < (score * 0.9) && i != 0 {
-                // Skip this loop since the score is not improving
-                pbar.update(crossover_rate_values.len() - i).unwrap();
-                break;
-            }
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", usage());
+            return;
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{}", usage());
+            process::exit(2);
+        }
+    };
+    if let Err(e) = logging::init(&options.log_config(env::var("RUST_LOG").ok())) {
+        eprintln!("Could not set up logging: {e}");
+        process::exit(2);
+    }
 
-            prev_local_score = score;
-            pbar.update(1).unwrap();
+    let format = options.format;
+    let started = Instant::now();
 
-            if prev_best_score >= TARGET_PROBLEM_FITNESS {
-                // Check if perfect score to close the algorithm execution
-                break;
+    if let Some(genome_lengths) = options.scaling {
+        let config = ScalingConfig {
+            genome_lengths,
+            runs: SCALING_RUN_TIMES,
+            solver: options.solver,
+            population_size: POPULATION_SIZE,
+            max_generations: SCALING_GENERATIONS,
+            crossover_rate: CROSSOVER_RATE_MAX,
+            select_parent_mode: SELECT_PARENT_MODE.to_string(),
+            // Always seeded so the printed config reproduces the analysis
+            seed: Some(options.seed.unwrap_or_else(|| thread_rng().gen())),
+            threads: options.jobs.threads(),
+            ..ScalingConfig::default()
+        };
+        status(
+            format,
+            format_args!(
+                "Running {} times the one max problem with {} for genome lengths {:?}, mutation rate 1/n",
+                config.runs, config.solver, config.genome_lengths
+            ),
+        );
+        let report = match run_scaling(&config) {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("Invalid scaling analysis: {e}");
+                process::exit(1);
             }
+        };
+        match format {
+            OutputFormat::Text => println!("{report}"),
+            OutputFormat::Json => print_json(&json!({
+                "crate_version": env!("CARGO_PKG_VERSION"),
+                "config": config,
+                "report": report,
+                "elapsed_seconds": started.elapsed().as_secs_f64(),
+            })),
         }
+        return;
     }
 
-    pbar.close().unwrap();
-    println!("--------------------------------------------------");
-    println!("\tBest results");
-    println!("--------------------------------------------------");
-    println!(
-        "Best Mutation Rate: {best_mutation_rate}\nBest Crossover Rate: {best_crossover_rate}"
-    );
-    best_result.print();
-}
-
-fn main() {
-    let mutation_rate_values: Vec<f64> =
-        generate_equally_spaced_values(MUTATION_RATE_MIN, MUTATION_RATE_MAX, 8, true);
-    let crossover_rate_values: Vec<f64> =
-        generate_equally_spaced_values(CROSSOVER_RATE_MIN, CROSSOVER_RATE_MAX, 5, false);
+    let config = ExperimentConfig {
+        run_times: RUN_TIMES,
+        generations: GENERATIONS,
+        population_size: POPULATION_SIZE,
+        genome_length: GENOME_LENGTH,
+        select_parent_mode: SELECT_PARENT_MODE.to_string(),
+        target_generation_fitness: TARGET_GENERATION_FITNESS,
+        target_problem_fitness: TARGET_PROBLEM_FITNESS,
+        mutation_rate_values: generate_equally_spaced_values(
+            MUTATION_RATE_MIN,
+            MUTATION_RATE_MAX,
+            8,
+            true,
+        ),
+        crossover_rate_values: generate_equally_spaced_values(
+            CROSSOVER_RATE_MIN,
+            CROSSOVER_RATE_MAX,
+            5,
+            false,
+        ),
+        solver: options.solver,
+        seed: options.seed,
+    };
 
-    println!(
-        "Running {} times the one max problem with genetic algorithms for:
+    status(
+        format,
+        format_args!(
+            "Running {} times the one max problem with genetic algorithms for:
+    Solver:                {}
     Generations:           {}
     Population Size:       {}
     Genome Length:         {}
     Parent selection mode: {}
     Mutation Rate:         {:.4} to {:.4} with {} steps
-    Crossover Rate:        {:.4} to {:.4} with {} steps",
-        RUN_TIMES,
-        GENERATIONS,
-        POPULATION_SIZE,
-        GENOME_LENGTH,
-        SELECT_PARENT_MODE,
-        mutation_rate_values.first().unwrap_or(&0.0),
-        mutation_rate_values.last().unwrap_or(&0.0),
-        mutation_rate_values.len(),
-        crossover_rate_values.first().unwrap_or(&0.0),
-        crossover_rate_values.last().unwrap_or(&0.0),
-        crossover_rate_values.len(),
+    Crossover Rate:        {:.4} to {:.4} with {} steps
+    Concurrent runs:       {}{}",
+            config.run_times,
+            config.solver,
+            config.generations,
+            config.population_size,
+            config.genome_length,
+            config.select_parent_mode,
+            config.mutation_rate_values.first().unwrap_or(&0.0),
+            config.mutation_rate_values.last().unwrap_or(&0.0),
+            config.mutation_rate_values.len(),
+            config.crossover_rate_values.first().unwrap_or(&0.0),
+            config.crossover_rate_values.last().unwrap_or(&0.0),
+            config.crossover_rate_values.len(),
+            options.jobs.threads(),
+            if options.pin_cores {
+                ", pinned to cores"
+            } else {
+                ""
+            },
+        ),
+    );
+
+    let checkpoint_options = CheckpointOptions {
+        path: options.checkpoint_path,
+        resume: options.resume,
+    };
+    let workers = Workers {
+        jobs: options.jobs,
+        pin_cores: options.pin_cores,
+    };
+    let outcome = if options.dashboard {
+        let dashboard = Arc::new(Dashboard::new(&config));
+        let terminal = match dashboard::spawn(Arc::clone(&dashboard), io::stdout()) {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                eprintln!("Could not start the dashboard: {e}");
+                process::exit(1);
+            }
+        };
+        let observer: Arc<dyn SweepObserver> = dashboard;
+        let outcome = process_genetic_algorithm_observed(
+            &config,
+            Some(&checkpoint_options),
+            Some(observer),
+            &workers,
+        );
+        if let Err(e) = terminal.finish() {
+            eprintln!("Dashboard error: {e}");
+        }
+        outcome
+    } else {
+        process_genetic_algorithm_observed(&config, Some(&checkpoint_options), None, &workers)
+    };
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    let elapsed = started.elapsed();
+    if outcome.aborted {
+        status(
+            format,
+            format_args!(
+                "Sweep aborted. The completed cells are stored in {}, run again with --resume to continue.",
+                checkpoint_options.path.display()
+            ),
+        );
+    }
+
+    status(
+        format,
+        format_args!(
+            "Best Mutation Rate: {}, Best Crossover Rate: {}, Overall Score: {:.3}, Seed: {}",
+            outcome.best_mutation_rate,
+            outcome.best_crossover_rate,
+            outcome.best_result.score,
+            outcome.seed
+        ),
     );
-    process_genetic_algorithm(&mutation_rate_values, &crossover_rate_values)
+    match write_html_report(&options.report_path, &config, &outcome) {
+        Ok(()) => status(
+            format,
+            format_args!("Report written to {}", options.report_path.display()),
+        ),
+        Err(e) => {
+            eprintln!(
+                "Could not write the report to {}: {e}",
+                options.report_path.display()
+            );
+            process::exit(1);
+        }
+    }
+
+    if let Some(directory) = &options.plots {
+        match write_sweep_plots(directory, &config, &outcome) {
+            Ok(paths) => {
+                for path in paths {
+                    status(format, format_args!("Wrote {}", path.display()));
+                }
+            }
+            Err(e) => {
+                eprintln!("Could not write the plots to {}: {e}", directory.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    if format == OutputFormat::Json {
+        print_json(&json_report(&config, &outcome, elapsed));
+    }
 }
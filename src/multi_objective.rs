@@ -0,0 +1,531 @@
+//! Multi-objective optimisation of bitstrings with NSGA-II.
+//!
+//! A [`MultiObjective`] maps a genome to a vector of objectives, all of them maximised.
+//! [`Nsga2`] breeds with the usual crossover and mutation and replaces the generational survivor
+//! scheme with non-dominated sorting plus crowding distance. The outcome holds the Pareto front
+//! of the final population and its [`hypervolume`]. [`Nsga2Replacement`] plugs the same survivor
+//! scheme into single-objective runs, which is how the sweep runs `--solver nsga2`.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::multi_objective::{Nsga2, OneMinMax};
+//!
+//! let mut nsga2 = Nsga2::new(20, 8, 50);
+//! nsga2.seed = Some(1);
+//! let outcome = nsga2.run(&OneMinMax).unwrap();
+//! println!("{outcome}");
+//! assert!(outcome.hypervolume > 0.0);
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::fitness::{Evaluator, Fitness};
+use crate::one_max_genetic_algorithm::{
+    crossover_with_rng, init_population_with_rng, mutate_with_rng,
+};
+use crate::operators::{breed, Operators, Replacement};
+
+/// A vector-valued fitness function. Every objective is maximised.
+pub trait MultiObjective: Send + Sync {
+    /// Number of objectives returned by [`MultiObjective::evaluate`].
+    fn objectives(&self) -> usize;
+
+    fn evaluate(&self, genome: &[u8]) -> Vec<f64>;
+
+    /// Point dominated by every solution of interest, used for the hypervolume. Defaults to the
+    /// origin.
+    fn reference_point(&self) -> Vec<f64> {
+        vec![0.0; self.objectives()]
+    }
+}
+
+/// LeadingOnes-TrailingZeros: the number of leading ones and the number of trailing zeros.
+pub struct Lotz;
+
+impl MultiObjective for Lotz {
+    fn objectives(&self) -> usize {
+        2
+    }
+
+    fn evaluate(&self, genome: &[u8]) -> Vec<f64> {
+        let leading_ones = genome.iter().take_while(|&&gene| gene == 1).count();
+        let trailing_zeros = genome.iter().rev().take_while(|&&gene| gene == 0).count();
+        vec![leading_ones as f64, trailing_zeros as f64]
+    }
+
+    fn reference_point(&self) -> Vec<f64> {
+        vec![-1.0, -1.0]
+    }
+}
+
+/// OneMinMax: the number of zeros and the number of ones. Every genome is Pareto optimal.
+pub struct OneMinMax;
+
+impl MultiObjective for OneMinMax {
+    fn objectives(&self) -> usize {
+        2
+    }
+
+    fn evaluate(&self, genome: &[u8]) -> Vec<f64> {
+        let ones = genome.iter().filter(|&&gene| gene == 1).count();
+        vec![(genome.len() - ones) as f64, ones as f64]
+    }
+
+    fn reference_point(&self) -> Vec<f64> {
+        vec![-1.0, -1.0]
+    }
+}
+
+/// One objective per scalar [`Fitness`], for example a fitness and a negated penalty.
+pub struct FitnessObjectives {
+    pub objectives: Vec<Arc<dyn Fitness>>,
+    pub reference: Vec<f64>,
+}
+
+impl FitnessObjectives {
+    /// Objectives with the origin as reference point.
+    pub fn new(objectives: Vec<Arc<dyn Fitness>>) -> Self {
+        let reference = vec![0.0; objectives.len()];
+        FitnessObjectives {
+            objectives,
+            reference,
+        }
+    }
+}
+
+impl MultiObjective for FitnessObjectives {
+    fn objectives(&self) -> usize {
+        self.objectives.len()
+    }
+
+    fn evaluate(&self, genome: &[u8]) -> Vec<f64> {
+        self.objectives
+            .iter()
+            .map(|objective| objective.evaluate(genome))
+            .collect()
+    }
+
+    fn reference_point(&self) -> Vec<f64> {
+        self.reference.clone()
+    }
+}
+
+/// Whether `a` is at least as good as `b` in every objective and better in one.
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// Splits the indices into fronts: the first front is not dominated at all, the second only by
+/// the first, and so on.
+pub fn non_dominated_sort(objectives: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    let size = objectives.len();
+    let mut domination_count = vec![0usize; size];
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); size];
+    for i in 0..size {
+        for j in i + 1..size {
+            if dominates(&objectives[i], &objectives[j]) {
+                dominated[i].push(j);
+                domination_count[j] += 1;
+            } else if dominates(&objectives[j], &objectives[i]) {
+                dominated[j].push(i);
+                domination_count[i] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..size).filter(|&i| domination_count[i] == 0).collect();
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+        for &i in &front {
+            for &j in &dominated[i] {
+                domination_count[j] -= 1;
+                if domination_count[j] == 0 {
+                    next_front.push(j);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next_front;
+    }
+    fronts
+}
+
+/// Crowding distance of every member of `front`, in the same order. Boundary solutions get an
+/// infinite distance.
+pub fn crowding_distance(objectives: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let mut distances = vec![0.0; front.len()];
+    let Some(&first) = front.first() else {
+        return distances;
+    };
+    let objective_value = |member: usize, objective: usize| objectives[front[member]][objective];
+    for objective in 0..objectives[first].len() {
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| {
+            objective_value(a, objective).total_cmp(&objective_value(b, objective))
+        });
+        let value = |position: usize| objective_value(order[position], objective);
+        let last = order.len() - 1;
+        distances[order[0]] = f64::INFINITY;
+        distances[order[last]] = f64::INFINITY;
+        let range = value(last) - value(0);
+        if range <= 0.0 {
+            continue;
+        }
+        for position in 1..last {
+            distances[order[position]] += (value(position + 1) - value(position - 1)) / range;
+        }
+    }
+    distances
+}
+
+/// Volume of the objective space dominated by the points and bounded by `reference`. Points that
+/// do not strictly dominate the reference point are ignored.
+pub fn hypervolume(points: &[Vec<f64>], reference: &[f64]) -> f64 {
+    let points: Vec<&[f64]> = points
+        .iter()
+        .map(Vec::as_slice)
+        .filter(|point| point.iter().zip(reference).all(|(x, r)| x > r))
+        .collect();
+    slice_hypervolume(&points, reference)
+}
+
+// Slices the space along the last objective and sums the volume of every slab
+fn slice_hypervolume(points: &[&[f64]], reference: &[f64]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+    let last = reference.len() - 1;
+    if last == 0 {
+        let best = points
+            .iter()
+            .map(|point| point[0])
+            .fold(f64::NEG_INFINITY, f64::max);
+        return best - reference[0];
+    }
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| b[last].total_cmp(&a[last]));
+
+    let mut volume = 0.0;
+    for i in 0..sorted.len() {
+        let lower = sorted
+            .get(i + 1)
+            .map_or(reference[last], |point| point[last]);
+        let depth = sorted[i][last] - lower;
+        if depth > 0.0 {
+            let slab: Vec<&[f64]> = sorted[..=i].iter().map(|point| &point[..last]).collect();
+            volume += depth * slice_hypervolume(&slab, &reference[..last]);
+        }
+    }
+    volume
+}
+
+/// A member of a Pareto front.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParetoPoint {
+    pub genome: Vec<u8>,
+    pub objectives: Vec<f64>,
+}
+
+/// Outcome of [`Nsga2::run`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultiObjectiveOutcome {
+    /// Distinct non-dominated solutions of the final population, sorted by their objectives.
+    pub front: Vec<ParetoPoint>,
+    /// Hypervolume of the front with respect to the objective's reference point.
+    pub hypervolume: f64,
+    pub generations: u32,
+    pub evaluations: u64,
+    /// Seed the run was started with, so it can be reproduced.
+    pub seed: u64,
+}
+
+/// The Pareto front, one solution per line, and its hypervolume.
+impl fmt::Display for MultiObjectiveOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Pareto Front ({} solutions):", self.front.len())?;
+        for point in &self.front {
+            let genome: String = point.genome.iter().map(|gene| gene.to_string()).collect();
+            writeln!(f, "{:?} {}", point.objectives, genome)?;
+        }
+        write!(f, "Hypervolume:            {:.3}", self.hypervolume)
+    }
+}
+
+/// NSGA-II over bitstrings with one-point crossover and bit flip mutation.
+#[derive(Clone, Debug)]
+pub struct Nsga2 {
+    pub population_size: usize,
+    pub genome_length: usize,
+    pub max_generations: u32,
+    pub crossover_rate: f64,
+    pub mutation_rate: f64,
+    pub seed: Option<u64>,
+}
+
+impl Nsga2 {
+    /// NSGA-II with a crossover rate of 0.9 and a mutation rate of one gene per genome.
+    pub fn new(population_size: usize, genome_length: usize, max_generations: u32) -> Self {
+        Nsga2 {
+            population_size,
+            genome_length,
+            max_generations,
+            crossover_rate: 0.9,
+            mutation_rate: 1.0 / genome_length.max(1) as f64,
+            seed: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.population_size == 0 {
+            return Err(ConfigError::ZeroPopulation);
+        }
+        if self.genome_length < 2 {
+            return Err(ConfigError::GenomeTooShort(self.genome_length));
+        }
+        if self.max_generations == 0 {
+            return Err(ConfigError::ZeroGenerations);
+        }
+        for (name, value) in [
+            ("mutation rate", self.mutation_rate),
+            ("crossover rate", self.crossover_rate),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ConfigError::RateOutOfRange { name, value });
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs NSGA-II for `max_generations` generations.
+    pub fn run(
+        &self,
+        objective: &dyn MultiObjective,
+    ) -> Result<MultiObjectiveOutcome, ConfigError> {
+        self.validate()?;
+        let seed = self.seed.unwrap_or_else(|| thread_rng().gen());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let mut population =
+            init_population_with_rng(self.population_size, self.genome_length, &mut rng);
+        let mut objectives: Vec<Vec<f64>> = population
+            .iter()
+            .map(|genome| objective.evaluate(genome))
+            .collect();
+        let mut evaluations = population.len() as u64;
+        let (mut rank, mut crowding) = rank_and_crowding(&objectives);
+
+        for _ in 0..self.max_generations {
+            // Binary tournament on rank, then crowding distance
+            let select = |rng: &mut ChaCha8Rng| {
+                let (a, b) = (
+                    rng.gen_range(0..population.len()),
+                    rng.gen_range(0..population.len()),
+                );
+                let a_wins =
+                    rank[a] < rank[b] || (rank[a] == rank[b] && crowding[a] >= crowding[b]);
+                if a_wins {
+                    a
+                } else {
+                    b
+                }
+            };
+            let mut offspring = Vec::with_capacity(self.population_size);
+            while offspring.len() < self.population_size {
+                let parent1 = select(&mut rng);
+                let parent2 = select(&mut rng);
+                let (child1, child2) = crossover_with_rng(
+                    &population[parent1],
+                    &population[parent2],
+                    self.crossover_rate,
+                    &mut rng,
+                );
+                offspring.push(mutate_with_rng(&child1, self.mutation_rate, &mut rng));
+                offspring.push(mutate_with_rng(&child2, self.mutation_rate, &mut rng));
+            }
+            offspring.truncate(self.population_size);
+            evaluations += offspring.len() as u64;
+            objectives.extend(offspring.iter().map(|genome| objective.evaluate(genome)));
+            population.extend(offspring);
+
+            let survivors = select_survivors(&objectives, self.population_size);
+            population = survivors.iter().map(|&i| population[i].clone()).collect();
+            objectives = survivors.iter().map(|&i| objectives[i].clone()).collect();
+            (rank, crowding) = rank_and_crowding(&objectives);
+        }
+
+        let front = pareto_front(&population, &objectives);
+        Ok(MultiObjectiveOutcome {
+            hypervolume: front_hypervolume(&front, objective),
+            front,
+            generations: self.max_generations,
+            evaluations,
+            seed,
+        })
+    }
+}
+
+/// Distinct non-dominated members of `population`, whose objectives are `objectives`, sorted by
+/// their objectives.
+pub fn pareto_front(population: &[Vec<u8>], objectives: &[Vec<f64>]) -> Vec<ParetoPoint> {
+    let Some(first) = non_dominated_sort(objectives).into_iter().next() else {
+        return Vec::new();
+    };
+    let mut front: Vec<ParetoPoint> = first
+        .into_iter()
+        .map(|i| ParetoPoint {
+            genome: population[i].clone(),
+            objectives: objectives[i].clone(),
+        })
+        .collect();
+    front.sort_by(|a, b| {
+        a.objectives
+            .partial_cmp(&b.objectives)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.genome.cmp(&b.genome))
+    });
+    front.dedup();
+    front
+}
+
+/// Hypervolume of `front` with respect to the reference point of `objective`.
+pub fn front_hypervolume(front: &[ParetoPoint], objective: &dyn MultiObjective) -> f64 {
+    let objectives: Vec<Vec<f64>> = front.iter().map(|point| point.objectives.clone()).collect();
+    hypervolume(&objectives, &objective.reference_point())
+}
+
+/// Hypervolume of the Pareto front of `population` under `objective`.
+pub fn population_hypervolume(population: &[Vec<u8>], objective: &dyn MultiObjective) -> f64 {
+    let objectives: Vec<Vec<f64>> = population
+        .iter()
+        .map(|genome| objective.evaluate(genome))
+        .collect();
+    front_hypervolume(&pareto_front(population, &objectives), objective)
+}
+
+/// NSGA-II as the survivor scheme of a single-objective run, so it goes through
+/// [`GaConfig`](crate::config::GaConfig), [`Evolution`](crate::evolution::Evolution) and the
+/// sweep like any other [`Replacement`]. Parents are picked by the run's selection operator on
+/// their front and crowding distance, the offspring are bred with its crossover and mutation, and
+/// the best half of parents and offspring survives. The run's fitness still decides when it is
+/// solved: as the population spreads along the front its mean never reaches a target generation
+/// fitness, so it is solved once its best genome is optimal.
+pub struct Nsga2Replacement {
+    pub objective: Arc<dyn MultiObjective>,
+}
+
+impl Nsga2Replacement {
+    pub fn new(objective: Arc<dyn MultiObjective>) -> Self {
+        Nsga2Replacement { objective }
+    }
+}
+
+impl Replacement for Nsga2Replacement {
+    fn solved_by_best_genome(&self) -> bool {
+        true
+    }
+
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let mut objectives: Vec<Vec<f64>> = population
+            .iter()
+            .map(|genome| self.objective.evaluate(genome))
+            .collect();
+        let (offspring, known_fitness) = breed(
+            population_size,
+            population,
+            &crowded_comparison_values(&objectives),
+            fitness_values,
+            operators,
+            evaluator.fitness(),
+            rng,
+        );
+        let offspring_fitness =
+            evaluator.evaluate_offspring(&offspring, &known_fitness, evaluations);
+        objectives.extend(
+            offspring
+                .iter()
+                .map(|genome| self.objective.evaluate(genome)),
+        );
+
+        let candidates: Vec<&Vec<u8>> = population.iter().chain(&offspring).collect();
+        let candidate_fitness: Vec<f64> = fitness_values
+            .iter()
+            .chain(&offspring_fitness)
+            .copied()
+            .collect();
+        let survivors = select_survivors(&objectives, population_size);
+        (
+            survivors.iter().map(|&i| candidates[i].clone()).collect(),
+            survivors.iter().map(|&i| candidate_fitness[i]).collect(),
+        )
+    }
+}
+
+/// Positive selection values ordering the individuals by front, then by decreasing crowding
+/// distance, so a fitness-based selection operator picks parents like NSGA-II's crowded
+/// comparison.
+fn crowded_comparison_values(objectives: &[Vec<f64>]) -> Vec<f64> {
+    let (rank, crowding) = rank_and_crowding(objectives);
+    let mut order: Vec<usize> = (0..objectives.len()).collect();
+    order.sort_by(|&a, &b| {
+        rank[a]
+            .cmp(&rank[b])
+            .then_with(|| crowding[b].total_cmp(&crowding[a]))
+    });
+    let mut values = vec![0.0; objectives.len()];
+    for (position, &i) in order.iter().enumerate() {
+        values[i] = (objectives.len() - position) as f64;
+    }
+    values
+}
+
+/// Front index and crowding distance of every individual.
+fn rank_and_crowding(objectives: &[Vec<f64>]) -> (Vec<usize>, Vec<f64>) {
+    let mut rank = vec![0; objectives.len()];
+    let mut crowding = vec![0.0; objectives.len()];
+    for (front_index, front) in non_dominated_sort(objectives).iter().enumerate() {
+        for (&i, distance) in front.iter().zip(crowding_distance(objectives, front)) {
+            rank[i] = front_index;
+            crowding[i] = distance;
+        }
+    }
+    (rank, crowding)
+}
+
+/// Indices of the `size` survivors: whole fronts in order, and the least crowded members of the
+/// front that does not fit entirely.
+fn select_survivors(objectives: &[Vec<f64>], size: usize) -> Vec<usize> {
+    let mut survivors = Vec::with_capacity(size);
+    for front in non_dominated_sort(objectives) {
+        if survivors.len() + front.len() <= size {
+            survivors.extend(front);
+            continue;
+        }
+        let distances = crowding_distance(objectives, &front);
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| distances[b].total_cmp(&distances[a]));
+        let missing = size - survivors.len();
+        survivors.extend(
+            order
+                .into_iter()
+                .take(missing)
+                .map(|position| front[position]),
+        );
+        break;
+    }
+    survivors
+}
@@ -0,0 +1,277 @@
+//! Niching methods that preserve diversity by letting similar individuals compete with each
+//! other rather than with the whole population.
+//!
+//! They plug into a run as its [`Replacement`] through
+//! [`crate::config::GaConfigBuilder::replacement`]:
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//! use one_max_genetic_algorithm_rust::niching::DeterministicCrowding;
+//!
+//! let outcome = GaConfig::builder()
+//!     .replacement(DeterministicCrowding)
+//!     .max_generations(50)
+//!     .build()
+//!     .unwrap()
+//!     .run();
+//! println!("Final diversity: {:?}", outcome.diversity.last());
+//! ```
+//!
+//! Crowding and restricted tournament selection pick parents at random and ignore the
+//! configured selection. Their fitness based decisions assume non-negative fitness values.
+
+use rand::seq::index::sample;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+use crate::diversity::hamming_distance;
+use crate::fitness::Evaluator;
+use crate::operators::{breed, vary, Operators, Replacement};
+
+/// Fitness divided by the niche count, the number of individuals within `radius` genes weighted
+/// by `1 - (distance / radius)^alpha`.
+pub fn shared_fitness(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    radius: f64,
+    alpha: f64,
+) -> Vec<f64> {
+    population
+        .iter()
+        .zip(fitness_values)
+        .map(|(genome, &genome_fitness)| {
+            let niche_count: f64 = population
+                .iter()
+                .map(|other| {
+                    let distance = hamming_distance(genome, other) as f64;
+                    if distance < radius {
+                        1.0 - (distance / radius).powf(alpha)
+                    } else {
+                        0.0
+                    }
+                })
+                .sum();
+            genome_fitness / niche_count.max(1.0)
+        })
+        .collect()
+}
+
+/// Generational replacement whose parents are selected on their shared fitness, so crowded
+/// regions of the search space get fewer offspring.
+pub struct FitnessSharing {
+    /// Sharing radius in genes.
+    pub radius: f64,
+    /// Shape of the sharing function, 1.0 for a triangular one.
+    pub alpha: f64,
+}
+
+impl FitnessSharing {
+    /// Triangular sharing with the given radius in genes.
+    pub fn new(radius: f64) -> Self {
+        FitnessSharing { radius, alpha: 1.0 }
+    }
+}
+
+impl Replacement for FitnessSharing {
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let shared = shared_fitness(population, fitness_values, self.radius, self.alpha);
+        let (offspring, known_fitness) = breed(
+            population_size,
+            population,
+            &shared,
+            fitness_values,
+            operators,
+            evaluator.fitness(),
+            rng,
+        );
+        let offspring_fitness =
+            evaluator.evaluate_offspring(&offspring, &known_fitness, evaluations);
+        (offspring, offspring_fitness)
+    }
+}
+
+/// Pairs the population at random, and every child competes with the more similar of its two
+/// parents. `child_wins` decides each competition. An unpaired individual survives as is.
+fn crowding(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    operators: &Operators,
+    evaluator: &mut Evaluator,
+    evaluations: &mut u64,
+    rng: &mut dyn RngCore,
+    child_wins: fn(f64, f64, &mut dyn RngCore) -> bool,
+) -> (Vec<Vec<u8>>, Vec<f64>) {
+    let mut order: Vec<usize> = (0..population.len()).collect();
+    order.shuffle(rng);
+    let pairs: Vec<(usize, usize)> = order
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect();
+
+    let mut children = Vec::with_capacity(pairs.len() * 2);
+    let mut known_fitness = Vec::with_capacity(pairs.len() * 2);
+    for &(parent1, parent2) in &pairs {
+        for (child, child_fitness) in vary(
+            (&population[parent1], Some(fitness_values[parent1])),
+            (&population[parent2], Some(fitness_values[parent2])),
+            operators,
+            evaluator.fitness(),
+            rng,
+        ) {
+            children.push(child);
+            known_fitness.push(child_fitness);
+        }
+    }
+    let children_fitness = evaluator.evaluate_offspring(&children, &known_fitness, evaluations);
+
+    let mut next_population = population.to_vec();
+    let mut next_fitness = fitness_values.to_vec();
+    for (pair_index, &(parent1, parent2)) in pairs.iter().enumerate() {
+        let (child1, child2) = (2 * pair_index, 2 * pair_index + 1);
+        let distance =
+            |parent: usize, child: usize| hamming_distance(&population[parent], &children[child]);
+        let matches = if distance(parent1, child1) + distance(parent2, child2)
+            <= distance(parent1, child2) + distance(parent2, child1)
+        {
+            [(parent1, child1), (parent2, child2)]
+        } else {
+            [(parent1, child2), (parent2, child1)]
+        };
+        for (parent, child) in matches {
+            if child_wins(children_fitness[child], fitness_values[parent], rng) {
+                next_population[parent] = children[child].clone();
+                next_fitness[parent] = children_fitness[child];
+            }
+        }
+    }
+    (next_population, next_fitness)
+}
+
+/// Crowding where a child replaces its closest parent when it is at least as fit.
+pub struct DeterministicCrowding;
+
+impl Replacement for DeterministicCrowding {
+    /// Keeps the size of the current population.
+    fn replace(
+        &self,
+        _population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        crowding(
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+            |child_fitness, parent_fitness, _| child_fitness >= parent_fitness,
+        )
+    }
+}
+
+/// Crowding where a child replaces its closest parent with probability
+/// `child_fitness / (child_fitness + parent_fitness)`.
+pub struct ProbabilisticCrowding;
+
+impl Replacement for ProbabilisticCrowding {
+    /// Keeps the size of the current population.
+    fn replace(
+        &self,
+        _population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        crowding(
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+            |child_fitness, parent_fitness, rng| {
+                let total = child_fitness + parent_fitness;
+                let probability = if total > 0.0 {
+                    child_fitness / total
+                } else {
+                    0.5
+                };
+                rng.gen::<f64>() < probability
+            },
+        )
+    }
+}
+
+/// Restricted tournament selection: two random parents breed two children, and each child
+/// replaces the most similar of `window_size` random individuals when it is at least as fit.
+/// Repeated for half the population every generation.
+pub struct RestrictedTournament {
+    pub window_size: usize,
+}
+
+impl Replacement for RestrictedTournament {
+    /// Keeps the size of the current population.
+    fn replace(
+        &self,
+        _population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let mut next_population = population.to_vec();
+        let mut next_fitness = fitness_values.to_vec();
+        let size = population.len();
+        if size < 2 {
+            return (next_population, next_fitness);
+        }
+        let window_size = self.window_size.clamp(1, size);
+
+        for _ in 0..size / 2 {
+            let parents = sample(rng, size, 2);
+            let (parent1, parent2) = (parents.index(0), parents.index(1));
+            let (children, known_fitness): (Vec<Vec<u8>>, Vec<Option<f64>>) = vary(
+                (&next_population[parent1], Some(next_fitness[parent1])),
+                (&next_population[parent2], Some(next_fitness[parent2])),
+                operators,
+                evaluator.fitness(),
+                rng,
+            )
+            .into_iter()
+            .unzip();
+            let children_fitness =
+                evaluator.evaluate_offspring(&children, &known_fitness, evaluations);
+
+            for (child, child_fitness) in children.into_iter().zip(children_fitness) {
+                let closest = sample(rng, size, window_size)
+                    .iter()
+                    .min_by_key(|&index| hamming_distance(&next_population[index], &child))
+                    .unwrap();
+                if child_fitness >= next_fitness[closest] {
+                    next_population[closest] = child;
+                    next_fitness[closest] = child_fitness;
+                }
+            }
+        }
+        (next_population, next_fitness)
+    }
+}
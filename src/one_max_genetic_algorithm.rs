@@ -1,9 +1,27 @@
+//! Operators and main loop of the genetic algorithm.
+
+use std::cmp::Ordering;
+
 use rand::seq::index::sample;
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use tracing::info;
 
+use crate::config::ConfigError;
+use crate::evolution::Evolution;
+use crate::fitness::Evaluator;
+use crate::operators::Operators;
+use crate::restart::Restart;
+
+/// Random genome of `length` genes, each 0 or 1 with equal probability.
 pub fn random_genome(length: usize) -> Vec<u8> {
+    random_genome_with_rng(length, &mut thread_rng())
+}
+
+/// Same as [`random_genome`] drawing from the given RNG.
+pub fn random_genome_with_rng<R: Rng + ?Sized>(length: usize, rng: &mut R) -> Vec<u8> {
     let mut genome: Vec<u8> = Vec::with_capacity(length);
-    let mut rng = rand::thread_rng();
     for _ in 0..length {
         let gen: u8 = rng.gen_bool(0.5) as u8;
         genome.push(gen);
@@ -11,15 +29,26 @@ pub fn random_genome(length: usize) -> Vec<u8> {
     genome
 }
 
+/// Population of `population_size` random genomes.
 pub fn init_population(population_size: usize, genome_length: usize) -> Vec<Vec<u8>> {
+    init_population_with_rng(population_size, genome_length, &mut thread_rng())
+}
+
+/// Same as [`init_population`] drawing from the given RNG.
+pub fn init_population_with_rng<R: Rng + ?Sized>(
+    population_size: usize,
+    genome_length: usize,
+    rng: &mut R,
+) -> Vec<Vec<u8>> {
     let mut population: Vec<Vec<u8>> = Vec::with_capacity(population_size);
     for _ in 0..population_size {
-        let genome: Vec<u8> = random_genome(genome_length);
+        let genome: Vec<u8> = random_genome_with_rng(genome_length, rng);
         population.push(genome);
     }
     population
 }
 
+/// Share of ones in the genome, from 0.0 to 1.0. An empty genome scores 0.0.
 pub fn get_genome_fitness(genome: &[u8]) -> f64 {
     let sum: u32 = genome.iter().map(|&x| x as u32).sum();
     let genome_fitness: f64 = sum as f64 / genome.len() as f64;
@@ -29,7 +58,8 @@ pub fn get_genome_fitness(genome: &[u8]) -> f64 {
     genome_fitness
 }
 
-pub fn calculate_population_fitness(population: &Vec<Vec<u8>>) -> Vec<f64> {
+/// Fitness of every genome, in population order.
+pub fn calculate_population_fitness(population: &[Vec<u8>]) -> Vec<f64> {
     let mut population_fitness_vector: Vec<f64> = Vec::with_capacity(population.len());
 
     for genome in population {
@@ -39,10 +69,12 @@ pub fn calculate_population_fitness(population: &Vec<Vec<u8>>) -> Vec<f64> {
     population_fitness_vector
 }
 
+/// Fitness of the optimal genome.
 pub fn get_target_fitness() -> f64 {
     1.0
 }
 
+/// Highest fitness value, or negative infinity for an empty slice.
 pub fn get_best_fitness(fitnesses_values: &[f64]) -> f64 {
     fitnesses_values
         .iter()
@@ -50,6 +82,14 @@ pub fn get_best_fitness(fitnesses_values: &[f64]) -> f64 {
         .fold(f64::NEG_INFINITY, f64::max)
 }
 
+/// Orders fitness values with NaN below every number, so a fitness function returning NaN never
+/// wins a selection.
+pub fn fitness_cmp(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()).reverse())
+}
+
+/// Average fitness of the population.
 pub fn get_generation_fitness(fitnesses_values: &[f64], population_size: usize) -> f64 {
     let mut sum_fitness: f64 = fitnesses_values.iter().sum();
     sum_fitness /= population_size as f64;
@@ -59,11 +99,32 @@ pub fn get_generation_fitness(fitnesses_values: &[f64], population_size: usize)
     sum_fitness
 }
 
-pub fn select_parent(population: &Vec<Vec<u8>>, fitness_values: &[f64], mode: &str) -> Vec<u8> {
+/// Selects a parent with `"tournament"` or `"roulette"` selection. Unknown modes use tournament.
+pub fn select_parent(population: &[Vec<u8>], fitness_values: &[f64], mode: &str) -> Vec<u8> {
+    select_parent_with_rng(population, fitness_values, mode, &mut thread_rng())
+}
+
+/// Same as [`select_parent`] drawing from the given RNG.
+pub fn select_parent_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    mode: &str,
+    rng: &mut R,
+) -> Vec<u8> {
+    population[select_parent_index_with_rng(population, fitness_values, mode, rng)].clone()
+}
+
+/// Same as [`select_parent_with_rng`] returning the index of the parent in the population.
+pub fn select_parent_index_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    mode: &str,
+    rng: &mut R,
+) -> usize {
     if mode.to_lowercase() == "tournament"
         || !["roulette", "tournament"].contains(&mode.to_lowercase().as_str())
     {
-        let mut tournament_size: usize = thread_rng().gen_range(
+        let mut tournament_size: usize = rng.gen_range(
             ((*population).len() as f64 * 0.6) as usize
                 ..=((*population).len() as f64 * 0.8) as usize,
         );
@@ -72,52 +133,123 @@ pub fn select_parent(population: &Vec<Vec<u8>>, fitness_values: &[f64], mode: &s
         } else {
             1
         };
-        select_parent_tournament(population, fitness_values, tournament_size)
+        select_parent_tournament_index_with_rng(population, fitness_values, tournament_size, rng)
     } else {
-        select_parent_roulette(population, fitness_values)
+        select_parent_roulette_index_with_rng(population, fitness_values, rng)
     }
 }
 
+/// Returns the fittest of `tournament_size` distinct individuals picked at random.
 pub fn select_parent_tournament(
     population: &[Vec<u8>],
     fitness_values: &[f64],
     tournament_size: usize,
 ) -> Vec<u8> {
+    select_parent_tournament_with_rng(
+        population,
+        fitness_values,
+        tournament_size,
+        &mut thread_rng(),
+    )
+}
+
+/// Same as [`select_parent_tournament`] drawing from the given RNG.
+pub fn select_parent_tournament_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    tournament_size: usize,
+    rng: &mut R,
+) -> Vec<u8> {
+    let winner =
+        select_parent_tournament_index_with_rng(population, fitness_values, tournament_size, rng);
+    population[winner].clone() // Return the selected individual from the winning tournament
+}
+
+/// Same as [`select_parent_tournament_with_rng`] returning the index of the winner.
+pub fn select_parent_tournament_index_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    tournament_size: usize,
+    rng: &mut R,
+) -> usize {
     // Tournament implementation
-    let mut rng = rand::thread_rng();
-    let selected_indices = sample(&mut rng, population.len(), tournament_size);
+    let selected_indices = sample(rng, population.len(), tournament_size);
 
-    let winner = selected_indices
+    selected_indices
         .iter()
-        .map(|i| (population[i].clone(), fitness_values[i]))
-        .max_by(|(_, fitness1), (_, fitness2)| fitness1.partial_cmp(fitness2).unwrap())
-        .unwrap(); // Select the candidate with the highest fitness
-    winner.0 // Return the selected individual from the winning tournament
+        .max_by(|&i, &j| fitness_cmp(fitness_values[i], fitness_values[j]))
+        .unwrap() // Select the candidate with the highest fitness
 }
 
+/// Fitness proportionate selection.
 pub fn select_parent_roulette(population: &[Vec<u8>], fitness_values: &[f64]) -> Vec<u8> {
-    let total_fitness: f64 = fitness_values.iter().sum();
-    if total_fitness == 0.0 {
-        return population[0].clone();
+    select_parent_roulette_with_rng(population, fitness_values, &mut thread_rng())
+}
+
+/// Same as [`select_parent_roulette`] drawing from the given RNG.
+pub fn select_parent_roulette_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    rng: &mut R,
+) -> Vec<u8> {
+    population[select_parent_roulette_index_with_rng(population, fitness_values, rng)].clone()
+}
+
+/// Same as [`select_parent_roulette_with_rng`] returning the index of the parent.
+pub fn select_parent_roulette_index_with_rng<R: Rng + ?Sized>(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    rng: &mut R,
+) -> usize {
+    // NaN fitness values get no share of the wheel
+    let total_fitness: f64 = fitness_values.iter().filter(|value| !value.is_nan()).sum();
+    if total_fitness <= 0.0 {
+        return 0;
     }
-    let pick = thread_rng().gen_range(0.0..total_fitness);
+    let pick = rng.gen_range(0.0..total_fitness);
     let mut current = 0.0;
-    for (individual, fitness_value) in population.iter().zip(fitness_values.iter()) {
+    for (index, fitness_value) in fitness_values.iter().enumerate().take(population.len()) {
+        if fitness_value.is_nan() {
+            continue;
+        }
         current += *fitness_value;
         if current > pick {
-            return individual.clone();
+            return index;
         }
     }
-    population[0].clone() // Default to the first individual if no individual is selected
+    0 // Default to the first individual if no individual is selected
 }
 
+/// One-point crossover applied with probability `crossover_rate`. Otherwise the parents are copied.
 pub fn crossover(parent1: &[u8], parent2: &[u8], crossover_rate: f64) -> (Vec<u8>, Vec<u8>) {
-    let mut rng = thread_rng();
+    crossover_with_rng(parent1, parent2, crossover_rate, &mut thread_rng())
+}
+
+/// Same as [`crossover`] drawing from the given RNG.
+pub fn crossover_with_rng<R: Rng + ?Sized>(
+    parent1: &[u8],
+    parent2: &[u8],
+    crossover_rate: f64,
+    rng: &mut R,
+) -> (Vec<u8>, Vec<u8>) {
+    let (child1, child2, _) = crossover_point_with_rng(parent1, parent2, crossover_rate, rng);
+    (child1, child2)
+}
+
+/// Same as [`crossover_with_rng`] also returning the crossover point, which is the genome length
+/// when the parents were copied.
+pub fn crossover_point_with_rng<R: Rng + ?Sized>(
+    parent1: &[u8],
+    parent2: &[u8],
+    crossover_rate: f64,
+    rng: &mut R,
+) -> (Vec<u8>, Vec<u8>, usize) {
     let random_float: f64 = rng.gen_range(0.0..=1.0);
     let mut child1: Vec<u8> = Vec::with_capacity(parent1.len());
     let mut child2: Vec<u8> = Vec::with_capacity(parent2.len());
 
-    if random_float < crossover_rate {
+    // A genome shorter than two genes has no point to cut at
+    if random_float < crossover_rate && parent1.len() > 1 {
         let crossover_point = rng.gen_range(1..parent1.len());
 
         child1.extend_from_slice(&parent1[..crossover_point]);
@@ -125,65 +257,288 @@ pub fn crossover(parent1: &[u8], parent2: &[u8], crossover_rate: f64) -> (Vec<u8
 
         child2.extend_from_slice(&parent2[..crossover_point]);
         child2.extend_from_slice(&parent1[crossover_point..]);
+        (child1, child2, crossover_point)
     } else {
-        return (parent1.to_vec(), parent2.to_vec());
+        (parent1.to_vec(), parent2.to_vec(), parent1.len())
     }
-    (child1, child2)
 }
 
+/// Flips every gene independently with probability `mutation_rate`.
 pub fn mutate(genome: &[u8], mutation_rate: f64) -> Vec<u8> {
-    let mut rng = thread_rng();
+    mutate_with_rng(genome, mutation_rate, &mut thread_rng())
+}
+
+/// Same as [`mutate`] drawing from the given RNG.
+pub fn mutate_with_rng<R: Rng + ?Sized>(genome: &[u8], mutation_rate: f64, rng: &mut R) -> Vec<u8> {
+    mutate_flips_with_rng(genome, mutation_rate, rng).0
+}
+
+/// Same as [`mutate_with_rng`] also returning the flipped positions in ascending order.
+pub fn mutate_flips_with_rng<R: Rng + ?Sized>(
+    genome: &[u8],
+    mutation_rate: f64,
+    rng: &mut R,
+) -> (Vec<u8>, Vec<usize>) {
     let mut mutated_genome = genome.to_vec();
+    let mut flipped = Vec::new();
 
-    for gene in mutated_genome.iter_mut().take(genome.len()) {
+    for (position, gene) in mutated_genome.iter_mut().enumerate() {
         if rng.gen::<f64>() < mutation_rate {
             *gene ^= 1;
+            flipped.push(position);
         }
     }
-    mutated_genome
+    (mutated_genome, flipped)
 }
 
-pub fn print_best_values(fitness_values: &[f64], population: &[Vec<u8>], generation_fitness: f64) {
+/// Logs the best genome of the population with its fitness at info level.
+pub fn log_best_values(fitness_values: &[f64], population: &[Vec<u8>], generation_fitness: f64) {
     let best_index = fitness_values
         .iter()
         .position(|&x| x == get_best_fitness(fitness_values))
         .unwrap();
-    let best_solution = &population[best_index];
-    println!("Best Final Solution: {:?}", best_solution);
-    println!(
-        "Best Final Fitness: {:?}",
-        get_genome_fitness(best_solution)
-    );
-    println!(
-        "Generation perfect fitness percentage: {:.2}",
-        generation_fitness
+    let genome: String = population[best_index]
+        .iter()
+        .map(|&gene| if gene == 0 { '0' } else { '1' })
+        .collect();
+    info!(
+        genome,
+        fitness = fitness_values[best_index],
+        generation_fitness,
+        "best final solution"
     );
 }
 
+/// Breeds the next generation through selection, crossover and mutation.
 pub fn create_new_population(
     population_size: usize,
-    population: &Vec<Vec<u8>>,
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    select_parent_mode: &str,
+    crossover_rate: f64,
+    mutation_rate: f64,
+) -> Vec<Vec<u8>> {
+    create_new_population_with_rng(
+        population_size,
+        population,
+        fitness_values,
+        select_parent_mode,
+        crossover_rate,
+        mutation_rate,
+        &mut thread_rng(),
+    )
+}
+
+/// Same as [`create_new_population`] drawing from the given RNG.
+pub fn create_new_population_with_rng<R: Rng + ?Sized>(
+    population_size: usize,
+    population: &[Vec<u8>],
     fitness_values: &[f64],
     select_parent_mode: &str,
     crossover_rate: f64,
     mutation_rate: f64,
+    rng: &mut R,
 ) -> Vec<Vec<u8>> {
     let mut new_population = vec![];
 
     for _ in 0..population_size / 2 {
-        let parent1 = select_parent(population, fitness_values, select_parent_mode);
-        let parent2 = select_parent(population, fitness_values, select_parent_mode);
-        let (offspring1, offspring2) = crossover(&parent1, &parent2, crossover_rate);
-        new_population.push(mutate(&offspring1, mutation_rate));
-        new_population.push(mutate(&offspring2, mutation_rate));
+        let parent1 = select_parent_with_rng(population, fitness_values, select_parent_mode, rng);
+        let parent2 = select_parent_with_rng(population, fitness_values, select_parent_mode, rng);
+        let (offspring1, offspring2) = crossover_with_rng(&parent1, &parent2, crossover_rate, rng);
+        new_population.push(mutate_with_rng(&offspring1, mutation_rate, rng));
+        new_population.push(mutate_with_rng(&offspring2, mutation_rate, rng));
     }
-    if population_size % 2 != 0 {
-        let parent = select_parent(population, fitness_values, select_parent_mode);
-        new_population.push(mutate(&parent, mutation_rate));
+    if !population_size.is_multiple_of(2) {
+        let parent = select_parent_with_rng(population, fitness_values, select_parent_mode, rng);
+        new_population.push(mutate_with_rng(&parent, mutation_rate, rng));
     }
     new_population
 }
 
+/// Operator parameters of a single run, as passed to [`genetic_algorithm`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GaParameters {
+    pub population_size: usize,
+    pub genome_length: usize,
+    pub max_generations: u32,
+    pub mutation_rate: f64,
+    pub crossover_rate: f64,
+    pub select_parent_mode: String,
+    pub target_generation_fitness: f64,
+}
+
+/// Fitness summary of one generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GenerationStats {
+    pub generation: u32,
+    /// Average fitness of the population.
+    pub generation_fitness: f64,
+    /// Fitness of the best genome of the population.
+    pub best_fitness: f64,
+}
+
+/// Full state of a single run. Everything needed to continue the run bit-for-bit is stored here,
+/// including the RNG, so it can be snapshotted and restored later or on another machine.
+///
+/// The state of what runs alongside the evolution is not: the generation counter of a
+/// [`DynamicPenalty`](crate::constraints::DynamicPenalty), the feasibility history and coefficient
+/// of an [`AdaptivePenalty`](crate::constraints::AdaptivePenalty), the stagnation counter of a
+/// restart policy and the progress of termination criteria. A restored run using any of them
+/// diverges from the original, so
+/// [`genetic_algorithm_with_checkpoints`](crate::checkpoint::genetic_algorithm_with_checkpoints)
+/// refuses stateful penalties, and restarts and terminations, which only apply to
+/// [`GaConfig::run`](crate::config::GaConfig::run), are never snapshotted.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GaState {
+    pub parameters: GaParameters,
+    pub generation: u32,
+    pub population: Vec<Vec<u8>>,
+    pub fitness_values: Vec<f64>,
+    pub best_population: Vec<Vec<u8>>,
+    pub best_generation: u32,
+    pub best_generation_fitness: f64,
+    pub best_fitness: f64,
+    pub solved: bool,
+    /// Number of genome evaluations so far.
+    #[serde(default)]
+    pub evaluations: u64,
+    /// Per-gene probabilities of a one kept by the estimation-of-distribution algorithms of
+    /// [`crate::eda`]. Empty for the genetic algorithm.
+    #[serde(default)]
+    pub model: Vec<f64>,
+    rng: ChaCha8Rng,
+}
+
+impl GaState {
+    /// Starts a One-Max run with a random population drawn from an RNG seeded with `seed`.
+    pub fn new(parameters: GaParameters, seed: u64) -> Self {
+        GaState::with_evaluator(parameters, seed, &mut Evaluator::default())
+    }
+
+    /// Starts a run whose random initial population is evaluated by `evaluator`.
+    pub fn with_evaluator(parameters: GaParameters, seed: u64, evaluator: &mut Evaluator) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let population = init_population_with_rng(
+            parameters.population_size,
+            parameters.genome_length,
+            &mut rng,
+        );
+        let mut evaluations = 0;
+        let fitness_values = evaluator.evaluate_population(&population, &mut evaluations);
+        GaState {
+            parameters,
+            generation: 0,
+            population,
+            fitness_values,
+            best_population: vec![],
+            best_generation: 0,
+            best_generation_fitness: 0.0,
+            best_fitness: 0.0,
+            solved: false,
+            evaluations,
+            model: vec![],
+            rng,
+        }
+    }
+
+    /// Builds a new population with `restart`, drawing from the run's RNG.
+    pub(crate) fn restart_population(&mut self, restart: &mut dyn Restart) -> Vec<Vec<u8>> {
+        restart.restart(
+            &self.population,
+            &self.fitness_values,
+            self.parameters.genome_length,
+            &mut self.rng,
+        )
+    }
+
+    /// Whether the run was solved or used all its generations.
+    pub fn is_finished(&self) -> bool {
+        self.solved || self.generation >= self.parameters.max_generations
+    }
+
+    /// Runs one generation of One-Max with the default operators of the parameters.
+    pub fn step(&mut self) -> GenerationStats {
+        let operators = Operators::from_parameters(&self.parameters);
+        self.step_with(&operators, &mut Evaluator::default())
+    }
+
+    /// Runs one generation with the given operators, evaluating it with `evaluator`.
+    pub fn step_with(
+        &mut self,
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+    ) -> GenerationStats {
+        let parameters = &self.parameters;
+        (self.population, self.fitness_values) = operators.replacement.replace_with_model(
+            &mut self.model,
+            parameters.population_size,
+            &self.population,
+            &self.fitness_values,
+            operators,
+            evaluator,
+            &mut self.evaluations,
+            &mut self.rng,
+        );
+        evaluator.update_penalty(&self.population, &self.fitness_values);
+        let generation_fitness =
+            get_generation_fitness(&self.fitness_values, parameters.population_size);
+        let best_gen_fitness = get_best_fitness(&self.fitness_values);
+
+        // Only a feasible genome at the target solves the run, however fit infeasible ones are
+        let target = evaluator.target();
+        let optimum_found =
+            self.population
+                .iter()
+                .zip(&self.fitness_values)
+                .any(|(genome, &genome_fitness)| {
+                    (genome_fitness - target).abs() < f64::EPSILON
+                        && evaluator.violation(genome) == 0.0
+                });
+        let solved = (operators.replacement.solved_by_best_genome()
+            || generation_fitness >= parameters.target_generation_fitness)
+            && optimum_found;
+        // A solved generation is reported as the best one even when its mean is not
+        if solved || generation_fitness >= self.best_fitness {
+            self.best_population = self.population.clone();
+            self.best_generation = self.generation;
+            self.best_generation_fitness = generation_fitness;
+            self.best_fitness = best_gen_fitness;
+        }
+
+        let stats = GenerationStats {
+            generation: self.generation,
+            generation_fitness,
+            best_fitness: best_gen_fitness,
+        };
+        self.solved = solved;
+        if !self.solved {
+            self.generation += 1;
+        }
+        stats
+    }
+
+    /// Returns `(generation, generation_fitness, best_fitness)` as reported by [`genetic_algorithm`].
+    pub fn outcome(&self) -> (u32, f64, f64) {
+        if self.solved {
+            let generation_fitness =
+                get_generation_fitness(&self.fitness_values, self.parameters.population_size);
+            (self.generation, generation_fitness, self.best_fitness)
+        } else {
+            // Equal to max_generations unless the run was stopped early
+            (
+                self.generation,
+                self.best_generation_fitness,
+                self.best_fitness,
+            )
+        }
+    }
+}
+
+/// Runs the genetic algorithm and returns `(generation, generation_fitness, best_fitness)`.
+///
+/// The run stops early once the average fitness reaches `target_generation_fitness` and the best
+/// genome is optimal. Otherwise `max_generations` is returned along with the best generation seen.
+/// The parameters are checked with [`validate_parameters`](crate::config::validate_parameters) before the run starts.
 #[allow(clippy::too_many_arguments)]
 pub fn genetic_algorithm(
     population_size: usize,
@@ -194,65 +549,16 @@ pub fn genetic_algorithm(
     select_parent_mode: &str,
     target_generation_fitness: f64,
     verbose: bool,
-) -> (u32, f64, f64) {
-    let target_fitness = get_target_fitness();
-    let mut population = init_population(population_size, genome_length);
-    let mut fitness_values = calculate_population_fitness(&population);
-
-    let mut best_population = vec![];
-    let mut best_generation = 0;
-    let mut best_generation_fitness = 0.0;
-    let mut best_fitness = 0.0;
-
-    for generation in 0..max_generations {
-        population = create_new_population(
-            population_size,
-            &population,
-            &fitness_values,
-            select_parent_mode,
-            crossover_rate,
-            mutation_rate,
-        );
-        fitness_values = calculate_population_fitness(&population);
-        let generation_fitness = get_generation_fitness(&fitness_values, population_size);
-        let best_gen_fitness = get_best_fitness(&fitness_values);
-
-        if verbose {
-            println!(
-                "Generation {}: Best Fitness = {} Generation Fitness Percentage: {:.2}",
-                generation, best_gen_fitness, generation_fitness
-            );
-        }
-
-        if generation_fitness >= best_fitness {
-            best_population = population.clone();
-            best_generation = generation;
-            best_generation_fitness = generation_fitness;
-            best_fitness = best_gen_fitness;
-        }
-
-        if generation_fitness >= target_generation_fitness
-            && (best_gen_fitness - target_fitness).abs() < f64::EPSILON
-        {
-            if verbose {
-                println!("Ideal solution found in generation {}.", generation);
-                print_best_values(&fitness_values, &population, generation_fitness);
-            }
-            return (generation, generation_fitness, best_fitness); // Early return
-        }
-    }
-
-    if verbose {
-        println!(
-            "Best solution found after {} generations was generation number {}.",
-            max_generations, best_generation
-        );
-        let best_fitness_values = calculate_population_fitness(&best_population);
-        print_best_values(
-            &best_fitness_values,
-            &best_population,
-            best_generation_fitness,
-        );
-    }
-    (max_generations, best_generation_fitness, best_fitness)
+) -> Result<(u32, f64, f64), ConfigError> {
+    let parameters = GaParameters {
+        population_size,
+        genome_length,
+        max_generations,
+        mutation_rate,
+        crossover_rate,
+        select_parent_mode: select_parent_mode.to_string(),
+        target_generation_fitness,
+    };
+    let mut evolution = Evolution::new(parameters, thread_rng().gen())?;
+    Ok(evolution.run(verbose, |_, _| true))
 }
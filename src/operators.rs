@@ -0,0 +1,520 @@
+//! Pluggable selection, crossover and mutation operators.
+//!
+//! The built-in operators wrap the functions of [`crate::one_max_genetic_algorithm`]. Custom
+//! operators can be plugged into a run through [`crate::config::GaConfigBuilder`].
+//!
+//! Operators may also report how they changed the genomes: the index of the selected parent, the
+//! crossover point and the flipped genes. [`create_offspring`] passes these changes to
+//! [`Fitness::evaluate_change`] so separable fitness functions skip a full re-evaluation.
+
+use rand::RngCore;
+
+use crate::constraints::Repair;
+use crate::fitness::{Change, Evaluator, Fitness};
+use crate::one_max_genetic_algorithm::{
+    crossover_point_with_rng, mutate_flips_with_rng, select_parent_index_with_rng,
+    select_parent_roulette_index_with_rng, select_parent_tournament_index_with_rng, GaParameters,
+};
+
+/// Picks a parent from the current population.
+pub trait Selection: Send + Sync {
+    fn select(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8>;
+
+    /// Same as `select` returning the index of the parent, so its fitness is known. `None`, the
+    /// default, means the operator cannot tell and `select` is called instead. Implementations
+    /// must not draw from `rng` when returning `None`.
+    fn select_index(
+        &self,
+        _population: &[Vec<u8>],
+        _fitness_values: &[f64],
+        _rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        None
+    }
+}
+
+/// Combines two parents into two children.
+pub trait Crossover: Send + Sync {
+    fn crossover(
+        &self,
+        parent1: &[u8],
+        parent2: &[u8],
+        rng: &mut dyn RngCore,
+    ) -> (Vec<u8>, Vec<u8>);
+
+    /// Same as `crossover` also returning the point of a one-point crossover: the children are
+    /// `parent1[..point] + parent2[point..]` and `parent2[..point] + parent1[point..]`, and the
+    /// point is the genome length when the parents were copied. `None`, the default, means the
+    /// children cannot be described that way. Implementations must not draw from `rng` when
+    /// returning `None`.
+    fn crossover_point(
+        &self,
+        _parent1: &[u8],
+        _parent2: &[u8],
+        _rng: &mut dyn RngCore,
+    ) -> Option<(Vec<u8>, Vec<u8>, usize)> {
+        None
+    }
+}
+
+/// Introduces random changes into a genome.
+pub trait Mutation: Send + Sync {
+    fn mutate(&self, genome: &[u8], rng: &mut dyn RngCore) -> Vec<u8>;
+
+    /// Same as `mutate` also returning the flipped positions. `None`, the default, means the
+    /// operator cannot tell. Implementations must not draw from `rng` when returning `None`.
+    fn mutate_flips(
+        &self,
+        _genome: &[u8],
+        _rng: &mut dyn RngCore,
+    ) -> Option<(Vec<u8>, Vec<usize>)> {
+        None
+    }
+}
+
+/// Selection by mode name, `"tournament"` or `"roulette"`, as done by `select_parent`.
+pub struct ModeSelection {
+    pub mode: String,
+}
+
+impl Selection for ModeSelection {
+    fn select(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8> {
+        population[select_parent_index_with_rng(population, fitness_values, &self.mode, rng)]
+            .clone()
+    }
+
+    fn select_index(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        Some(select_parent_index_with_rng(
+            population,
+            fitness_values,
+            &self.mode,
+            rng,
+        ))
+    }
+}
+
+/// Tournament selection with a fixed tournament size.
+pub struct TournamentSelection {
+    pub tournament_size: usize,
+}
+
+impl Selection for TournamentSelection {
+    fn select(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8> {
+        let index = self.select_index(population, fitness_values, rng).unwrap();
+        population[index].clone()
+    }
+
+    fn select_index(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        let tournament_size = self.tournament_size.clamp(1, population.len());
+        Some(select_parent_tournament_index_with_rng(
+            population,
+            fitness_values,
+            tournament_size,
+            rng,
+        ))
+    }
+}
+
+/// Fitness proportionate selection.
+pub struct RouletteSelection;
+
+impl Selection for RouletteSelection {
+    fn select(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8> {
+        population[select_parent_roulette_index_with_rng(population, fitness_values, rng)].clone()
+    }
+
+    fn select_index(
+        &self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        rng: &mut dyn RngCore,
+    ) -> Option<usize> {
+        Some(select_parent_roulette_index_with_rng(
+            population,
+            fitness_values,
+            rng,
+        ))
+    }
+}
+
+/// One-point crossover applied with probability `crossover_rate`.
+pub struct OnePointCrossover {
+    pub crossover_rate: f64,
+}
+
+impl Crossover for OnePointCrossover {
+    fn crossover(
+        &self,
+        parent1: &[u8],
+        parent2: &[u8],
+        rng: &mut dyn RngCore,
+    ) -> (Vec<u8>, Vec<u8>) {
+        let (child1, child2, _) =
+            crossover_point_with_rng(parent1, parent2, self.crossover_rate, rng);
+        (child1, child2)
+    }
+
+    fn crossover_point(
+        &self,
+        parent1: &[u8],
+        parent2: &[u8],
+        rng: &mut dyn RngCore,
+    ) -> Option<(Vec<u8>, Vec<u8>, usize)> {
+        Some(crossover_point_with_rng(
+            parent1,
+            parent2,
+            self.crossover_rate,
+            rng,
+        ))
+    }
+}
+
+/// Flips every gene independently with probability `mutation_rate`.
+pub struct BitFlipMutation {
+    pub mutation_rate: f64,
+}
+
+impl Mutation for BitFlipMutation {
+    fn mutate(&self, genome: &[u8], rng: &mut dyn RngCore) -> Vec<u8> {
+        mutate_flips_with_rng(genome, self.mutation_rate, rng).0
+    }
+
+    fn mutate_flips(&self, genome: &[u8], rng: &mut dyn RngCore) -> Option<(Vec<u8>, Vec<usize>)> {
+        Some(mutate_flips_with_rng(genome, self.mutation_rate, rng))
+    }
+}
+
+/// The operators used to breed a new generation.
+pub struct Operators {
+    pub selection: Box<dyn Selection>,
+    pub crossover: Box<dyn Crossover>,
+    pub mutation: Box<dyn Mutation>,
+    pub replacement: Box<dyn Replacement>,
+    /// Run on every child after crossover and after mutation, if set.
+    pub repair: Option<Box<dyn Repair>>,
+}
+
+impl Operators {
+    /// The default operators for the given parameters, matching `create_new_population`.
+    pub fn from_parameters(parameters: &GaParameters) -> Self {
+        Operators {
+            selection: Box::new(ModeSelection {
+                mode: parameters.select_parent_mode.clone(),
+            }),
+            crossover: Box::new(OnePointCrossover {
+                crossover_rate: parameters.crossover_rate,
+            }),
+            mutation: Box::new(BitFlipMutation {
+                mutation_rate: parameters.mutation_rate,
+            }),
+            replacement: Box::new(GenerationalReplacement),
+            repair: None,
+        }
+    }
+}
+
+/// Same as `create_new_population` using the given operators.
+pub fn create_new_population_with_operators(
+    population_size: usize,
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    operators: &Operators,
+    rng: &mut dyn RngCore,
+) -> Vec<Vec<u8>> {
+    let mut new_population = Vec::with_capacity(population_size);
+
+    let mutate = |genome: &[u8], rng: &mut dyn RngCore| {
+        let mut mutated = operators.mutation.mutate(genome, rng);
+        repair(operators, &mut mutated, None, rng);
+        mutated
+    };
+
+    for _ in 0..population_size / 2 {
+        let parent1 = operators.selection.select(population, fitness_values, rng);
+        let parent2 = operators.selection.select(population, fitness_values, rng);
+        let (mut offspring1, mut offspring2) =
+            operators.crossover.crossover(&parent1, &parent2, rng);
+        repair(operators, &mut offspring1, None, rng);
+        repair(operators, &mut offspring2, None, rng);
+        new_population.push(mutate(&offspring1, rng));
+        new_population.push(mutate(&offspring2, rng));
+    }
+    if !population_size.is_multiple_of(2) {
+        let parent = operators.selection.select(population, fitness_values, rng);
+        new_population.push(mutate(&parent, rng));
+    }
+    new_population
+}
+
+/// Runs the repair of `operators` on the genome. Returns `genome_fitness` unless the repair
+/// changed the genome.
+fn repair(
+    operators: &Operators,
+    genome: &mut [u8],
+    genome_fitness: Option<f64>,
+    rng: &mut dyn RngCore,
+) -> Option<f64> {
+    match &operators.repair {
+        Some(repair) if repair.repair(genome, rng) => None,
+        _ => genome_fitness,
+    }
+}
+
+/// Same as [`create_new_population_with_operators`], also returning the fitness of every child
+/// that `fitness` could derive from the changes reported by the operators. Children whose
+/// fitness is `None` need a full evaluation. Draws the same random numbers as
+/// [`create_new_population_with_operators`], so both breed the same population.
+pub fn create_offspring(
+    population_size: usize,
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    operators: &Operators,
+    fitness: &dyn Fitness,
+    rng: &mut dyn RngCore,
+) -> (Vec<Vec<u8>>, Vec<Option<f64>>) {
+    breed(
+        population_size,
+        population,
+        fitness_values,
+        fitness_values,
+        operators,
+        fitness,
+        rng,
+    )
+}
+
+/// Same as [`create_offspring`] selecting parents on `selection_values` instead of their fitness.
+pub(crate) fn breed(
+    population_size: usize,
+    population: &[Vec<u8>],
+    selection_values: &[f64],
+    fitness_values: &[f64],
+    operators: &Operators,
+    fitness: &dyn Fitness,
+    rng: &mut dyn RngCore,
+) -> (Vec<Vec<u8>>, Vec<Option<f64>>) {
+    let mut new_population = Vec::with_capacity(population_size);
+    let mut known_fitness = Vec::with_capacity(population_size);
+
+    let select = |rng: &mut dyn RngCore| match operators.selection.select_index(
+        population,
+        selection_values,
+        rng,
+    ) {
+        Some(index) => (population[index].clone(), Some(fitness_values[index])),
+        None => (
+            operators
+                .selection
+                .select(population, selection_values, rng),
+            None,
+        ),
+    };
+
+    for _ in 0..population_size / 2 {
+        let (parent1, fitness1) = select(rng);
+        let (parent2, fitness2) = select(rng);
+        for (child, child_fitness) in vary(
+            (&parent1, fitness1),
+            (&parent2, fitness2),
+            operators,
+            fitness,
+            rng,
+        ) {
+            new_population.push(child);
+            known_fitness.push(child_fitness);
+        }
+    }
+    if !population_size.is_multiple_of(2) {
+        let (parent, parent_fitness) = select(rng);
+        let (child, child_fitness) = mutate(&parent, parent_fitness, operators, fitness, rng);
+        new_population.push(child);
+        known_fitness.push(child_fitness);
+    }
+    (new_population, known_fitness)
+}
+
+/// Crosses over and mutates two parents, deriving the fitness of the children when the parents'
+/// fitness is known and the operators report their changes.
+pub(crate) fn vary(
+    (parent1, fitness1): (&[u8], Option<f64>),
+    (parent2, fitness2): (&[u8], Option<f64>),
+    operators: &Operators,
+    fitness: &dyn Fitness,
+    rng: &mut dyn RngCore,
+) -> [(Vec<u8>, Option<f64>); 2] {
+    let (mut offspring1, mut offspring2, offspring_fitness1, offspring_fitness2) =
+        match operators.crossover.crossover_point(parent1, parent2, rng) {
+            Some((offspring1, offspring2, point)) => {
+                let derive = |first: &[u8], first_fitness, second: &[u8], second_fitness| {
+                    fitness.evaluate_change(&Change::OnePointCrossover {
+                        first,
+                        first_fitness,
+                        second,
+                        second_fitness,
+                        point,
+                    })
+                };
+                let (offspring_fitness1, offspring_fitness2) = match (fitness1, fitness2) {
+                    (Some(fitness1), Some(fitness2)) => (
+                        derive(parent1, fitness1, parent2, fitness2),
+                        derive(parent2, fitness2, parent1, fitness1),
+                    ),
+                    _ => (None, None),
+                };
+                (
+                    offspring1,
+                    offspring2,
+                    offspring_fitness1,
+                    offspring_fitness2,
+                )
+            }
+            None => {
+                let (offspring1, offspring2) = operators.crossover.crossover(parent1, parent2, rng);
+                (offspring1, offspring2, None, None)
+            }
+        };
+    let offspring_fitness1 = repair(operators, &mut offspring1, offspring_fitness1, rng);
+    let offspring_fitness2 = repair(operators, &mut offspring2, offspring_fitness2, rng);
+    [
+        mutate(&offspring1, offspring_fitness1, operators, fitness, rng),
+        mutate(&offspring2, offspring_fitness2, operators, fitness, rng),
+    ]
+}
+
+fn mutate(
+    genome: &[u8],
+    genome_fitness: Option<f64>,
+    operators: &Operators,
+    fitness: &dyn Fitness,
+    rng: &mut dyn RngCore,
+) -> (Vec<u8>, Option<f64>) {
+    let (mut mutated, mutated_fitness) = match operators.mutation.mutate_flips(genome, rng) {
+        Some((mutated, flipped)) => {
+            let mutated_fitness = genome_fitness.and_then(|parent_fitness| {
+                fitness.evaluate_change(&Change::Flips {
+                    parent: genome,
+                    parent_fitness,
+                    flipped: &flipped,
+                })
+            });
+            (mutated, mutated_fitness)
+        }
+        None => (operators.mutation.mutate(genome, rng), None),
+    };
+    let mutated_fitness = repair(operators, &mut mutated, mutated_fitness, rng);
+    (mutated, mutated_fitness)
+}
+
+/// Breeds the next generation from the current one and decides which individuals survive.
+///
+/// The default, [`GenerationalReplacement`], replaces the whole population with its offspring.
+/// The niching methods of [`crate::niching`] plug in here as well.
+pub trait Replacement: Send + Sync {
+    /// Returns the next population with its fitness values, which should hold `population_size`
+    /// individuals. Children are evaluated through `evaluator`, which adds its fitness function
+    /// calls to `evaluations`.
+    #[allow(clippy::too_many_arguments)]
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>);
+
+    /// Same as [`Replacement::replace`] for replacements that keep a probability model between
+    /// generations, like the estimation-of-distribution algorithms of [`crate::eda`]. The model
+    /// lives in the run's [`GaState`](crate::GaState), so it is checkpointed with it, and is
+    /// empty at the start of a run and after the population was replaced. The default ignores it.
+    #[allow(clippy::too_many_arguments)]
+    fn replace_with_model(
+        &self,
+        _model: &mut Vec<f64>,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        self.replace(
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator,
+            evaluations,
+            rng,
+        )
+    }
+
+    /// Whether a run is solved as soon as its best genome reaches the evaluator's target, whatever
+    /// the mean fitness of the population. Replacements whose population can never converge as a
+    /// whole, like the margin-clamped models of [`crate::eda`], return true. The default, false,
+    /// also requires the mean to reach the run's target generation fitness.
+    fn solved_by_best_genome(&self) -> bool {
+        false
+    }
+}
+
+/// Replaces the whole population with as many children, bred by [`create_offspring`].
+pub struct GenerationalReplacement;
+
+impl Replacement for GenerationalReplacement {
+    fn replace(
+        &self,
+        population_size: usize,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        operators: &Operators,
+        evaluator: &mut Evaluator,
+        evaluations: &mut u64,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<u8>>, Vec<f64>) {
+        let (offspring, known_fitness) = create_offspring(
+            population_size,
+            population,
+            fitness_values,
+            operators,
+            evaluator.fitness(),
+            rng,
+        );
+        let offspring_fitness =
+            evaluator.evaluate_offspring(&offspring, &known_fitness, evaluations);
+        (offspring, offspring_fitness)
+    }
+}
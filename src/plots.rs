@@ -0,0 +1,656 @@
+//! Offline SVG charts of a sweep: convergence curves, score heatmaps and box plots.
+//!
+//! The charts are plain SVG written by hand, so they need no plotting dependency, open in any
+//! browser and can be embedded in other documents as they are.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GenerationStats;
+//! use one_max_genetic_algorithm_rust::plots::convergence_svg;
+//! use one_max_genetic_algorithm_rust::Results;
+//!
+//! let mut results = Results::new(3, 1.0);
+//! let history: Vec<GenerationStats> = (1..=3)
+//!     .map(|generation| GenerationStats {
+//!         generation,
+//!         generation_fitness: 0.3 * generation as f64,
+//!         best_fitness: 0.3 * generation as f64 + 0.1,
+//!     })
+//!     .collect();
+//! results.add_convergence(&history);
+//! let svg = convergence_svg(&results, "One-Max");
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::checkpoint::SweepCell;
+use crate::results::Results;
+use crate::sweep::{ExperimentConfig, SweepOutcome};
+
+const WIDTH: f64 = 720.0;
+const HEIGHT: f64 = 440.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const MARGIN_TOP: f64 = 50.0;
+const MARGIN_BOTTOM: f64 = 60.0;
+const BEST_COLOR: &str = "#1f77b4";
+const MEAN_COLOR: &str = "#ff7f0e";
+const MISSING_COLOR: &str = "#eeeeee";
+
+/// Maps `domain` linearly onto `range`.
+#[derive(Clone, Copy)]
+struct Scale {
+    domain: (f64, f64),
+    range: (f64, f64),
+}
+
+impl Scale {
+    fn map(&self, value: f64) -> f64 {
+        let (d0, d1) = self.domain;
+        let (r0, r1) = self.range;
+        if d1 == d0 {
+            return (r0 + r1) / 2.0;
+        }
+        r0 + (value - d0) / (d1 - d0) * (r1 - r0)
+    }
+}
+
+/// Round tick values within `[low, high]`, about `count` of them.
+pub fn nice_ticks(low: f64, high: f64, count: usize) -> Vec<f64> {
+    if high.partial_cmp(&low) != Some(std::cmp::Ordering::Greater) {
+        return vec![low];
+    }
+    let step = tick_step(low, high, count);
+    let first = (low / step).ceil() as i64;
+    (first..)
+        .map(|k| k as f64 * step)
+        .take_while(|&tick| tick <= high + step * 1e-9)
+        // Drops the floating point noise of the multiplication, like 0.6000000000000001
+        .map(|tick| (tick * 1e12).round() / 1e12)
+        .collect()
+}
+
+fn tick_step(low: f64, high: f64, count: usize) -> f64 {
+    let raw = (high - low) / count.max(1) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|multiple| multiple * magnitude)
+        .find(|&step| step >= raw)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Formats `value` with as many decimals as ticks `step` apart need.
+fn format_tick(value: f64, step: f64) -> String {
+    let mut decimals = (-step.log10().floor()).max(0.0) as usize;
+    if (step * 10f64.powi(decimals as i32)).fract().abs() > 1e-9 {
+        decimals += 1;
+    }
+    format!("{value:.decimals$}")
+}
+
+/// Heatmap color of `t` within `[0, 1]`, from red through yellow to green.
+pub fn score_rgb(t: f64) -> (u8, u8, u8) {
+    let t = t.clamp(0.0, 1.0);
+    let (r, g) = if t < 0.5 {
+        (200.0, 60.0 + 280.0 * t)
+    } else {
+        (200.0 - 280.0 * (t - 0.5), 200.0)
+    };
+    (r as u8, g as u8, 60)
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Escapes the characters with a meaning in XML and HTML.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// SVG document under construction.
+struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+impl Svg {
+    fn new(width: f64, height: f64, title: &str) -> Self {
+        let mut svg = Svg {
+            width,
+            height,
+            body: String::new(),
+        };
+        svg.push(format!(
+            r#"<rect width="{width}" height="{height}" fill="white"/>"#
+        ));
+        svg.text(width / 2.0, 28.0, title, "middle", 16.0, "");
+        svg
+    }
+
+    fn push(&mut self, element: String) {
+        self.body.push_str(&element);
+        self.body.push('\n');
+    }
+
+    fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), stroke: &str, extra: &str) {
+        self.push(format!(
+            r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="{stroke}"{extra}/>"#
+        ));
+    }
+
+    fn text(&mut self, x: f64, y: f64, text: &str, anchor: &str, size: f64, extra: &str) {
+        self.push(format!(
+            r#"<text x="{x:.1}" y="{y:.1}" text-anchor="{anchor}" font-family="sans-serif" font-size="{size}"{extra}>{}</text>"#,
+            escape(text)
+        ));
+    }
+
+    fn points(points: &[(f64, f64)]) -> String {
+        let mut text = String::new();
+        for (x, y) in points {
+            let _ = write!(text, "{x:.1},{y:.1} ");
+        }
+        text.trim_end().to_string()
+    }
+
+    fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n{2}</svg>\n",
+            self.width, self.height, self.body
+        )
+    }
+}
+
+/// Draws the axes of `x` and `y` with their ticks, grid lines and labels.
+fn axes(svg: &mut Svg, x: Scale, y: Scale, x_label: &str, y_label: &str) {
+    let (left, right) = x.range;
+    let (bottom, top) = y.range;
+    let y_step = tick_step(y.domain.0, y.domain.1, 5);
+    for tick in nice_ticks(y.domain.0, y.domain.1, 5) {
+        let position = y.map(tick);
+        svg.line((left, position), (right, position), "#dddddd", "");
+        svg.text(
+            left - 8.0,
+            position + 4.0,
+            &format_tick(tick, y_step),
+            "end",
+            11.0,
+            "",
+        );
+    }
+    let x_step = tick_step(x.domain.0, x.domain.1, 8);
+    for tick in nice_ticks(x.domain.0, x.domain.1, 8) {
+        let position = x.map(tick);
+        svg.line((position, bottom), (position, bottom + 5.0), "black", "");
+        svg.text(
+            position,
+            bottom + 18.0,
+            &format_tick(tick, x_step),
+            "middle",
+            11.0,
+            "",
+        );
+    }
+    svg.line((left, bottom), (right, bottom), "black", "");
+    svg.line((left, bottom), (left, top), "black", "");
+    svg.text(
+        (left + right) / 2.0,
+        bottom + 40.0,
+        x_label,
+        "middle",
+        13.0,
+        "",
+    );
+    let middle = (bottom + top) / 2.0;
+    svg.text(
+        18.0,
+        middle,
+        y_label,
+        "middle",
+        13.0,
+        &format!(r#" transform="rotate(-90 18 {middle:.1})""#),
+    );
+}
+
+/// Line chart of the best and mean fitness per generation averaged over the runs of `results`,
+/// each with a band from its lowest to its highest run.
+pub fn convergence_svg(results: &Results, title: &str) -> String {
+    let mut svg = Svg::new(WIDTH, HEIGHT, title);
+    let bands = &results.convergence;
+    if bands.is_empty() {
+        svg.text(
+            WIDTH / 2.0,
+            HEIGHT / 2.0,
+            "No convergence data",
+            "middle",
+            14.0,
+            "",
+        );
+        return svg.finish();
+    }
+
+    let low = bands
+        .iter()
+        .map(|band| band.mean_min.min(band.best_min))
+        .fold(f64::INFINITY, f64::min);
+    let high = bands
+        .iter()
+        .map(|band| band.best_max.max(band.mean_max))
+        .fold(f64::NEG_INFINITY, f64::max);
+    let y_step = tick_step(low, high.max(low + 1e-9), 5);
+    let x = Scale {
+        domain: (1.0, bands.len().max(2) as f64),
+        range: (MARGIN_LEFT, WIDTH - MARGIN_RIGHT),
+    };
+    let y = Scale {
+        domain: (
+            (low / y_step).floor() * y_step,
+            (high / y_step).ceil() * y_step,
+        ),
+        range: (HEIGHT - MARGIN_BOTTOM, MARGIN_TOP),
+    };
+    axes(&mut svg, x, y, "Generation", "Fitness");
+
+    // Average, lowest and highest run of every generation
+    let best: Vec<(f64, f64, f64)> = bands
+        .iter()
+        .map(|band| (band.avg_best(), band.best_min, band.best_max))
+        .collect();
+    let mean: Vec<(f64, f64, f64)> = bands
+        .iter()
+        .map(|band| (band.avg_mean(), band.mean_min, band.mean_max))
+        .collect();
+    let at = |index: usize| x.map(index as f64 + 1.0);
+    for (name, color, values) in [("best", BEST_COLOR, &best), ("mean", MEAN_COLOR, &mean)] {
+        let mut band: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(index, &(_, _, max))| (at(index), y.map(max)))
+            .collect();
+        band.extend(
+            values
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, &(_, min, _))| (at(index), y.map(min))),
+        );
+        svg.push(format!(
+            r#"<polygon class="band {name}" points="{}" fill="{color}" fill-opacity="0.2" stroke="none"/>"#,
+            Svg::points(&band)
+        ));
+        let line: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(index, &(average, _, _))| (at(index), y.map(average)))
+            .collect();
+        svg.push(format!(
+            r#"<polyline class="line {name}" points="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+            Svg::points(&line)
+        ));
+    }
+
+    let legend_x = WIDTH - MARGIN_RIGHT - 190.0;
+    for (row, (label, color)) in [
+        ("Best fitness (min-max band)", BEST_COLOR),
+        ("Mean fitness (min-max band)", MEAN_COLOR),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let legend_y = MARGIN_TOP + 10.0 + 18.0 * row as f64;
+        svg.line(
+            (legend_x, legend_y),
+            (legend_x + 20.0, legend_y),
+            color,
+            r#" stroke-width="3""#,
+        );
+        svg.text(legend_x + 26.0, legend_y + 4.0, label, "start", 11.0, "");
+    }
+    svg.finish()
+}
+
+/// Grid of the score of every cell over the mutation and crossover rates of the sweep. Colors
+/// span from the lowest to the highest score; cells that did not run are left grey.
+pub fn heatmap_svg(
+    cells: &[SweepCell],
+    mutation_rates: &[f64],
+    crossover_rates: &[f64],
+    title: &str,
+) -> String {
+    let legend_width = 90.0;
+    let mut svg = Svg::new(WIDTH + legend_width, HEIGHT, title);
+    let scores: Vec<f64> = cells.iter().map(|cell| cell.results.score).collect();
+    let low = scores.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let normalise = |score: f64| {
+        if high > low {
+            (score - low) / (high - low)
+        } else {
+            1.0
+        }
+    };
+
+    let left = MARGIN_LEFT + 20.0;
+    let cell_width = (WIDTH - MARGIN_RIGHT - left) / crossover_rates.len().max(1) as f64;
+    let cell_height = (HEIGHT - MARGIN_BOTTOM - MARGIN_TOP) / mutation_rates.len().max(1) as f64;
+    for (m, mutation_rate) in mutation_rates.iter().enumerate() {
+        let top = MARGIN_TOP + m as f64 * cell_height;
+        svg.text(
+            left - 8.0,
+            top + cell_height / 2.0 + 4.0,
+            &format!("{mutation_rate:.4}"),
+            "end",
+            11.0,
+            "",
+        );
+        for (i, _) in crossover_rates.iter().enumerate() {
+            let x = left + i as f64 * cell_width;
+            let cell = cells
+                .iter()
+                .find(|cell| cell.mutation_index == m && cell.crossover_index == i);
+            let (class, fill) = match cell {
+                Some(cell) => ("cell", hex(score_rgb(normalise(cell.results.score)))),
+                None => ("missing", MISSING_COLOR.to_string()),
+            };
+            svg.push(format!(
+                r#"<rect class="{class}" x="{x:.1}" y="{top:.1}" width="{cell_width:.1}" height="{cell_height:.1}" fill="{fill}" stroke="white"/>"#
+            ));
+            let label = cell.map_or("-".to_string(), |cell| format!("{:.3}", cell.results.score));
+            svg.text(
+                x + cell_width / 2.0,
+                top + cell_height / 2.0 + 4.0,
+                &label,
+                "middle",
+                11.0,
+                "",
+            );
+        }
+    }
+    let bottom = HEIGHT - MARGIN_BOTTOM;
+    for (i, crossover_rate) in crossover_rates.iter().enumerate() {
+        svg.text(
+            left + (i as f64 + 0.5) * cell_width,
+            bottom + 18.0,
+            &format!("{crossover_rate:.3}"),
+            "middle",
+            11.0,
+            "",
+        );
+    }
+    let right = WIDTH - MARGIN_RIGHT;
+    svg.text(
+        (left + right) / 2.0,
+        bottom + 40.0,
+        "Crossover rate",
+        "middle",
+        13.0,
+        "",
+    );
+    let middle = (bottom + MARGIN_TOP) / 2.0;
+    svg.text(
+        18.0,
+        middle,
+        "Mutation rate",
+        "middle",
+        13.0,
+        &format!(r#" transform="rotate(-90 18 {middle:.1})""#),
+    );
+
+    // Vertical color legend from the highest score at the top to the lowest at the bottom
+    if !scores.is_empty() {
+        let legend_x = WIDTH + 10.0;
+        svg.push(format!(
+            r#"<defs><linearGradient id="score" x1="0" y1="1" x2="0" y2="0"><stop offset="0" stop-color="{}"/><stop offset="0.5" stop-color="{}"/><stop offset="1" stop-color="{}"/></linearGradient></defs>"#,
+            hex(score_rgb(0.0)),
+            hex(score_rgb(0.5)),
+            hex(score_rgb(1.0))
+        ));
+        svg.push(format!(
+            r#"<rect x="{legend_x:.1}" y="{MARGIN_TOP:.1}" width="16" height="{:.1}" fill="url(#score)"/>"#,
+            bottom - MARGIN_TOP
+        ));
+        svg.text(
+            legend_x + 22.0,
+            MARGIN_TOP + 10.0,
+            &format!("{high:.3}"),
+            "start",
+            11.0,
+            "",
+        );
+        svg.text(
+            legend_x + 22.0,
+            bottom,
+            &format!("{low:.3}"),
+            "start",
+            11.0,
+            "",
+        );
+    }
+    svg.finish()
+}
+
+/// Quartiles, Tukey whiskers and outliers of a sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxStats {
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    /// Lowest value within 1.5 interquartile ranges below the first quartile.
+    pub low_whisker: f64,
+    /// Highest value within 1.5 interquartile ranges above the third quartile.
+    pub high_whisker: f64,
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Statistics of `values`, or `None` if there are none.
+    pub fn new(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let inside = |value: &&f64| **value >= q1 - fence && **value <= q3 + fence;
+        Some(BoxStats {
+            q1,
+            median: quantile(&sorted, 0.5),
+            q3,
+            low_whisker: *sorted.iter().find(inside).unwrap_or(&q1),
+            high_whisker: *sorted.iter().rev().find(inside).unwrap_or(&q3),
+            outliers: sorted
+                .iter()
+                .filter(|value| !inside(value))
+                .copied()
+                .collect(),
+        })
+    }
+}
+
+/// Linearly interpolated quantile `q` of `sorted`.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    sorted[below] + (position - below as f64) * (sorted[above] - sorted[below])
+}
+
+/// One box of the generations every run of a cell needed, per cell in grid order, with the
+/// generation limit drawn as a dashed line.
+pub fn box_plot_svg(cells: &[SweepCell], title: &str) -> String {
+    let mut cells: Vec<&SweepCell> = cells.iter().collect();
+    cells.sort_by_key(|cell| (cell.mutation_index, cell.crossover_index));
+    let box_spacing = 30.0;
+    let width = WIDTH.max(MARGIN_LEFT + MARGIN_RIGHT + box_spacing * cells.len() as f64);
+    // Extra room below the axis for the rotated configuration labels
+    let height = HEIGHT + 60.0;
+    let mut svg = Svg::new(width, height, title);
+    let bottom = height - MARGIN_BOTTOM - 60.0;
+
+    let max_generations = cells
+        .iter()
+        .map(|cell| cell.results.max_generations)
+        .max()
+        .unwrap_or(1);
+    let y = Scale {
+        domain: (0.0, max_generations.max(1) as f64),
+        range: (bottom, MARGIN_TOP),
+    };
+    let y_step = tick_step(y.domain.0, y.domain.1, 5);
+    let left = MARGIN_LEFT;
+    let right = width - MARGIN_RIGHT;
+    for tick in nice_ticks(y.domain.0, y.domain.1, 5) {
+        let position = y.map(tick);
+        svg.line((left, position), (right, position), "#dddddd", "");
+        svg.text(
+            left - 8.0,
+            position + 4.0,
+            &format_tick(tick, y_step),
+            "end",
+            11.0,
+            "",
+        );
+    }
+    svg.line((left, bottom), (right, bottom), "black", "");
+    svg.line((left, bottom), (left, MARGIN_TOP), "black", "");
+    let limit = y.map(max_generations as f64);
+    svg.line(
+        (left, limit),
+        (right, limit),
+        "#999999",
+        r#" stroke-dasharray="6 4""#,
+    );
+    svg.text(right, limit - 6.0, "generation limit", "end", 11.0, "");
+    let middle = (bottom + MARGIN_TOP) / 2.0;
+    svg.text(
+        18.0,
+        middle,
+        "Generations run",
+        "middle",
+        13.0,
+        &format!(r#" transform="rotate(-90 18 {middle:.1})""#),
+    );
+
+    let spacing = (right - left) / cells.len().max(1) as f64;
+    let half = (spacing * 0.3).min(12.0);
+    for (index, cell) in cells.iter().enumerate() {
+        let center = left + (index as f64 + 0.5) * spacing;
+        let label_y = bottom + 14.0;
+        svg.text(
+            center,
+            label_y,
+            &format!("m {:.4} c {:.3}", cell.mutation_rate, cell.crossover_rate),
+            "end",
+            10.0,
+            &format!(r#" transform="rotate(-45 {center:.1} {label_y:.1})""#),
+        );
+        let generations: Vec<f64> = cell
+            .results
+            .generations
+            .iter()
+            .map(|&generation| generation as f64)
+            .collect();
+        let Some(stats) = BoxStats::new(&generations) else {
+            continue;
+        };
+        svg.line(
+            (center, y.map(stats.low_whisker)),
+            (center, y.map(stats.q1)),
+            "black",
+            "",
+        );
+        svg.line(
+            (center, y.map(stats.q3)),
+            (center, y.map(stats.high_whisker)),
+            "black",
+            "",
+        );
+        for whisker in [stats.low_whisker, stats.high_whisker] {
+            svg.line(
+                (center - half / 2.0, y.map(whisker)),
+                (center + half / 2.0, y.map(whisker)),
+                "black",
+                "",
+            );
+        }
+        let (top, height) = (y.map(stats.q3), y.map(stats.q1) - y.map(stats.q3));
+        svg.push(format!(
+            r#"<rect class="box" x="{:.1}" y="{top:.1}" width="{:.1}" height="{height:.1}" fill="{BEST_COLOR}" fill-opacity="0.35" stroke="black"/>"#,
+            center - half,
+            2.0 * half
+        ));
+        svg.line(
+            (center - half, y.map(stats.median)),
+            (center + half, y.map(stats.median)),
+            "black",
+            r#" class="median" stroke-width="2""#,
+        );
+        for outlier in &stats.outliers {
+            svg.push(format!(
+                r#"<circle class="outlier" cx="{center:.1}" cy="{:.1}" r="3" fill="none" stroke="black"/>"#,
+                y.map(*outlier)
+            ));
+        }
+    }
+    svg.finish()
+}
+
+/// Writes `convergence.svg` of the best cell, `heatmap.svg` of the scores and `generations.svg`
+/// with the box plots of a finished sweep into `directory`, creating it if needed, and returns
+/// the paths written.
+pub fn write_sweep_plots(
+    directory: &Path,
+    config: &ExperimentConfig,
+    outcome: &SweepOutcome,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(directory)?;
+    let charts = [
+        (
+            "convergence.svg",
+            convergence_svg(
+                &outcome.best_result,
+                &format!(
+                    "Convergence of {} at mutation rate {:.4}, crossover rate {:.3} ({} runs)",
+                    config.solver,
+                    outcome.best_mutation_rate,
+                    outcome.best_crossover_rate,
+                    outcome.best_result.total_generations
+                ),
+            ),
+        ),
+        (
+            "heatmap.svg",
+            heatmap_svg(
+                &outcome.cells,
+                &config.mutation_rate_values,
+                &config.crossover_rate_values,
+                &format!("Score of {} per mutation and crossover rate", config.solver),
+            ),
+        ),
+        (
+            "generations.svg",
+            box_plot_svg(
+                &outcome.cells,
+                &format!("Generations run by {} per configuration", config.solver),
+            ),
+        ),
+    ];
+    let mut paths = Vec::with_capacity(charts.len());
+    for (name, svg) in charts {
+        let path = directory.join(name);
+        fs::write(&path, svg)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
@@ -0,0 +1,498 @@
+//! Constrained bitstring benchmarks: the 0/1 knapsack and set cover.
+//!
+//! Both implement [`Fitness`] with a normalised fitness, 1.0 at the known optimum, and report
+//! their constraint violations through [`Fitness::violation`], so they are meant to be run with
+//! a penalty, a feasibility tournament or their greedy [`Repair`] from [`crate::constraints`].
+//! Infeasible genomes score at most 0.5 even without them, and a run is only solved by a
+//! feasible genome.
+//! Instances are read from the standard benchmark formats: Pisinger's `knapPI` files and the
+//! OR-Library `mknap` files for the knapsack, and the OR-Library `scp` files for set cover.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::RngCore;
+
+use crate::constraints::Repair;
+use crate::fitness::Fitness;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Whitespace separated numbers of an instance file.
+struct Numbers<'a> {
+    tokens: std::str::SplitWhitespace<'a>,
+}
+
+impl<'a> Numbers<'a> {
+    fn new(content: &'a str) -> Self {
+        Numbers {
+            tokens: content.split_whitespace(),
+        }
+    }
+
+    fn next<T: FromStr>(&mut self, what: &str) -> io::Result<T> {
+        let token = self
+            .tokens
+            .next()
+            .ok_or_else(|| invalid_data(format!("unexpected end of file, expected {what}")))?;
+        token
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid {what} \"{token}\"")))
+    }
+
+    fn take<T: FromStr>(&mut self, count: usize, what: &str) -> io::Result<Vec<T>> {
+        (0..count).map(|_| self.next(what)).collect()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.clone().next().is_none()
+    }
+}
+
+/// A 0/1 knapsack with one or more capacity constraints. Gene `i` packs item `i`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Knapsack {
+    pub name: String,
+    pub profits: Vec<f64>,
+    /// One row of item weights per constraint.
+    pub weights: Vec<Vec<f64>>,
+    /// One capacity per constraint.
+    pub capacities: Vec<f64>,
+    /// Best known total profit, if any.
+    pub optimum: Option<f64>,
+}
+
+impl Knapsack {
+    /// Single constraint knapsack.
+    pub fn new(profits: Vec<f64>, weights: Vec<f64>, capacity: f64) -> Self {
+        Knapsack {
+            name: String::new(),
+            profits,
+            weights: vec![weights],
+            capacities: vec![capacity],
+            optimum: None,
+        }
+    }
+
+    pub fn with_optimum(mut self, optimum: f64) -> Self {
+        self.optimum = Some(optimum);
+        self
+    }
+
+    /// Number of items, which is the genome length.
+    pub fn len(&self) -> usize {
+        self.profits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profits.is_empty()
+    }
+
+    pub fn profit(&self, genome: &[u8]) -> f64 {
+        packed(genome).map(|item| self.profits[item]).sum()
+    }
+
+    /// Total weight of the packed items for every constraint.
+    pub fn loads(&self, genome: &[u8]) -> Vec<f64> {
+        self.weights
+            .iter()
+            .map(|weights| packed(genome).map(|item| weights[item]).sum())
+            .collect()
+    }
+
+    pub fn is_feasible(&self, genome: &[u8]) -> bool {
+        self.violation(genome) <= 0.0
+    }
+
+    /// Reads every instance of a file in Pisinger's format: a name line, then `n`, `c`, `z` and
+    /// `time` lines, then one `index,profit,weight,solution` line per item, instances being
+    /// separated by a `-----` line. `z` is the optimum.
+    pub fn parse_pisinger(content: &str) -> io::Result<Vec<Knapsack>> {
+        content
+            .split("-----")
+            .filter(|block| !block.trim().is_empty())
+            .map(parse_pisinger_instance)
+            .collect()
+    }
+
+    /// Reads every instance of an OR-Library `mknap` file: the number of instances, then for
+    /// each one the number of items, the number of constraints and the optimum, the profits,
+    /// one row of weights per constraint and the capacities.
+    pub fn parse_or_library(content: &str) -> io::Result<Vec<Knapsack>> {
+        let mut numbers = Numbers::new(content);
+        let count: usize = numbers.next("number of instances")?;
+        let mut instances = Vec::with_capacity(count);
+        for index in 0..count {
+            let items: usize = numbers.next("number of items")?;
+            let constraints: usize = numbers.next("number of constraints")?;
+            let optimum: f64 = numbers.next("optimum")?;
+            let profits = numbers.take(items, "profit")?;
+            let weights = (0..constraints)
+                .map(|_| numbers.take(items, "weight"))
+                .collect::<io::Result<_>>()?;
+            let capacities = numbers.take(constraints, "capacity")?;
+            instances.push(Knapsack {
+                name: format!("mknap {}", index + 1),
+                profits,
+                weights,
+                capacities,
+                // Unsolved instances are stored with an optimum of 0
+                optimum: (optimum > 0.0).then_some(optimum),
+            });
+        }
+        Ok(instances)
+    }
+
+    /// Reads a file with [`Knapsack::parse_pisinger`].
+    pub fn load_pisinger(path: &Path) -> io::Result<Vec<Knapsack>> {
+        Knapsack::parse_pisinger(&fs::read_to_string(path)?)
+    }
+
+    /// Reads a file with [`Knapsack::parse_or_library`].
+    pub fn load_or_library(path: &Path) -> io::Result<Vec<Knapsack>> {
+        Knapsack::parse_or_library(&fs::read_to_string(path)?)
+    }
+}
+
+fn parse_pisinger_instance(block: &str) -> io::Result<Knapsack> {
+    let mut lines = block.lines().map(str::trim).filter(|line| !line.is_empty());
+    let name = lines
+        .next()
+        .ok_or_else(|| invalid_data("missing instance name".to_string()))?
+        .to_string();
+    let mut header = |key: &str| -> io::Result<f64> {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid_data(format!("{name}: missing \"{key}\" line")))?;
+        match line.split_once(' ') {
+            Some((found, value)) if found == key => value
+                .trim()
+                .parse()
+                .map_err(|_| invalid_data(format!("{name}: invalid \"{key}\" value \"{value}\""))),
+            _ => Err(invalid_data(format!(
+                "{name}: expected \"{key}\" line, got \"{line}\""
+            ))),
+        }
+    };
+    let items = header("n")? as usize;
+    let capacity = header("c")?;
+    let optimum = header("z")?;
+    header("time")?;
+
+    let mut profits = Vec::with_capacity(items);
+    let mut weights = Vec::with_capacity(items);
+    for line in lines.take(items) {
+        let fields = line.replace(',', " ");
+        let mut numbers = Numbers::new(&fields);
+        let _index: usize = numbers.next("item index")?;
+        profits.push(numbers.next("profit")?);
+        weights.push(numbers.next("weight")?);
+    }
+    if profits.len() != items {
+        return Err(invalid_data(format!(
+            "{name}: expected {items} items, got {}",
+            profits.len()
+        )));
+    }
+    Ok(Knapsack {
+        name,
+        ..Knapsack::new(profits, weights, capacity).with_optimum(optimum)
+    })
+}
+
+/// Indices of the set genes.
+fn packed(genome: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    genome
+        .iter()
+        .enumerate()
+        .filter(|(_, &gene)| gene == 1)
+        .map(|(index, _)| index)
+}
+
+/// Fitness of an infeasible genome whose objective alone scores `fitness`: half of it, and at most
+/// 0.5, so no infeasible genome reaches the feasible optimum of 1.0 while infeasible genomes still
+/// rank by their objective among themselves.
+fn infeasible_fitness(fitness: f64) -> f64 {
+    fitness.clamp(0.0, 1.0) / 2.0
+}
+
+impl Fitness for Knapsack {
+    /// Profit divided by the optimum, or by the total profit if the optimum is unknown. An
+    /// overloaded knapsack scores [half of it](infeasible_fitness), at most 0.5.
+    fn evaluate(&self, genome: &[u8]) -> f64 {
+        let scale = self
+            .optimum
+            .unwrap_or_else(|| self.profits.iter().sum::<f64>());
+        let fitness = if scale > 0.0 {
+            self.profit(genome) / scale
+        } else {
+            0.0
+        };
+        if self.is_feasible(genome) {
+            fitness
+        } else {
+            infeasible_fitness(fitness)
+        }
+    }
+
+    /// 1.0 when the optimum is known. Without it a run cannot tell when it is solved.
+    fn target(&self) -> f64 {
+        if self.optimum.is_some() {
+            1.0
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Sum over the constraints of the overload relative to the capacity.
+    fn violation(&self, genome: &[u8]) -> f64 {
+        self.loads(genome)
+            .iter()
+            .zip(&self.capacities)
+            .map(|(&load, &capacity)| (load - capacity).max(0.0) / capacity.max(1.0))
+            .sum()
+    }
+}
+
+/// Greedy knapsack repair: unpacks the items of lowest profit per unit of weight until every
+/// constraint holds.
+#[derive(Clone, Debug)]
+pub struct KnapsackRepair {
+    knapsack: Knapsack,
+    /// Items from the worst profit to weight ratio to the best.
+    order: Vec<usize>,
+}
+
+impl KnapsackRepair {
+    pub fn new(knapsack: Knapsack) -> Self {
+        let ratio = |item: usize| {
+            let weight: f64 = knapsack
+                .weights
+                .iter()
+                .zip(&knapsack.capacities)
+                .map(|(weights, &capacity)| weights[item] / capacity.max(1.0))
+                .sum();
+            knapsack.profits[item] / weight.max(f64::MIN_POSITIVE)
+        };
+        let mut order: Vec<usize> = (0..knapsack.len()).collect();
+        order.sort_by(|&a, &b| ratio(a).total_cmp(&ratio(b)));
+        KnapsackRepair { knapsack, order }
+    }
+}
+
+impl Repair for KnapsackRepair {
+    fn repair(&self, genome: &mut [u8], _rng: &mut dyn RngCore) -> bool {
+        let mut loads = self.knapsack.loads(genome);
+        let overloaded = |loads: &[f64]| {
+            loads
+                .iter()
+                .zip(&self.knapsack.capacities)
+                .any(|(load, capacity)| load > capacity)
+        };
+        let mut changed = false;
+        for &item in &self.order {
+            if !overloaded(&loads) {
+                break;
+            }
+            if genome[item] == 1 {
+                genome[item] = 0;
+                for (load, weights) in loads.iter_mut().zip(&self.knapsack.weights) {
+                    *load -= weights[item];
+                }
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Unicost or weighted set cover. Gene `j` selects column `j`, which covers some of the rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetCover {
+    pub name: String,
+    pub rows: usize,
+    pub costs: Vec<f64>,
+    /// Rows covered by every column.
+    pub columns: Vec<Vec<usize>>,
+    /// Best known total cost, if any.
+    pub optimum: Option<f64>,
+}
+
+impl SetCover {
+    pub fn new(rows: usize, costs: Vec<f64>, columns: Vec<Vec<usize>>) -> Self {
+        SetCover {
+            name: String::new(),
+            rows,
+            costs,
+            columns,
+            optimum: None,
+        }
+    }
+
+    pub fn with_optimum(mut self, optimum: f64) -> Self {
+        self.optimum = Some(optimum);
+        self
+    }
+
+    pub fn cost(&self, genome: &[u8]) -> f64 {
+        packed(genome).map(|column| self.costs[column]).sum()
+    }
+
+    /// Number of columns covering every row.
+    pub fn coverage(&self, genome: &[u8]) -> Vec<usize> {
+        let mut coverage = vec![0; self.rows];
+        for column in packed(genome) {
+            for &row in &self.columns[column] {
+                coverage[row] += 1;
+            }
+        }
+        coverage
+    }
+
+    pub fn uncovered_rows(&self, genome: &[u8]) -> usize {
+        self.coverage(genome)
+            .iter()
+            .filter(|&&count| count == 0)
+            .count()
+    }
+
+    /// Reads an OR-Library `scp` file: the number of rows and columns, the cost of every column,
+    /// then for every row the number of columns covering it followed by those columns, numbered
+    /// from 1.
+    pub fn parse_or_library(content: &str) -> io::Result<SetCover> {
+        let mut numbers = Numbers::new(content);
+        let rows: usize = numbers.next("number of rows")?;
+        let column_count: usize = numbers.next("number of columns")?;
+        let costs = numbers.take(column_count, "cost")?;
+        let mut columns = vec![Vec::new(); column_count];
+        for row in 0..rows {
+            let count: usize = numbers.next("number of covering columns")?;
+            for column in numbers.take::<usize>(count, "column")? {
+                if column == 0 || column > column_count {
+                    return Err(invalid_data(format!(
+                        "row {}: column {column} out of 1..={column_count}",
+                        row + 1
+                    )));
+                }
+                columns[column - 1].push(row);
+            }
+        }
+        if !numbers.is_empty() {
+            return Err(invalid_data(
+                "unexpected data after the last row".to_string(),
+            ));
+        }
+        Ok(SetCover::new(rows, costs, columns))
+    }
+
+    /// Reads a file with [`SetCover::parse_or_library`], named after the file.
+    pub fn load_or_library(path: &Path) -> io::Result<SetCover> {
+        let mut set_cover = SetCover::parse_or_library(&fs::read_to_string(path)?)?;
+        set_cover.name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(set_cover)
+    }
+}
+
+impl Fitness for SetCover {
+    /// `1 - (cost - optimum) / total cost`, with an optimum of 0 when it is unknown, so the
+    /// optimal cover scores 1.0 and every extra unit of cost lowers the fitness. A selection
+    /// leaving rows uncovered scores [half of it](infeasible_fitness), at most 0.5.
+    fn evaluate(&self, genome: &[u8]) -> f64 {
+        let total: f64 = self.costs.iter().sum();
+        let fitness = if total > 0.0 {
+            1.0 - (self.cost(genome) - self.optimum.unwrap_or(0.0)) / total
+        } else {
+            1.0
+        };
+        if self.uncovered_rows(genome) == 0 {
+            fitness
+        } else {
+            infeasible_fitness(fitness)
+        }
+    }
+
+    /// 1.0 when the optimum is known. Without it a run cannot tell when it is solved.
+    fn target(&self) -> f64 {
+        if self.optimum.is_some() {
+            1.0
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Share of the rows left uncovered.
+    fn violation(&self, genome: &[u8]) -> f64 {
+        if self.rows == 0 {
+            return 0.0;
+        }
+        self.uncovered_rows(genome) as f64 / self.rows as f64
+    }
+}
+
+/// Greedy set cover repair: adds the column of lowest cost per newly covered row until every
+/// row is covered, then drops the columns that became redundant, most expensive first.
+#[derive(Clone, Debug)]
+pub struct SetCoverRepair {
+    set_cover: SetCover,
+}
+
+impl SetCoverRepair {
+    pub fn new(set_cover: SetCover) -> Self {
+        SetCoverRepair { set_cover }
+    }
+}
+
+impl Repair for SetCoverRepair {
+    fn repair(&self, genome: &mut [u8], _rng: &mut dyn RngCore) -> bool {
+        let set_cover = &self.set_cover;
+        let mut coverage = set_cover.coverage(genome);
+        if coverage.iter().all(|&count| count > 0) {
+            return false;
+        }
+        let mut changed = false;
+        while coverage.contains(&0) {
+            let newly_covered = |column: usize| {
+                set_cover.columns[column]
+                    .iter()
+                    .filter(|&&row| coverage[row] == 0)
+                    .count()
+            };
+            let best = (0..set_cover.costs.len())
+                .filter(|&column| genome[column] == 0 && newly_covered(column) > 0)
+                .min_by(|&a, &b| {
+                    let price =
+                        |column: usize| set_cover.costs[column] / newly_covered(column) as f64;
+                    price(a).total_cmp(&price(b))
+                });
+            // Rows no column covers cannot be repaired
+            let Some(best) = best else {
+                break;
+            };
+            genome[best] = 1;
+            for &row in &set_cover.columns[best] {
+                coverage[row] += 1;
+            }
+            changed = true;
+        }
+
+        let mut selected: Vec<usize> = packed(genome).collect();
+        selected.sort_by(|&a, &b| set_cover.costs[b].total_cmp(&set_cover.costs[a]));
+        for column in selected {
+            let rows = &set_cover.columns[column];
+            if rows.iter().all(|&row| coverage[row] > 1) {
+                genome[column] = 0;
+                for &row in rows {
+                    coverage[row] -= 1;
+                }
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,348 @@
+//! Self-contained HTML report of a sweep.
+//!
+//! [`html_report`] puts the experiment configuration, the environment it ran on, the best
+//! configuration with its [`Results`] breakdown, the charts of [`crate::plots`] and the table of
+//! every cell into a single HTML file. Styles and charts are inlined, so the file can be shared and
+//! opened without a server or an internet connection. [`json_report`] is the machine-readable
+//! counterpart for scripts.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+use crate::plots::{box_plot_svg, convergence_svg, escape, heatmap_svg};
+use crate::results::Results;
+use crate::sweep::{sweep_threads, ExperimentConfig, SweepOutcome};
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em auto;max-width:1100px;color:#222}
+h1{margin-bottom:0}
+.subtitle{color:#666;margin-top:.2em}
+table{border-collapse:collapse;margin:1em 0}
+th,td{border:1px solid #ccc;padding:.3em .6em;text-align:right}
+th{background:#f3f3f3}
+td:first-child,th:first-child{text-align:left}
+tr.best td{background:#e3f5e1;font-weight:bold}
+tr.skipped td{color:#999}
+figure{margin:1.5em 0;overflow-x:auto}
+.notice{background:#fff4e0;border:1px solid #f0c070;padding:.6em}";
+
+/// Where and how a sweep ran.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    pub crate_version: String,
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    /// Runs executed at the same time.
+    pub threads: usize,
+    pub command_line: String,
+    /// Seconds since the Unix epoch when the report was generated.
+    pub generated_at: u64,
+}
+
+impl Environment {
+    /// Environment of the current process.
+    pub fn current() -> Self {
+        Environment {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: num_cpus::get(),
+            threads: sweep_threads(),
+            command_line: std::env::args().collect::<Vec<_>>().join(" "),
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        }
+    }
+}
+
+/// Formats seconds since the Unix epoch as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_utc(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+    // Civil date of a day count, after Howard Hinnant's days_from_civil inverse
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Two column table of `rows`, whose values are already escaped HTML.
+fn key_value_table(html: &mut String, rows: &[(&str, String)]) {
+    html.push_str("<table>\n");
+    for (key, value) in rows {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{value}</td></tr>", escape(key));
+    }
+    html.push_str("</table>\n");
+}
+
+fn results_rows(results: &Results) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("Overall score", format!("{:.4}", results.score)),
+        ("Best fitness", format!("{:.4}", results.best_fitness)),
+        (
+            "Avg generation fitness",
+            format!("{:.4}", results.avg_generation_fitness),
+        ),
+        (
+            "Avg best fitness",
+            format!("{:.4}", results.avg_best_fitness),
+        ),
+        (
+            "Avg generations run",
+            format!(
+                "{:.2} of {}",
+                results.avg_generation, results.max_generations
+            ),
+        ),
+        ("Runs", results.total_generations.to_string()),
+    ];
+    if !results.diversities.is_empty() {
+        rows.extend([
+            (
+                "Avg final Hamming distance",
+                format!("{:.3}", results.avg_hamming_distance),
+            ),
+            (
+                "Avg final locus entropy",
+                format!("{:.4}", results.avg_locus_entropy),
+            ),
+            (
+                "Avg final unique genomes",
+                format!("{:.2}", results.avg_unique_genomes),
+            ),
+            (
+                "Avg final allele spread",
+                format!("{:.4}", results.avg_allele_frequency_spread),
+            ),
+        ]);
+    }
+    if !results.hypervolumes.is_empty() {
+        rows.push(("Avg hypervolume", format!("{:.4}", results.avg_hypervolume)));
+    }
+    rows
+}
+
+/// Table of the outcome of every run of `results`.
+fn runs_table(html: &mut String, results: &Results) {
+    html.push_str(
+        "<table>\n<tr><th>Run</th><th>Generations</th><th>Generation fitness</th><th>Best fitness</th></tr>\n",
+    );
+    for (run, ((generation, generation_fitness), best_fitness)) in results
+        .generations
+        .iter()
+        .zip(&results.generation_fitnesses)
+        .zip(&results.best_fitnesses)
+        .enumerate()
+    {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{generation}</td><td>{generation_fitness:.4}</td><td>{best_fitness:.4}</td></tr>",
+            run + 1
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+/// Table of every cell of the grid in sweep order, the best one highlighted and those that did not
+/// run greyed out.
+fn sweep_table(html: &mut String, config: &ExperimentConfig, outcome: &SweepOutcome) {
+    html.push_str(
+        "<table>\n<tr><th>Mutation rate</th><th>Crossover rate</th><th>Score</th><th>Best fitness</th><th>Avg best fitness</th><th>Avg generation fitness</th><th>Avg generations</th><th>Runs</th></tr>\n",
+    );
+    for (m, mutation_rate) in config.mutation_rate_values.iter().enumerate() {
+        for (i, crossover_rate) in config.crossover_rate_values.iter().enumerate() {
+            let cell = outcome
+                .cells
+                .iter()
+                .find(|cell| cell.mutation_index == m && cell.crossover_index == i);
+            let Some(cell) = cell else {
+                let _ = writeln!(
+                    html,
+                    "<tr class=\"skipped\"><td>{mutation_rate:.4}</td><td>{crossover_rate:.3}</td><td colspan=\"6\">not run</td></tr>"
+                );
+                continue;
+            };
+            let results = &cell.results;
+            let best = cell.mutation_rate == outcome.best_mutation_rate
+                && cell.crossover_rate == outcome.best_crossover_rate;
+            let _ = writeln!(
+                html,
+                "<tr{}><td>{mutation_rate:.4}</td><td>{crossover_rate:.3}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td><td>{:.2}</td><td>{}</td></tr>",
+                if best { " class=\"best\"" } else { "" },
+                results.score,
+                results.best_fitness,
+                results.avg_best_fitness,
+                results.avg_generation_fitness,
+                results.avg_generation,
+                results.total_generations
+            );
+        }
+    }
+    html.push_str("</table>\n");
+}
+
+/// Complete HTML document reporting the sweep of `config` that produced `outcome`.
+pub fn html_report(
+    config: &ExperimentConfig,
+    outcome: &SweepOutcome,
+    environment: &Environment,
+) -> String {
+    let mut html = String::new();
+    let title = format!("One-Max sweep report: {}", config.solver);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n<p class=\"subtitle\">Generated {}</p>\n",
+        escape(&title),
+        escape(&title),
+        format_utc(environment.generated_at)
+    );
+    if outcome.aborted {
+        html.push_str(
+            "<p class=\"notice\">The sweep was aborted before it finished. Only the completed cells are reported.</p>\n",
+        );
+    }
+
+    html.push_str("<h2>Best configuration</h2>\n");
+    let mut rows = vec![
+        (
+            "Mutation rate",
+            format!("{:.6}", outcome.best_mutation_rate),
+        ),
+        (
+            "Crossover rate",
+            format!("{:.6}", outcome.best_crossover_rate),
+        ),
+    ];
+    rows.extend(results_rows(&outcome.best_result));
+    key_value_table(&mut html, &rows);
+    html.push_str("<details>\n<summary>Every run of the best configuration</summary>\n");
+    runs_table(&mut html, &outcome.best_result);
+    html.push_str("</details>\n");
+
+    html.push_str("<h2>Charts</h2>\n");
+    let charts = [
+        convergence_svg(
+            &outcome.best_result,
+            "Convergence of the best configuration",
+        ),
+        heatmap_svg(
+            &outcome.cells,
+            &config.mutation_rate_values,
+            &config.crossover_rate_values,
+            "Score per mutation and crossover rate",
+        ),
+        box_plot_svg(&outcome.cells, "Generations run per configuration"),
+    ];
+    for chart in charts {
+        let _ = writeln!(html, "<figure>\n{chart}</figure>");
+    }
+
+    html.push_str("<h2>Sweep</h2>\n");
+    sweep_table(&mut html, config, outcome);
+
+    html.push_str("<h2>Configuration</h2>\n");
+    let rates = |values: &[f64]| {
+        values
+            .iter()
+            .map(|value| format!("{value:.4}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    key_value_table(
+        &mut html,
+        &[
+            ("Solver", escape(config.solver.name())),
+            ("Runs per cell", config.run_times.to_string()),
+            ("Generations", config.generations.to_string()),
+            ("Population size", config.population_size.to_string()),
+            ("Genome length", config.genome_length.to_string()),
+            ("Parent selection", escape(&config.select_parent_mode)),
+            (
+                "Target generation fitness",
+                config.target_generation_fitness.to_string(),
+            ),
+            (
+                "Target problem fitness",
+                config.target_problem_fitness.to_string(),
+            ),
+            ("Mutation rates", rates(&config.mutation_rate_values)),
+            ("Crossover rates", rates(&config.crossover_rate_values)),
+        ],
+    );
+
+    html.push_str("<h2>Environment</h2>\n");
+    key_value_table(
+        &mut html,
+        &[
+            ("Version", escape(&environment.crate_version)),
+            (
+                "Platform",
+                escape(&format!("{} {}", environment.os, environment.arch)),
+            ),
+            ("CPUs", environment.cpus.to_string()),
+            ("Concurrent runs", environment.threads.to_string()),
+            ("Command line", escape(&environment.command_line)),
+        ],
+    );
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Writes the [`html_report`] of the sweep to `path`, for the current [`Environment`] and the
+/// threads the sweep ran on.
+pub fn write_html_report(
+    path: &Path,
+    config: &ExperimentConfig,
+    outcome: &SweepOutcome,
+) -> io::Result<()> {
+    let environment = Environment {
+        threads: outcome.threads,
+        ..Environment::current()
+    };
+    fs::write(path, html_report(config, outcome, &environment))
+}
+
+/// JSON document summarising the sweep of `config` that produced `outcome` in `elapsed`: the config
+/// with the seed that reproduces it, the best rates with their full [`Results`], whether the sweep
+/// was aborted and how many cells completed.
+pub fn json_report(config: &ExperimentConfig, outcome: &SweepOutcome, elapsed: Duration) -> Value {
+    let config = ExperimentConfig {
+        seed: Some(outcome.seed),
+        ..config.clone()
+    };
+    json!({
+        "crate_version": env!("CARGO_PKG_VERSION"),
+        "config": config,
+        "seed": outcome.seed,
+        "best": {
+            "mutation_rate": outcome.best_mutation_rate,
+            "crossover_rate": outcome.best_crossover_rate,
+            "results": outcome.best_result,
+        },
+        "aborted": outcome.aborted,
+        "completed_cells": outcome.cells.len(),
+        "elapsed_seconds": elapsed.as_secs_f64(),
+    })
+}
@@ -0,0 +1,179 @@
+//! Restart strategies for runs that stagnate.
+//!
+//! A [`Restart`] builds a fresh population once the best fitness stopped improving for a number
+//! of generations, configured with
+//! [`crate::config::GaConfigBuilder::restart_on_stagnation`]. Every restart is recorded as a
+//! [`RestartEvent`] in [`crate::config::RunOutcome::restarts`].
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//! use one_max_genetic_algorithm_rust::restart::IpopRestart;
+//!
+//! let outcome = GaConfig::builder()
+//!     .population_size(10)
+//!     .max_generations(100)
+//!     .restart_on_stagnation(
+//!         15,
+//!         IpopRestart {
+//!             growth_factor: 2.0,
+//!             max_population_size: 160,
+//!         },
+//!     )
+//!     .build()
+//!     .unwrap()
+//!     .run();
+//! println!("Restarted {} times", outcome.restarts.len());
+//! ```
+
+use std::fmt;
+
+use rand::RngCore;
+
+use crate::one_max_genetic_algorithm::{mutate_with_rng, random_genome_with_rng};
+
+/// Builds the population a stagnated run continues with.
+pub trait Restart: Send {
+    /// Name used when logging the restart.
+    fn name(&self) -> &str;
+
+    /// Returns the new population. Its size becomes the population size of the following
+    /// generations. `fitness_values` belong to `population`.
+    fn restart(
+        &mut self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        genome_length: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Vec<u8>>;
+}
+
+/// A restart that happened during a run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestartEvent {
+    /// Generation the new population was bred from.
+    pub generation: u32,
+    pub strategy: String,
+    /// Best fitness of the population that was replaced.
+    pub best_fitness: f64,
+    /// Size of the new population.
+    pub population_size: usize,
+}
+
+impl fmt::Display for RestartEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} restart at generation {}: Best Fitness = {}, Population Size = {}",
+            self.strategy, self.generation, self.best_fitness, self.population_size
+        )
+    }
+}
+
+/// Indices of the population sorted from fittest to least fit.
+fn ranking(fitness_values: &[f64]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..fitness_values.len()).collect();
+    indices.sort_by(|&a, &b| fitness_values[b].total_cmp(&fitness_values[a]));
+    indices
+}
+
+/// The best genome followed by random ones, up to `size` genomes.
+fn reinitialise_keeping_best(
+    population: &[Vec<u8>],
+    fitness_values: &[f64],
+    genome_length: usize,
+    size: usize,
+    rng: &mut dyn RngCore,
+) -> Vec<Vec<u8>> {
+    let mut new_population = Vec::with_capacity(size);
+    if let Some(&best) = ranking(fitness_values).first() {
+        new_population.push(population[best].clone());
+    }
+    while new_population.len() < size {
+        new_population.push(random_genome_with_rng(genome_length, rng));
+    }
+    new_population
+}
+
+/// Re-initialises the whole population at random, keeping only the best genome.
+pub struct FullRestart;
+
+impl Restart for FullRestart {
+    fn name(&self) -> &str {
+        "Full"
+    }
+
+    fn restart(
+        &mut self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        genome_length: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Vec<u8>> {
+        reinitialise_keeping_best(
+            population,
+            fitness_values,
+            genome_length,
+            population.len(),
+            rng,
+        )
+    }
+}
+
+/// Replaces the worst `fraction` of the population with copies of the `elite` best genomes,
+/// mutated with `mutation_rate`. The rest of the population is kept.
+pub struct PartialRestart {
+    pub elite: usize,
+    pub fraction: f64,
+    pub mutation_rate: f64,
+}
+
+impl Restart for PartialRestart {
+    fn name(&self) -> &str {
+        "Partial"
+    }
+
+    fn restart(
+        &mut self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        _genome_length: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Vec<u8>> {
+        let ranking = ranking(fitness_values);
+        let elite = self.elite.clamp(1, population.len().max(1));
+        let reseeded = ((population.len() as f64 * self.fraction.clamp(0.0, 1.0)).round() as usize)
+            .min(population.len().saturating_sub(elite));
+
+        let mut new_population = population.to_vec();
+        for (offset, &index) in ranking.iter().rev().take(reseeded).enumerate() {
+            let parent = &population[ranking[offset % elite]];
+            new_population[index] = mutate_with_rng(parent, self.mutation_rate, rng);
+        }
+        new_population
+    }
+}
+
+/// IPOP-style restart: a new random population `growth_factor` times larger than the current
+/// one, up to `max_population_size`, keeping the best genome.
+pub struct IpopRestart {
+    pub growth_factor: f64,
+    pub max_population_size: usize,
+}
+
+impl Restart for IpopRestart {
+    fn name(&self) -> &str {
+        "IPOP"
+    }
+
+    fn restart(
+        &mut self,
+        population: &[Vec<u8>],
+        fitness_values: &[f64],
+        genome_length: usize,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Vec<u8>> {
+        let size = (population.len() as f64 * self.growth_factor.max(1.0)).ceil() as usize;
+        let size = size.min(self.max_population_size).max(population.len());
+        reinitialise_keeping_best(population, fitness_values, genome_length, size, rng)
+    }
+}
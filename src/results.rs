@@ -1,4 +1,55 @@
-#[derive(Clone)]
+//! Aggregation of run outcomes into a comparable score.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::diversity::Diversity;
+use crate::one_max_genetic_algorithm::GenerationStats;
+
+/// Best and mean population fitness of one generation over the runs of a [`Results`]. Runs that
+/// stopped earlier take part with their final values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConvergenceBand {
+    pub runs: usize,
+    pub best_sum: f64,
+    pub best_min: f64,
+    pub best_max: f64,
+    pub mean_sum: f64,
+    pub mean_min: f64,
+    pub mean_max: f64,
+}
+
+impl ConvergenceBand {
+    fn add(&mut self, stats: &GenerationStats) {
+        if self.runs == 0 {
+            self.best_min = stats.best_fitness;
+            self.best_max = stats.best_fitness;
+            self.mean_min = stats.generation_fitness;
+            self.mean_max = stats.generation_fitness;
+        }
+        self.runs += 1;
+        self.best_sum += stats.best_fitness;
+        self.best_min = self.best_min.min(stats.best_fitness);
+        self.best_max = self.best_max.max(stats.best_fitness);
+        self.mean_sum += stats.generation_fitness;
+        self.mean_min = self.mean_min.min(stats.generation_fitness);
+        self.mean_max = self.mean_max.max(stats.generation_fitness);
+    }
+
+    /// Best fitness averaged over the runs.
+    pub fn avg_best(&self) -> f64 {
+        self.best_sum / self.runs.max(1) as f64
+    }
+
+    /// Mean population fitness averaged over the runs.
+    pub fn avg_mean(&self) -> f64 {
+        self.mean_sum / self.runs.max(1) as f64
+    }
+}
+
+/// Aggregated outcome of several runs with the same parameters.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Results {
     pub max_generations: u32,
     pub max_fitness: f64,
@@ -12,9 +63,32 @@ pub struct Results {
     pub avg_generation_fitness: f64,
     pub avg_best_fitness: f64,
     pub best_fitness: f64,
+
+    /// Diversity of the final population of every run that reported it.
+    #[serde(default)]
+    pub diversities: Vec<Diversity>,
+    #[serde(default)]
+    pub avg_hamming_distance: f64,
+    #[serde(default)]
+    pub avg_locus_entropy: f64,
+    #[serde(default)]
+    pub avg_unique_genomes: f64,
+    #[serde(default)]
+    pub avg_allele_frequency_spread: f64,
+
+    /// Hypervolume of the Pareto front of every multi-objective run.
+    #[serde(default)]
+    pub hypervolumes: Vec<f64>,
+    #[serde(default)]
+    pub avg_hypervolume: f64,
+
+    /// Convergence of the runs that reported their history, one band per generation.
+    #[serde(default)]
+    pub convergence: Vec<ConvergenceBand>,
 }
 
 impl Results {
+    /// Empty results for runs of at most `max_generations` and a fitness of at most `max_fitness`.
     pub fn new(max_generations: u32, max_fitness: f64) -> Self {
         Results {
             max_generations,
@@ -28,9 +102,18 @@ impl Results {
             avg_generation_fitness: 0.0,
             avg_best_fitness: 0.0,
             best_fitness: 0.0,
+            diversities: Vec::new(),
+            avg_hamming_distance: 0.0,
+            avg_locus_entropy: 0.0,
+            avg_unique_genomes: 0.0,
+            avg_allele_frequency_spread: 0.0,
+            hypervolumes: Vec::new(),
+            avg_hypervolume: 0.0,
+            convergence: Vec::new(),
         }
     }
 
+    /// Adds the outcome of one run, as returned by `genetic_algorithm`, and updates the aggregates.
     pub fn add_result(&mut self, generation: u32, generation_fitness: f64, best_fitness: f64) {
         self.generations.push(generation);
         self.generation_fitnesses.push(generation_fitness);
@@ -38,6 +121,45 @@ impl Results {
         self.set_overall_values();
     }
 
+    /// Adds the diversity of the final population of one run and updates its averages. It does
+    /// not affect the score.
+    pub fn add_diversity(&mut self, diversity: Diversity) {
+        self.diversities.push(diversity);
+        let runs = self.diversities.len() as f64;
+        let average = |measure: fn(&Diversity) -> f64| {
+            self.diversities.iter().map(measure).sum::<f64>() / runs
+        };
+        self.avg_hamming_distance = average(|d| d.mean_hamming_distance);
+        self.avg_locus_entropy = average(|d| d.mean_locus_entropy);
+        self.avg_unique_genomes = average(|d| d.unique_genomes as f64);
+        self.avg_allele_frequency_spread = average(|d| d.allele_frequency_spread);
+    }
+
+    /// Adds the hypervolume of the Pareto front of one multi-objective run and updates its
+    /// average. It does not affect the score.
+    pub fn add_hypervolume(&mut self, hypervolume: f64) {
+        self.hypervolumes.push(hypervolume);
+        self.avg_hypervolume =
+            self.hypervolumes.iter().sum::<f64>() / self.hypervolumes.len() as f64;
+    }
+
+    /// Adds the statistics of every generation of one run to the convergence bands. The run is
+    /// extended with its last generation up to `max_generations`, so runs that stopped early keep
+    /// counting with their final fitness. It does not affect the score.
+    pub fn add_convergence(&mut self, history: &[GenerationStats]) {
+        let Some(last) = history.last() else {
+            return;
+        };
+        let length = history.len().max(self.max_generations as usize);
+        if self.convergence.len() < length {
+            self.convergence.resize(length, ConvergenceBand::default());
+        }
+        for (index, band) in self.convergence.iter_mut().enumerate() {
+            band.add(history.get(index).unwrap_or(last));
+        }
+    }
+
+    /// Recomputes the averages, best fitness and score from the stored runs.
     pub fn set_overall_values(&mut self) {
         self.total_generations = self.generations.len();
 
@@ -64,6 +186,7 @@ impl Results {
         self.calculate_ponderate_score();
     }
 
+    /// Weighted score from 0.0 to 1.0, rewarding high fitness and few generations.
     pub fn calculate_ponderate_score(&mut self) {
         if self.generations.is_empty() {
             self.score = 0.0;
@@ -90,19 +213,41 @@ impl Results {
             + weight_avg_best_fitness * score_avg_best_fitness;
     }
 
+    /// Score computed by [`Results::calculate_ponderate_score`].
     pub fn get_score(&self) -> f64 {
         self.score
     }
+}
 
-    #[allow(dead_code)]
-    pub fn print(&self) {
-        println!("Overall Score:          {:.3}", self.score);
-        println!("Best Fitness:           {}", self.best_fitness);
-        println!("Avg Generation Fitness: {:.3}", self.avg_generation_fitness);
-        println!("Avg Best Fitness:       {:.3}", self.avg_best_fitness);
-        println!(
+/// The aggregates, one per line.
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Overall Score:          {:.3}", self.score)?;
+        writeln!(f, "Best Fitness:           {}", self.best_fitness)?;
+        writeln!(
+            f,
+            "Avg Generation Fitness: {:.3}",
+            self.avg_generation_fitness
+        )?;
+        writeln!(f, "Avg Best Fitness:       {:.3}", self.avg_best_fitness)?;
+        write!(
+            f,
             "Avg Generations Run:    {:.3} of {}",
             self.avg_generation, self.max_generations
-        );
+        )?;
+        if !self.diversities.is_empty() {
+            write!(
+                f,
+                "\nAvg Final Diversity:    Hamming {:.2}, Entropy {:.3}, Unique {:.1}, Allele Spread {:.3}",
+                self.avg_hamming_distance,
+                self.avg_locus_entropy,
+                self.avg_unique_genomes,
+                self.avg_allele_frequency_spread
+            )?;
+        }
+        if !self.hypervolumes.is_empty() {
+            write!(f, "\nAvg Hypervolume:        {:.3}", self.avg_hypervolume)?;
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,457 @@
+//! Runtime scaling analysis: how the number of evaluations to reach the optimum grows with the
+//! genome length.
+//!
+//! [`run_scaling`] runs a solver on every genome length of a [`ScalingConfig`], records the
+//! evaluations each run needed to find the optimum with a 95% confidence interval per length, and
+//! fits `c * g(n)` for every [`ComplexityModel`] to pick the one that matches best. A run's count
+//! stops at the evaluation that first scored the optimum, and single-trajectory solvers start from
+//! one genome, so the counts follow the usual definition of run time, e.g. about `e n ln n` for
+//! the (1+1) EA.
+//!
+//! ```
+//! use one_max_genetic_algorithm_rust::scaling::{run_scaling, ScalingConfig};
+//! use one_max_genetic_algorithm_rust::solver::Solver;
+//!
+//! let config = ScalingConfig {
+//!     genome_lengths: vec![8, 16, 32],
+//!     runs: 4,
+//!     solver: Solver::OnePlusOneEa,
+//!     seed: Some(1),
+//!     ..ScalingConfig::default()
+//! };
+//! let report = run_scaling(&config).unwrap();
+//! println!("{report}");
+//! assert_eq!(report.points.len(), 3);
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{validate_parameters, ConfigError};
+use crate::evolution::Evolution;
+use crate::fitness::{Evaluator, Fitness, OneMax};
+use crate::one_max_genetic_algorithm::GaParameters;
+use crate::solver::Solver;
+use crate::sweep::sweep_threads;
+use crate::utils::distribute_run_times;
+
+/// Series of runs of one solver over increasing genome lengths.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalingConfig {
+    pub genome_lengths: Vec<usize>,
+    /// Independent runs per genome length.
+    pub runs: usize,
+    pub solver: Solver,
+    /// Population of every run. [Single-trajectory](Solver::is_single_trajectory) solvers start
+    /// from one individual instead, so their counts do not include a population they never use.
+    pub population_size: usize,
+    /// Generation limit of every run. Runs that hit it without finding the optimum are counted as
+    /// failures and left out of the statistics. Single-trajectory solvers get `population_size`
+    /// times as many generations, which keeps their evaluation budget the same.
+    pub max_generations: u32,
+    /// Mutation rate of every run, `1 / n` when `None`.
+    pub mutation_rate: Option<f64>,
+    pub crossover_rate: f64,
+    pub select_parent_mode: String,
+    /// Seed of the first run. Later runs use the following seeds, so the analysis is reproducible.
+    /// Runs are seeded randomly when `None`.
+    pub seed: Option<u64>,
+    /// Runs executed at the same time, [`sweep_threads`] by default like the sweep.
+    pub threads: usize,
+}
+
+impl Default for ScalingConfig {
+    fn default() -> Self {
+        ScalingConfig {
+            genome_lengths: vec![10, 20, 40, 80],
+            runs: 10,
+            solver: Solver::Ga,
+            population_size: 50,
+            max_generations: 10_000,
+            mutation_rate: None,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            seed: None,
+            threads: sweep_threads(),
+        }
+    }
+}
+
+impl ScalingConfig {
+    /// Parameters of every run at `genome_length`.
+    pub fn parameters(&self, genome_length: usize) -> GaParameters {
+        let (population_size, max_generations) = if self.solver.is_single_trajectory() {
+            let scale = u32::try_from(self.population_size).unwrap_or(u32::MAX);
+            (1, self.max_generations.saturating_mul(scale))
+        } else {
+            (self.population_size, self.max_generations)
+        };
+        GaParameters {
+            population_size,
+            genome_length,
+            max_generations,
+            mutation_rate: self
+                .mutation_rate
+                .unwrap_or(1.0 / genome_length.max(1) as f64),
+            crossover_rate: self.crossover_rate,
+            select_parent_mode: self.select_parent_mode.clone(),
+            // Solved as soon as the optimum shows up, whatever the rest of the population
+            target_generation_fitness: 0.0,
+        }
+    }
+
+    /// Checks the config and the parameters of every genome length up front.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.runs == 0 {
+            return Err(ConfigError::ZeroRuns);
+        }
+        if self.genome_lengths.is_empty() {
+            return Err(ConfigError::EmptyValues("genome lengths"));
+        }
+        for &genome_length in &self.genome_lengths {
+            validate_parameters(&self.parameters(genome_length))?;
+        }
+        Ok(())
+    }
+}
+
+/// Evaluations to optimum of every run at one genome length.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalingPoint {
+    pub genome_length: usize,
+    /// Evaluations of the runs that found the optimum, counted up to the one that first scored
+    /// it.
+    pub evaluations: Vec<u64>,
+    /// Runs that hit the generation limit first.
+    pub failures: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// 95% confidence interval of the mean, from Student's t distribution.
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl ScalingPoint {
+    pub fn new(genome_length: usize, evaluations: Vec<u64>, failures: usize) -> Self {
+        let count = evaluations.len() as f64;
+        let mean = if evaluations.is_empty() {
+            0.0
+        } else {
+            evaluations.iter().sum::<u64>() as f64 / count
+        };
+        let std_dev = if evaluations.len() > 1 {
+            let variance = evaluations
+                .iter()
+                .map(|&value| (value as f64 - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1.0);
+            variance.sqrt()
+        } else {
+            0.0
+        };
+        let half_width = if evaluations.len() > 1 {
+            t_critical_95(evaluations.len() - 1) * std_dev / count.sqrt()
+        } else {
+            0.0
+        };
+        ScalingPoint {
+            genome_length,
+            evaluations,
+            failures,
+            mean,
+            std_dev,
+            ci_low: mean - half_width,
+            ci_high: mean + half_width,
+        }
+    }
+}
+
+/// Two-sided 95% critical value of Student's t distribution with `degrees_of_freedom`.
+pub fn t_critical_95(degrees_of_freedom: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+    match degrees_of_freedom {
+        0 => f64::INFINITY,
+        1..=30 => TABLE[degrees_of_freedom - 1],
+        31..=60 => 2.000,
+        61..=120 => 1.980,
+        _ => 1.960,
+    }
+}
+
+/// Growth functions `g(n)` the mean evaluations are fitted against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComplexityModel {
+    Linear,
+    NLogN,
+    NSqrtN,
+    Quadratic,
+    QuadraticLogN,
+    Cubic,
+    Exponential,
+}
+
+impl ComplexityModel {
+    pub const ALL: [ComplexityModel; 7] = [
+        ComplexityModel::Linear,
+        ComplexityModel::NLogN,
+        ComplexityModel::NSqrtN,
+        ComplexityModel::Quadratic,
+        ComplexityModel::QuadraticLogN,
+        ComplexityModel::Cubic,
+        ComplexityModel::Exponential,
+    ];
+
+    pub fn growth(&self, n: f64) -> f64 {
+        match self {
+            ComplexityModel::Linear => n,
+            ComplexityModel::NLogN => n * n.ln(),
+            ComplexityModel::NSqrtN => n * n.sqrt(),
+            ComplexityModel::Quadratic => n * n,
+            ComplexityModel::QuadraticLogN => n * n * n.ln(),
+            ComplexityModel::Cubic => n * n * n,
+            ComplexityModel::Exponential => n.exp2(),
+        }
+    }
+}
+
+impl fmt::Display for ComplexityModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ComplexityModel::Linear => "n",
+            ComplexityModel::NLogN => "n log n",
+            ComplexityModel::NSqrtN => "n^1.5",
+            ComplexityModel::Quadratic => "n^2",
+            ComplexityModel::QuadraticLogN => "n^2 log n",
+            ComplexityModel::Cubic => "n^3",
+            ComplexityModel::Exponential => "2^n",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// `c * g(n)` fitted to the mean evaluations.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelFit {
+    pub model: ComplexityModel,
+    pub coefficient: f64,
+    /// Root mean squared relative error of the fit, the lower the better.
+    pub relative_error: f64,
+}
+
+/// Fits `c * g(n)` minimising the squared relative error, so every genome length weighs the same
+/// however large its evaluations are.
+pub fn fit_model(model: ComplexityModel, points: &[(f64, f64)]) -> ModelFit {
+    // With r = g / y, minimising sum (1 - c r)^2 gives c = sum r / sum r^2
+    let ratios: Vec<f64> = points.iter().map(|&(n, y)| model.growth(n) / y).collect();
+    let coefficient = ratios.iter().sum::<f64>()
+        / ratios
+            .iter()
+            .map(|r| r * r)
+            .sum::<f64>()
+            .max(f64::MIN_POSITIVE);
+    let relative_error = if ratios.is_empty() {
+        f64::INFINITY
+    } else {
+        (ratios
+            .iter()
+            .map(|r| (1.0 - coefficient * r).powi(2))
+            .sum::<f64>()
+            / ratios.len() as f64)
+            .sqrt()
+    };
+    ModelFit {
+        model,
+        coefficient,
+        relative_error,
+    }
+}
+
+/// Slope of the least squares line through `(ln n, ln y)`, the empirical polynomial degree.
+pub fn log_log_slope(points: &[(f64, f64)]) -> f64 {
+    let logs: Vec<(f64, f64)> = points.iter().map(|&(n, y)| (n.ln(), y.ln())).collect();
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance > 0.0 {
+        covariance / variance
+    } else {
+        f64::NAN
+    }
+}
+
+/// Outcome of [`run_scaling`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalingReport {
+    pub solver: Solver,
+    pub points: Vec<ScalingPoint>,
+    /// Fit of every model, best first. Empty when fewer than two lengths had successful runs.
+    pub fits: Vec<ModelFit>,
+    pub log_log_slope: f64,
+}
+
+impl ScalingReport {
+    /// Builds the report of measured points, fitting the models to the lengths with at least one
+    /// successful run.
+    pub fn new(solver: Solver, points: Vec<ScalingPoint>) -> Self {
+        let measured: Vec<(f64, f64)> = points
+            .iter()
+            .filter(|point| !point.evaluations.is_empty() && point.mean > 0.0)
+            .map(|point| (point.genome_length as f64, point.mean))
+            .collect();
+        let mut fits = Vec::new();
+        let mut slope = f64::NAN;
+        if measured.len() >= 2 {
+            fits = ComplexityModel::ALL
+                .iter()
+                .map(|&model| fit_model(model, &measured))
+                .collect();
+            fits.sort_by(|a, b| a.relative_error.total_cmp(&b.relative_error));
+            slope = log_log_slope(&measured);
+        }
+        ScalingReport {
+            solver,
+            points,
+            fits,
+            log_log_slope: slope,
+        }
+    }
+
+    /// Best matching model, if any could be fitted.
+    pub fn best_fit(&self) -> Option<&ModelFit> {
+        self.fits.first()
+    }
+}
+
+/// The table of evaluations per genome length followed by the model fits, best first.
+impl fmt::Display for ScalingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Evaluations to optimum of {}", self.solver)?;
+        writeln!(
+            f,
+            "{:>8} {:>6} {:>12} {:>12} {:>25}",
+            "Length", "Solved", "Mean", "Std Dev", "95% CI"
+        )?;
+        for point in &self.points {
+            let runs = point.evaluations.len() + point.failures;
+            writeln!(
+                f,
+                "{:>8} {:>6} {:>12.1} {:>12.1} {:>25}",
+                point.genome_length,
+                format!("{}/{}", point.evaluations.len(), runs),
+                point.mean,
+                point.std_dev,
+                format!("[{:.1}, {:.1}]", point.ci_low, point.ci_high)
+            )?;
+        }
+        let Some(best) = self.best_fit() else {
+            return write!(f, "Not enough solved lengths to fit a complexity model.");
+        };
+        writeln!(f, "Log-log slope: {:.3}", self.log_log_slope)?;
+        for fit in &self.fits {
+            writeln!(
+                f,
+                "  {:<10} c = {:<12.4} relative error {:.3}",
+                fit.model.to_string(),
+                fit.coefficient,
+                fit.relative_error
+            )?;
+        }
+        write!(
+            f,
+            "Best matching model: {:.4} * {}",
+            best.coefficient, best.model
+        )
+    }
+}
+
+/// One-Max remembering how many evaluations it took to first score the optimum. It derives no
+/// fitness from changes, so every evaluation of the run goes through it.
+#[derive(Default)]
+struct FirstOptimum {
+    calls: AtomicU64,
+    /// Number of the call that first scored the optimum, 0 until then.
+    found_at: AtomicU64,
+}
+
+impl Fitness for FirstOptimum {
+    fn evaluate(&self, genome: &[u8]) -> f64 {
+        let call = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        let fitness = OneMax.evaluate(genome);
+        if (fitness - OneMax.target()).abs() < f64::EPSILON {
+            // Only the first one counts
+            let _ = self
+                .found_at
+                .compare_exchange(0, call, Ordering::Relaxed, Ordering::Relaxed);
+        }
+        fitness
+    }
+}
+
+/// Evaluations the run needed to reach the optimum, up to the one that scored it, or `None` if
+/// it ran out of generations.
+fn evaluations_to_optimum(parameters: GaParameters, solver: Solver, seed: u64) -> Option<u64> {
+    let operators = Arc::new(solver.operators(&parameters));
+    let counter = Arc::new(FirstOptimum::default());
+    let evaluator = Evaluator::new(Arc::clone(&counter) as Arc<dyn Fitness>);
+    let mut evolution = Evolution::with_evaluator(parameters, seed, operators, evaluator)
+        .expect("parameters are validated before the analysis starts");
+    evolution.run(false, |_, _| true);
+    evolution
+        .state()
+        .solved
+        .then(|| counter.found_at.load(Ordering::Relaxed))
+}
+
+/// Runs `config.runs` runs per genome length and fits the complexity models to the results, once
+/// the config is [validated](ScalingConfig::validate).
+pub fn run_scaling(config: &ScalingConfig) -> Result<ScalingReport, ConfigError> {
+    config.validate()?;
+    let mut points = Vec::with_capacity(config.genome_lengths.len());
+    let mut next_seed = config.seed;
+    for &genome_length in &config.genome_lengths {
+        let parameters = config.parameters(genome_length);
+        let seeds: Vec<u64> = (0..config.runs)
+            .map(|_| match next_seed.as_mut() {
+                Some(seed) => {
+                    let current = *seed;
+                    *seed = seed.wrapping_add(1);
+                    current
+                }
+                None => thread_rng().gen(),
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(config.runs);
+        let mut remaining = seeds.as_slice();
+        for batch in distribute_run_times(config.threads, config.runs) {
+            let (batch_seeds, rest) = remaining.split_at(batch.min(remaining.len()));
+            remaining = rest;
+            thread::scope(|scope| {
+                let handles: Vec<_> = batch_seeds
+                    .iter()
+                    .map(|&seed| {
+                        let parameters = parameters.clone();
+                        scope.spawn(move || evaluations_to_optimum(parameters, config.solver, seed))
+                    })
+                    .collect();
+                results.extend(handles.into_iter().map(|handle| handle.join().unwrap()));
+            });
+        }
+
+        let failures = results.iter().filter(|result| result.is_none()).count();
+        let evaluations = results.into_iter().flatten().collect();
+        points.push(ScalingPoint::new(genome_length, evaluations, failures));
+    }
+    Ok(ScalingReport::new(config.solver, points))
+}
@@ -0,0 +1,170 @@
+//! The algorithm families a sweep can run.
+//!
+//! Every [`Solver`] other than the genetic algorithm swaps the replacement of the default
+//! operators for one of [`crate::eda`], [`crate::local_search`] or [`crate::multi_objective`], so
+//! the sweep, its checkpoints and [`Results`](crate::Results) stay the same for all of them.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::eda::{CompactGa, Pbil, Umda};
+use crate::local_search::{
+    CoolingSchedule, HillClimbing, MuPlusOneEa, OnePlusLambdaEa, OnePlusOneEa,
+    RandomizedLocalSearch, SimulatedAnnealing,
+};
+use crate::multi_objective::{MultiObjective, Nsga2Replacement, OneMinMax};
+use crate::one_max_genetic_algorithm::GaParameters;
+use crate::operators::{Operators, Replacement};
+
+/// Algorithm family a sweep runs.
+///
+/// The estimation-of-distribution algorithms read their main parameter from the crossover rate
+/// axis of the sweep: UMDA its selection ratio and PBIL its learning rate, while PBIL also uses
+/// the mutation rate as its mutation probability. The compact GA uses the population size as its
+/// virtual population size, the (1+λ) EA as λ and the (μ+1) EA as μ. The local search baselines
+/// ignore both rates. NSGA-II breeds like the genetic algorithm but keeps the survivors along the
+/// OneMinMax front, and its runs are solved once they reach the all-ones end of it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Solver {
+    #[default]
+    Ga,
+    Umda,
+    Cga,
+    Pbil,
+    Rls,
+    #[serde(rename = "1+1-ea")]
+    OnePlusOneEa,
+    #[serde(rename = "1+lambda-ea")]
+    OnePlusLambdaEa,
+    #[serde(rename = "mu+1-ea")]
+    MuPlusOneEa,
+    HcFirst,
+    HcBest,
+    Sa,
+    Nsga2,
+}
+
+impl Solver {
+    pub const ALL: [Solver; 12] = [
+        Solver::Ga,
+        Solver::Umda,
+        Solver::Cga,
+        Solver::Pbil,
+        Solver::Rls,
+        Solver::OnePlusOneEa,
+        Solver::OnePlusLambdaEa,
+        Solver::MuPlusOneEa,
+        Solver::HcFirst,
+        Solver::HcBest,
+        Solver::Sa,
+        Solver::Nsga2,
+    ];
+
+    /// Name used on the command line and in checkpoints.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Solver::Ga => "ga",
+            Solver::Umda => "umda",
+            Solver::Cga => "cga",
+            Solver::Pbil => "pbil",
+            Solver::Rls => "rls",
+            Solver::OnePlusOneEa => "1+1-ea",
+            Solver::OnePlusLambdaEa => "1+lambda-ea",
+            Solver::MuPlusOneEa => "mu+1-ea",
+            Solver::HcFirst => "hc-first",
+            Solver::HcBest => "hc-best",
+            Solver::Sa => "sa",
+            Solver::Nsga2 => "nsga2",
+        }
+    }
+
+    /// Replacement running this solver with `parameters`, or `None` for the genetic algorithm.
+    pub fn replacement(&self, parameters: &GaParameters) -> Option<Box<dyn Replacement>> {
+        let replacement: Box<dyn Replacement> = match self {
+            Solver::Ga => return None,
+            Solver::Umda => Box::new(Umda::new(parameters.crossover_rate)),
+            Solver::Cga => Box::new(CompactGa::new(parameters.population_size)),
+            Solver::Pbil => Box::new(Pbil {
+                mutation_probability: parameters.mutation_rate,
+                ..Pbil::new(parameters.crossover_rate)
+            }),
+            Solver::Rls => Box::new(RandomizedLocalSearch),
+            Solver::OnePlusOneEa => Box::new(OnePlusOneEa::new()),
+            Solver::OnePlusLambdaEa => Box::new(OnePlusLambdaEa::new(parameters.population_size)),
+            Solver::MuPlusOneEa => Box::new(MuPlusOneEa::new()),
+            Solver::HcFirst => Box::new(HillClimbing::first_improvement()),
+            Solver::HcBest => Box::new(HillClimbing::best_improvement()),
+            Solver::Sa => Box::new(simulated_annealing(parameters)),
+            Solver::Nsga2 => Box::new(Nsga2Replacement::new(self.objective()?)),
+        };
+        Some(replacement)
+    }
+
+    /// Whether the solver continues from a single solution and ignores the population size, so
+    /// one initial individual is all it needs.
+    pub fn is_single_trajectory(&self) -> bool {
+        matches!(
+            self,
+            Solver::Rls | Solver::OnePlusOneEa | Solver::HcFirst | Solver::HcBest | Solver::Sa
+        )
+    }
+
+    /// Objectives a multi-objective solver keeps its population along, whose front's hypervolume
+    /// the sweep records, or `None` for single-objective solvers.
+    pub fn objective(&self) -> Option<Arc<dyn MultiObjective>> {
+        match self {
+            Solver::Nsga2 => Some(Arc::new(OneMinMax)),
+            _ => None,
+        }
+    }
+
+    /// Operators of `parameters` running this solver.
+    pub fn operators(&self, parameters: &GaParameters) -> Operators {
+        let mut operators = Operators::from_parameters(parameters);
+        if let Some(replacement) = self.replacement(parameters) {
+            operators.replacement = replacement;
+        }
+        operators
+    }
+}
+
+/// Annealing that starts out accepting the loss of one One-Max gene with probability 1/e and
+/// cools geometrically to a hundredth of that temperature over the run's evaluation budget.
+fn simulated_annealing(parameters: &GaParameters) -> SimulatedAnnealing {
+    let initial_temperature = 1.0 / parameters.genome_length.max(1) as f64;
+    let budget = (parameters.max_generations as f64 * parameters.population_size as f64).max(1.0);
+    SimulatedAnnealing::new(
+        initial_temperature,
+        CoolingSchedule::Geometric {
+            alpha: 0.01f64.powf(1.0 / budget),
+        },
+    )
+}
+
+impl fmt::Display for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Solver {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.to_lowercase();
+        Solver::ALL
+            .into_iter()
+            .find(|solver| solver.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Solver::ALL.iter().map(Solver::name).collect();
+                format!(
+                    "unknown solver \"{name}\", expected one of {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,533 @@
+//! Grid sweep over mutation and crossover rates.
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use core_affinity::CoreId;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use tqdm::pbar;
+use tracing::{debug, field, info, info_span, warn};
+
+use crate::checkpoint::{SweepCell, SweepCheckpoint};
+use crate::config::{validate_parameters, ConfigError};
+use crate::error::Error;
+use crate::evolution::Evolution;
+use crate::multi_objective::population_hypervolume;
+use crate::one_max_genetic_algorithm::{GaParameters, GenerationStats};
+use crate::results::Results;
+use crate::solver::Solver;
+use crate::utils::distribute_run_times;
+
+/// Everything that defines a sweep. Two sweeps with the same config are interchangeable, which is
+/// what a checkpoint is checked against before resuming.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentConfig {
+    /// Number of independent runs per mutation/crossover cell.
+    pub run_times: usize,
+    /// Maximum generations of every run.
+    pub generations: u32,
+    pub population_size: usize,
+    pub genome_length: usize,
+    /// `"tournament"` or `"roulette"`.
+    pub select_parent_mode: String,
+    /// Average population fitness at which a run stops early.
+    pub target_generation_fitness: f64,
+    /// Cell score at which the whole sweep stops.
+    pub target_problem_fitness: f64,
+    pub mutation_rate_values: Vec<f64>,
+    pub crossover_rate_values: Vec<f64>,
+    /// Algorithm every run uses, the genetic algorithm by default.
+    #[serde(default)]
+    pub solver: Solver,
+    /// Seed every run derives its own from with [`run_seed`], so the sweep can be reproduced.
+    /// Drawn at random when `None`, [`SweepOutcome::seed`] tells which one was used.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl ExperimentConfig {
+    /// Checks the config up front, including the parameters of every cell of the grid, so a sweep
+    /// fails before its first run rather than panicking in one of them.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.run_times == 0 {
+            return Err(ConfigError::ZeroRuns);
+        }
+        if self.mutation_rate_values.is_empty() {
+            return Err(ConfigError::EmptyValues("mutation rates"));
+        }
+        if self.crossover_rate_values.is_empty() {
+            return Err(ConfigError::EmptyValues("crossover rates"));
+        }
+        for &mutation_rate in &self.mutation_rate_values {
+            for &crossover_rate in &self.crossover_rate_values {
+                validate_parameters(&GaParameters {
+                    population_size: self.population_size,
+                    genome_length: self.genome_length,
+                    max_generations: self.generations,
+                    mutation_rate,
+                    crossover_rate,
+                    select_parent_mode: self.select_parent_mode.clone(),
+                    target_generation_fitness: self.target_generation_fitness,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the sweep checkpoint lives and whether finished cells stored in it should be skipped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckpointOptions {
+    pub path: PathBuf,
+    pub resume: bool,
+}
+
+/// Best cell found by [`process_genetic_algorithm`].
+#[derive(Clone)]
+pub struct SweepOutcome {
+    pub best_mutation_rate: f64,
+    pub best_crossover_rate: f64,
+    pub best_result: Results,
+    /// Whether the sweep was stopped by [`SweepObserver::should_abort`] before finishing.
+    pub aborted: bool,
+    /// Every completed cell of the sweep, including those replayed from the checkpoint.
+    pub cells: Vec<SweepCell>,
+    /// Seed the runs were derived from, the one of the config or of the resumed checkpoint.
+    pub seed: u64,
+    /// Runs executed at the same time.
+    pub threads: usize,
+}
+
+/// Receives the progress of a sweep as it happens, e.g. to draw it live. Runs call it from their
+/// own threads, `worker` being the slot of the run within its thread batch. Every method does
+/// nothing by default.
+pub trait SweepObserver: Send + Sync {
+    fn cell_started(
+        &self,
+        _mutation_index: usize,
+        _crossover_index: usize,
+        _mutation_rate: f64,
+        _crossover_rate: f64,
+    ) {
+    }
+
+    fn run_started(&self, _worker: usize) {}
+
+    fn generation(&self, _worker: usize, _stats: &GenerationStats) {}
+
+    fn run_finished(&self, _worker: usize) {}
+
+    /// Called for cells replayed from the checkpoint as well.
+    fn cell_finished(&self, _mutation_index: usize, _crossover_index: usize, _score: f64) {}
+
+    /// Polled between generations. Once it returns true the running runs stop, the unfinished
+    /// cell is discarded so a resumed sweep runs it again, and the sweep returns what it has.
+    fn should_abort(&self) -> bool {
+        false
+    }
+}
+
+/// Number of runs a sweep executes at the same time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jobs {
+    /// A fixed number of runs.
+    Count(usize),
+    /// One run per CPU.
+    All,
+    /// One run per CPU, leaving this many CPUs free.
+    AllMinus(usize),
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        Jobs::AllMinus(2)
+    }
+}
+
+impl Jobs {
+    /// Concurrent runs on a machine with `cpus` CPUs, never less than one.
+    pub fn threads_for(&self, cpus: usize) -> usize {
+        let threads = match *self {
+            Jobs::Count(count) => count,
+            Jobs::All => cpus,
+            Jobs::AllMinus(free) => cpus.saturating_sub(free),
+        };
+        threads.max(1)
+    }
+
+    /// Concurrent runs on this machine.
+    pub fn threads(&self) -> usize {
+        self.threads_for(num_cpus::get())
+    }
+}
+
+impl FromStr for Jobs {
+    type Err = String;
+
+    /// Parses `N`, `all` or `all-N`.
+    fn from_str(jobs: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid job count \"{jobs}\", expected a number, all or all-N");
+        let lowercase = jobs.trim().to_ascii_lowercase();
+        match lowercase.strip_prefix("all") {
+            Some("") => Ok(Jobs::All),
+            Some(rest) => rest
+                .strip_prefix('-')
+                .and_then(|free| free.trim().parse().ok())
+                .map(Jobs::AllMinus)
+                .ok_or_else(invalid),
+            None => match lowercase.parse() {
+                Ok(count) if count > 0 => Ok(Jobs::Count(count)),
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Jobs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Jobs::Count(count) => write!(f, "{count}"),
+            Jobs::All => write!(f, "all"),
+            Jobs::AllMinus(free) => write!(f, "all-{free}"),
+        }
+    }
+}
+
+/// Number of runs a sweep executes at the same time by default: the CPU count minus two, at least
+/// one.
+pub fn sweep_threads() -> usize {
+    Jobs::default().threads()
+}
+
+/// How the runs of a sweep are spread over the machine. Unlike the [`ExperimentConfig`] it does
+/// not change the results, so a sweep can be resumed with other workers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Workers {
+    pub jobs: Jobs,
+    /// Pin the k-th run of every thread batch to the k-th core, so the scheduler does not move
+    /// runs around and timings stay comparable.
+    pub pin_cores: bool,
+}
+
+/// Cores to pin workers to, none when pinning is off or the cores cannot be listed.
+fn pinned_cores(workers: &Workers) -> Vec<CoreId> {
+    if !workers.pin_cores {
+        return Vec::new();
+    }
+    let cores = core_affinity::get_core_ids().unwrap_or_default();
+    if cores.is_empty() {
+        warn!("the cores of this machine cannot be listed, runs are not pinned");
+    }
+    cores
+}
+
+fn checkpoint_error(options: &CheckpointOptions) -> impl FnOnce(io::Error) -> Error + '_ {
+    |source| Error::Checkpoint {
+        path: options.path.clone(),
+        source,
+    }
+}
+
+fn open_checkpoint(
+    config: &ExperimentConfig,
+    options: &CheckpointOptions,
+) -> Result<SweepCheckpoint, Error> {
+    if !options.resume || !options.path.exists() {
+        return Ok(SweepCheckpoint::new(config.clone()));
+    }
+    let checkpoint = SweepCheckpoint::load(&options.path).map_err(checkpoint_error(options))?;
+    if !checkpoint.is_compatible(config) {
+        return Err(Error::IncompatibleCheckpoint(options.path.clone()));
+    }
+    info!(
+        path = %options.path.display(),
+        cells = checkpoint.cells.len(),
+        "resuming from checkpoint"
+    );
+    Ok(checkpoint)
+}
+
+/// Seed of the `run`-th run of the cell at `mutation_rate` and `crossover_rate`, mixed from the
+/// sweep `seed` with SplitMix64 so neighbouring runs and cells get unrelated streams.
+pub fn run_seed(seed: u64, mutation_rate: f64, crossover_rate: f64, run: usize) -> u64 {
+    let mix = |value: u64| {
+        let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    [
+        mutation_rate.to_bits(),
+        crossover_rate.to_bits(),
+        run as u64,
+    ]
+    .into_iter()
+    .fold(mix(seed), |state, value| mix(state ^ value))
+}
+
+/// Runs `config.run_times` independent runs of one cell, spread over the given thread batches.
+pub fn run_cell(
+    config: &ExperimentConfig,
+    mutation_rate: f64,
+    crossover_rate: f64,
+    distributed_run_times: &[usize],
+) -> Results {
+    run_cell_observed(
+        config,
+        mutation_rate,
+        crossover_rate,
+        distributed_run_times,
+        None,
+        &[],
+    )
+}
+
+/// [`run_cell`] reporting every run and generation to `observer`. The k-th run of every batch is
+/// pinned to `cores[k % cores.len()]`, runs are left to the scheduler when `cores` is empty.
+pub fn run_cell_observed(
+    config: &ExperimentConfig,
+    mutation_rate: f64,
+    crossover_rate: f64,
+    distributed_run_times: &[usize],
+    observer: Option<&Arc<dyn SweepObserver>>,
+    cores: &[CoreId],
+) -> Results {
+    let cell_span = info_span!("cell", mutation_rate, crossover_rate);
+    let _cell = cell_span.enter();
+    let result = Arc::new(Mutex::new(Results::new(config.generations, 1.0)));
+    let mut first_run = 0;
+    // Outer loop to control the number of iterations based on MAX_THREADS
+    for &local_run_times in distributed_run_times {
+        let mut handles = vec![];
+
+        for worker in 0..local_run_times {
+            let run = first_run + worker;
+            let result_clone = Arc::clone(&result);
+            let config = config.clone();
+            let observer = observer.cloned();
+            let cell_span = cell_span.clone();
+            let core = (!cores.is_empty()).then(|| cores[worker % cores.len()]);
+            let handle = thread::spawn(move || {
+                if let Some(core) = core {
+                    if !core_affinity::set_for_current(core) {
+                        warn!(core = core.id, "could not pin run to core");
+                    }
+                }
+                let parameters = GaParameters {
+                    population_size: config.population_size,
+                    genome_length: config.genome_length,
+                    max_generations: config.generations,
+                    mutation_rate,
+                    crossover_rate,
+                    select_parent_mode: config.select_parent_mode.clone(),
+                    target_generation_fitness: config.target_generation_fitness,
+                };
+                let operators = Arc::new(config.solver.operators(&parameters));
+                let seed = config.seed.map_or_else(
+                    || thread_rng().gen(),
+                    |seed| run_seed(seed, mutation_rate, crossover_rate, run),
+                );
+                let _run = info_span!(parent: &cell_span, "run", worker, seed).entered();
+                let mut evolution = Evolution::with_operators(parameters, seed, operators)
+                    .expect("cell parameters are validated before the sweep starts");
+                if let Some(observer) = &observer {
+                    observer.run_started(worker);
+                }
+                let mut history = Vec::with_capacity(config.generations as usize);
+                let (generation, generation_fitness, best_fitness) =
+                    evolution.run(false, |_, stats| {
+                        history.push(*stats);
+                        match &observer {
+                            Some(observer) => {
+                                observer.generation(worker, stats);
+                                !observer.should_abort()
+                            }
+                            None => true,
+                        }
+                    });
+                debug!(generation, generation_fitness, best_fitness, "run finished");
+                if let Some(observer) = &observer {
+                    observer.run_finished(worker);
+                }
+                let hypervolume = config.solver.objective().map(|objective| {
+                    population_hypervolume(evolution.population(), objective.as_ref())
+                });
+                let mut result = result_clone.lock().unwrap();
+                result.add_result(generation, generation_fitness, best_fitness);
+                result.add_diversity(evolution.diversity());
+                result.add_convergence(&history);
+                if let Some(hypervolume) = hypervolume {
+                    result.add_hypervolume(hypervolume);
+                }
+            });
+
+            handles.push(handle);
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        first_run += local_run_times;
+        if observer.is_some_and(|observer| observer.should_abort()) {
+            break;
+        }
+    }
+    let result = result.lock().unwrap();
+    result.clone()
+}
+
+/// Sweeps every mutation and crossover rate of `config` and returns the best scoring cell.
+///
+/// A crossover row is abandoned once a cell's score is more than about 11% above the previous
+/// cell's, that is when the previous score is below 90% of it, and the whole sweep stops when a
+/// cell reaches `target_problem_fitness`. With `checkpoint_options` set, every completed cell is
+/// persisted so an interrupted sweep can be resumed. Progress is shown on a bar when stdout is a
+/// terminal. The config is [validated](ExperimentConfig::validate) before anything runs.
+pub fn process_genetic_algorithm(
+    config: &ExperimentConfig,
+    checkpoint_options: Option<&CheckpointOptions>,
+) -> Result<SweepOutcome, Error> {
+    process_genetic_algorithm_observed(config, checkpoint_options, None, &Workers::default())
+}
+
+/// [`process_genetic_algorithm`] running on `workers` and reporting its progress to `observer`,
+/// which replaces the progress bar and can abort the sweep.
+pub fn process_genetic_algorithm_observed(
+    config: &ExperimentConfig,
+    checkpoint_options: Option<&CheckpointOptions>,
+    observer: Option<Arc<dyn SweepObserver>>,
+    workers: &Workers,
+) -> Result<SweepOutcome, Error> {
+    config.validate()?;
+    let sweep_span = info_span!("sweep", solver = %config.solver, seed = field::Empty);
+    let _sweep = sweep_span.enter();
+    let mut checkpoint = match checkpoint_options {
+        Some(options) => open_checkpoint(config, options)?,
+        None => SweepCheckpoint::new(config.clone()),
+    };
+    if checkpoint.config.seed.is_none() && !checkpoint.cells.is_empty() {
+        // Checkpoints written before sweeps were seeded: the stored cells ran unseeded
+        warn!(
+            completed_cells = checkpoint.cells.len(),
+            "resuming a checkpoint without a seed, only the cells run from now on are reproducible"
+        );
+    }
+    let seed = *checkpoint
+        .config
+        .seed
+        .get_or_insert_with(|| thread_rng().gen());
+    sweep_span.record("seed", seed);
+    let config = &checkpoint.config.clone();
+    let mutation_rate_values = &config.mutation_rate_values;
+    let crossover_rate_values = &config.crossover_rate_values;
+
+    // Calculate the number of threads of each iteration based on MAX_THREADS
+    let max_threads = workers.jobs.threads();
+    let cores = pinned_cores(workers);
+    let distributed_run_times: Vec<usize> = distribute_run_times(max_threads, config.run_times);
+
+    let total_iterations = mutation_rate_values.len() * crossover_rate_values.len();
+    // The progress bar would only clutter a redirected output
+    let show_progress = observer.is_none() && io::stdout().is_terminal();
+    let mut pbar = show_progress.then(|| pbar(Some(total_iterations)));
+    let mut aborted = false;
+
+    let mut best_mutation_rate: f64 = 0.0;
+    let mut best_crossover_rate: f64 = 0.0;
+    let mut prev_best_score: f64 = 0.0;
+
+    let mut best_result: Results = Results::new(config.generations, 1.0);
+    let mut score: f64;
+
+    'sweep: for (m, &mutation_rate) in mutation_rate_values.iter().enumerate() {
+        let mut prev_local_score: f64 = 0.0;
+
+        for (i, &crossover_rate) in crossover_rate_values.iter().enumerate() {
+            if let Some(observer) = &observer {
+                observer.cell_started(m, i, mutation_rate, crossover_rate);
+            }
+            // Cells already stored in the checkpoint are replayed so the skipping logic below stays the same
+            let result = match checkpoint.find(m, i) {
+                Some(result) => result.clone(),
+                None => {
+                    let result = run_cell_observed(
+                        config,
+                        mutation_rate,
+                        crossover_rate,
+                        &distributed_run_times,
+                        observer.as_ref(),
+                        &cores,
+                    );
+                    if observer
+                        .as_ref()
+                        .is_some_and(|observer| observer.should_abort())
+                    {
+                        aborted = true;
+                        break 'sweep;
+                    }
+                    checkpoint.add_cell(SweepCell {
+                        mutation_index: m,
+                        crossover_index: i,
+                        mutation_rate,
+                        crossover_rate,
+                        results: result.clone(),
+                    });
+                    if let Some(options) = checkpoint_options {
+                        checkpoint
+                            .save(&options.path)
+                            .map_err(checkpoint_error(options))?;
+                    }
+                    result
+                }
+            };
+            score = result.get_score();
+            info!(mutation_rate, crossover_rate, score, "cell finished");
+            if let Some(observer) = &observer {
+                observer.cell_finished(m, i, score);
+            }
+
+            // General score check
+            if score >= prev_best_score {
+                best_mutation_rate = mutation_rate;
+                best_crossover_rate = crossover_rate;
+                best_result = result.clone();
+                prev_best_score = score;
+            }
+
+            if prev_local_score < (score * 0.9) && i != 0 {
+                // Skip this loop since the score is not improving
+                if let Some(pbar) = pbar.as_mut() {
+                    pbar.update(crossover_rate_values.len() - i).unwrap();
+                }
+                break;
+            }
+
+            prev_local_score = score;
+            if let Some(pbar) = pbar.as_mut() {
+                pbar.update(1).unwrap();
+            }
+
+            if prev_best_score >= config.target_problem_fitness {
+                // Check if perfect score to close the algorithm execution
+                break;
+            }
+        }
+    }
+
+    if let Some(mut pbar) = pbar {
+        pbar.close().unwrap();
+    }
+    Ok(SweepOutcome {
+        best_mutation_rate,
+        best_crossover_rate,
+        best_result,
+        aborted,
+        cells: checkpoint.cells,
+        seed,
+        threads: max_threads,
+    })
+}
@@ -0,0 +1,248 @@
+//! Composable termination criteria.
+//!
+//! Every run stops once it is solved or reaches `max_generations`. On top of that, criteria
+//! implementing [`Termination`] can be added to a [`crate::config::GaConfigBuilder`] and combined
+//! with [`AnyOf`] and [`AllOf`].
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use one_max_genetic_algorithm_rust::config::GaConfig;
+//! use one_max_genetic_algorithm_rust::termination::{
+//!     MaxEvaluations, Stagnation, TerminationExt, TimeLimit,
+//! };
+//!
+//! let outcome = GaConfig::builder()
+//!     .termination(
+//!         Stagnation::new(20)
+//!             .or(MaxEvaluations(5_000))
+//!             .or(TimeLimit::new(Duration::from_secs(10))),
+//!     )
+//!     .build()
+//!     .unwrap()
+//!     .run();
+//! println!("Stopped because of: {}", outcome.stop_reason);
+//! ```
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::one_max_genetic_algorithm::{GaState, GenerationStats};
+
+/// Why a run stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StopReason {
+    /// The average fitness reached `target_generation_fitness` with an optimal genome.
+    Solved,
+    /// The run used all of its `max_generations`.
+    MaxGenerations,
+    Generations(u32),
+    Evaluations(u64),
+    TimeLimit(Duration),
+    /// The best fitness did not improve for the given number of generations.
+    Stagnation(u32),
+    /// Every genome of the population is identical.
+    Converged,
+    TargetFitness(f64),
+    /// Several criteria that had to hold at the same time.
+    All(Vec<StopReason>),
+    Custom(String),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::Solved => write!(f, "solved"),
+            StopReason::MaxGenerations => write!(f, "maximum generations reached"),
+            StopReason::Generations(generations) => {
+                write!(f, "generation limit of {generations} reached")
+            }
+            StopReason::Evaluations(evaluations) => {
+                write!(f, "evaluation budget of {evaluations} used")
+            }
+            StopReason::TimeLimit(limit) => write!(f, "time limit of {limit:?} reached"),
+            StopReason::Stagnation(generations) => {
+                write!(f, "no improvement for {generations} generations")
+            }
+            StopReason::Converged => write!(f, "population converged"),
+            StopReason::TargetFitness(target) => write!(f, "target fitness {target} reached"),
+            StopReason::All(reasons) => {
+                let reasons: Vec<String> = reasons.iter().map(|r| r.to_string()).collect();
+                write!(f, "{}", reasons.join(" and "))
+            }
+            StopReason::Custom(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// Decides after every generation whether a run should stop.
+pub trait Termination: Send {
+    /// Returns the reason to stop, or `None` to keep going.
+    fn check(&mut self, state: &GaState, stats: &GenerationStats) -> Option<StopReason>;
+
+    /// Clears any state kept from a previous run. Called before every run.
+    fn reset(&mut self) {}
+}
+
+/// Closures stop the run with a [`StopReason::Custom`] reason when they return true.
+impl<F> Termination for F
+where
+    F: FnMut(&GaState, &GenerationStats) -> bool + Send,
+{
+    fn check(&mut self, state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        self(state, stats).then(|| StopReason::Custom("custom termination".to_string()))
+    }
+}
+
+/// Stops after the given number of generations.
+pub struct MaxGenerations(pub u32);
+
+impl Termination for MaxGenerations {
+    fn check(&mut self, _state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        (stats.generation + 1 >= self.0).then_some(StopReason::Generations(self.0))
+    }
+}
+
+/// Stops once the given number of fitness evaluations was used.
+pub struct MaxEvaluations(pub u64);
+
+impl Termination for MaxEvaluations {
+    fn check(&mut self, state: &GaState, _stats: &GenerationStats) -> Option<StopReason> {
+        (state.evaluations >= self.0).then_some(StopReason::Evaluations(self.0))
+    }
+}
+
+/// Stops once the run took longer than the given wall-clock time.
+pub struct TimeLimit {
+    limit: Duration,
+    started: Option<Instant>,
+}
+
+impl TimeLimit {
+    pub fn new(limit: Duration) -> Self {
+        TimeLimit {
+            limit,
+            started: None,
+        }
+    }
+}
+
+impl Termination for TimeLimit {
+    fn check(&mut self, _state: &GaState, _stats: &GenerationStats) -> Option<StopReason> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        (started.elapsed() >= self.limit).then_some(StopReason::TimeLimit(self.limit))
+    }
+
+    fn reset(&mut self) {
+        self.started = Some(Instant::now());
+    }
+}
+
+/// Stops when the best fitness did not improve for the given number of generations.
+pub struct Stagnation {
+    generations: u32,
+    best_fitness: f64,
+    stagnant_generations: u32,
+}
+
+impl Stagnation {
+    pub fn new(generations: u32) -> Self {
+        Stagnation {
+            generations,
+            best_fitness: f64::NEG_INFINITY,
+            stagnant_generations: 0,
+        }
+    }
+}
+
+impl Termination for Stagnation {
+    fn check(&mut self, _state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        if stats.best_fitness > self.best_fitness {
+            self.best_fitness = stats.best_fitness;
+            self.stagnant_generations = 0;
+        } else {
+            self.stagnant_generations += 1;
+        }
+        (self.stagnant_generations >= self.generations)
+            .then_some(StopReason::Stagnation(self.generations))
+    }
+
+    fn reset(&mut self) {
+        self.best_fitness = f64::NEG_INFINITY;
+        self.stagnant_generations = 0;
+    }
+}
+
+/// Stops when the population lost all diversity, that is every genome is identical.
+pub struct Converged;
+
+impl Termination for Converged {
+    fn check(&mut self, state: &GaState, _stats: &GenerationStats) -> Option<StopReason> {
+        let converged = state.population.windows(2).all(|pair| pair[0] == pair[1]);
+        converged.then_some(StopReason::Converged)
+    }
+}
+
+/// Stops once the best genome reaches the given fitness.
+pub struct TargetFitness(pub f64);
+
+impl Termination for TargetFitness {
+    fn check(&mut self, _state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        (stats.best_fitness >= self.0).then_some(StopReason::TargetFitness(self.0))
+    }
+}
+
+/// Stops as soon as any of the criteria does.
+pub struct AnyOf(pub Vec<Box<dyn Termination>>);
+
+impl Termination for AnyOf {
+    fn check(&mut self, state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        // Every criterion is checked so stateful ones see every generation
+        let mut reason = None;
+        for criterion in self.0.iter_mut() {
+            let criterion_reason = criterion.check(state, stats);
+            reason = reason.or(criterion_reason);
+        }
+        reason
+    }
+
+    fn reset(&mut self) {
+        self.0.iter_mut().for_each(|criterion| criterion.reset());
+    }
+}
+
+/// Stops once all of the criteria do in the same generation.
+pub struct AllOf(pub Vec<Box<dyn Termination>>);
+
+impl Termination for AllOf {
+    fn check(&mut self, state: &GaState, stats: &GenerationStats) -> Option<StopReason> {
+        let reasons: Vec<Option<StopReason>> = self
+            .0
+            .iter_mut()
+            .map(|criterion| criterion.check(state, stats))
+            .collect();
+        if reasons.is_empty() || reasons.iter().any(Option::is_none) {
+            return None;
+        }
+        Some(StopReason::All(reasons.into_iter().flatten().collect()))
+    }
+
+    fn reset(&mut self) {
+        self.0.iter_mut().for_each(|criterion| criterion.reset());
+    }
+}
+
+/// Combinators available on every criterion.
+pub trait TerminationExt: Termination + Sized + 'static {
+    /// Stops when either criterion does.
+    fn or<T: Termination + 'static>(self, other: T) -> AnyOf {
+        AnyOf(vec![Box::new(self), Box::new(other)])
+    }
+
+    /// Stops when both criteria do.
+    fn and<T: Termination + 'static>(self, other: T) -> AllOf {
+        AllOf(vec![Box::new(self), Box::new(other)])
+    }
+}
+
+impl<T: Termination + Sized + 'static> TerminationExt for T {}
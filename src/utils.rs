@@ -1,3 +1,6 @@
+//! Helpers for parameter grids and thread batches.
+
+/// `length` evenly spaced values from `min_val` to `max_val`, in descending order when `invert` is set.
 pub fn generate_equally_spaced_values(
     mut min_val: f64,
     mut max_val: f64,
@@ -21,6 +24,7 @@ pub fn generate_equally_spaced_values(
     values
 }
 
+/// Splits `num_iterations` runs into batches of at most `num_processes` concurrent threads.
 pub fn distribute_run_times(mut num_processes: usize, mut num_iterations: usize) -> Vec<usize> {
     if num_processes < 1 {
         num_processes = 1;
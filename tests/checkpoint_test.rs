@@ -0,0 +1,204 @@
+use one_max_genetic_algorithm_rust::checkpoint::*;
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::constraints::{AdaptivePenalty, DynamicPenalty, StaticPenalty};
+use one_max_genetic_algorithm_rust::evolution::Evolution;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::{GaParameters, GaState};
+use one_max_genetic_algorithm_rust::results::Results;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::ExperimentConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    fn config() -> ExperimentConfig {
+        ExperimentConfig {
+            run_times: 8,
+            generations: 100,
+            population_size: 50,
+            genome_length: 35,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.998,
+            target_problem_fitness: 0.999,
+            mutation_rate_values: vec![0.01, 0.001],
+            crossover_rate_values: vec![0.1, 0.6],
+            solver: Solver::Ga,
+            seed: None,
+        }
+    }
+
+    fn cell(mutation_index: usize, crossover_index: usize) -> SweepCell {
+        let mut results = Results::new(100, 1.0);
+        results.add_result(10, 0.9, 1.0);
+        SweepCell {
+            mutation_index,
+            crossover_index,
+            mutation_rate: 0.01,
+            crossover_rate: 0.1,
+            results,
+        }
+    }
+
+    #[test]
+    fn test_find_cell() {
+        let mut checkpoint = SweepCheckpoint::new(config());
+        checkpoint.add_cell(cell(0, 1));
+
+        assert!(checkpoint.find(0, 1).is_some());
+        assert!(checkpoint.find(1, 0).is_none());
+    }
+
+    #[test]
+    fn test_is_compatible() {
+        let checkpoint = SweepCheckpoint::new(config());
+        let mut other_config = config();
+        other_config.population_size = 60;
+
+        assert!(checkpoint.is_compatible(&config()));
+        assert!(!checkpoint.is_compatible(&other_config));
+    }
+
+    #[test]
+    fn test_is_compatible_seed() {
+        let seeded = |seed| ExperimentConfig {
+            seed: Some(seed),
+            ..config()
+        };
+        let checkpoint = SweepCheckpoint::new(seeded(3));
+        // A sweep without a seed resumes with the one of the checkpoint
+        assert!(checkpoint.is_compatible(&config()));
+        assert!(checkpoint.is_compatible(&seeded(3)));
+        assert!(!checkpoint.is_compatible(&seeded(4)));
+        assert!(!SweepCheckpoint::new(config()).is_compatible(&seeded(3)));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = env::temp_dir().join("one_max_checkpoint_test_save_and_load.json");
+        let mut checkpoint = SweepCheckpoint::new(config());
+        checkpoint.add_cell(cell(1, 0));
+        checkpoint.save(&path).unwrap();
+
+        let loaded = SweepCheckpoint::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.is_compatible(&config()));
+        assert_eq!(loaded.cells.len(), 1);
+        let results = loaded.find(1, 0).unwrap();
+        assert_eq!(results.generations, vec![10]);
+        assert_eq!(
+            results.get_score(),
+            checkpoint.find(1, 0).unwrap().get_score()
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = env::temp_dir().join("one_max_checkpoint_test_missing.json");
+        assert!(SweepCheckpoint::load(&path).is_err());
+    }
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 20,
+            genome_length: 40,
+            max_generations: 30,
+            mutation_rate: 0.01,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 1.0, // Never reached so every generation runs
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_run() {
+        let mut evolution1 = Evolution::new(parameters(), 42).unwrap();
+        let mut evolution2 = Evolution::new(parameters(), 42).unwrap();
+
+        let outcome1 = evolution1.run(false, |_, _| true);
+        let outcome2 = evolution2.run(false, |_, _| true);
+
+        assert_eq!(outcome1, outcome2);
+        assert_eq!(evolution1.population(), evolution2.population());
+    }
+
+    #[test]
+    fn test_restored_run_is_bit_for_bit() {
+        let path = env::temp_dir().join("one_max_checkpoint_test_restored_run.json");
+        let mut state = GaState::new(parameters(), 7);
+        while state.generation < 10 {
+            state.step();
+        }
+        save_run_state(&state, &path).unwrap();
+
+        let restored = load_run_state(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(restored.generation, 10);
+        assert_eq!(restored.parameters, parameters());
+
+        let mut evolution = Evolution::from_state(state);
+        let mut restored_evolution = Evolution::from_state(restored);
+        let outcome = evolution.run(false, |_, _| true);
+        let restored_outcome = restored_evolution.run(false, |_, _| true);
+        let state = evolution.into_state();
+        let restored = restored_evolution.into_state();
+
+        assert_eq!(outcome, restored_outcome);
+        assert_eq!(state.population, restored.population);
+        assert_eq!(state.fitness_values, restored.fitness_values);
+        assert_eq!(state.best_population, restored.best_population);
+        assert_eq!(state.best_generation, restored.best_generation);
+    }
+
+    #[test]
+    fn test_genetic_algorithm_with_checkpoints() {
+        let path = env::temp_dir().join("one_max_checkpoint_test_with_checkpoints.json");
+        let mut evolution = Evolution::new(parameters(), 3).unwrap();
+
+        let outcome = genetic_algorithm_with_checkpoints(&mut evolution, false, 4, &path).unwrap();
+        let saved = load_run_state(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let state = evolution.state();
+
+        assert_eq!(
+            outcome,
+            (30, state.best_generation_fitness, state.best_fitness)
+        );
+        assert!(saved.is_finished());
+        assert_eq!(saved.population, state.population);
+    }
+
+    #[test]
+    fn test_checkpoints_refuse_stateful_penalties() {
+        let path = env::temp_dir().join("one_max_checkpoint_test_stateful_penalty.json");
+        let builder = || {
+            GaConfig::builder()
+                .population_size(10)
+                .genome_length(8)
+                .max_generations(5)
+                .seed(3)
+        };
+        for config in [
+            builder().penalty(DynamicPenalty::new(0.5, 2.0, 1.0)),
+            builder().penalty(AdaptivePenalty::new(1.0, 2)),
+        ] {
+            let mut evolution = config.build().unwrap().evolution();
+            let error = genetic_algorithm_with_checkpoints(&mut evolution, false, 2, &path)
+                .err()
+                .unwrap();
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+            assert_eq!(evolution.generation(), 0);
+            assert!(!path.exists());
+        }
+
+        let mut evolution = builder()
+            .penalty(StaticPenalty { coefficient: 1.0 })
+            .build()
+            .unwrap()
+            .evolution();
+        assert!(genetic_algorithm_with_checkpoints(&mut evolution, false, 2, &path).is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+}
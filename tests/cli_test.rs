@@ -0,0 +1,179 @@
+#[path = "../src/cli.rs"]
+mod cli;
+use cli::*;
+use one_max_genetic_algorithm_rust::logging::{LogFormat, LogSink};
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::Jobs;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        let options = parse_args(args(&[])).unwrap().unwrap();
+        assert_eq!(options, CliOptions::default());
+        assert!(!options.resume);
+    }
+
+    #[test]
+    fn test_parse_args_resume_and_checkpoint() {
+        let options = parse_args(args(&["--resume", "--checkpoint", "sweep.json"]))
+            .unwrap()
+            .unwrap();
+        assert!(options.resume);
+        assert_eq!(options.checkpoint_path, PathBuf::from("sweep.json"));
+    }
+
+    #[test]
+    fn test_parse_args_plots() {
+        let options = parse_args(args(&["--plots", "charts"])).unwrap().unwrap();
+        assert_eq!(options.plots, Some(PathBuf::from("charts")));
+        assert!(parse_args(args(&["--plots"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_report() {
+        assert_eq!(
+            CliOptions::default().report_path,
+            PathBuf::from("sweep_report.html")
+        );
+        let options = parse_args(args(&["--report", "out.html"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.report_path, PathBuf::from("out.html"));
+        assert!(parse_args(args(&["--report"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_dashboard() {
+        assert!(
+            parse_args(args(&["--dashboard"]))
+                .unwrap()
+                .unwrap()
+                .dashboard
+        );
+        assert!(!CliOptions::default().dashboard);
+    }
+
+    #[test]
+    fn test_parse_args_missing_checkpoint_path() {
+        assert!(parse_args(args(&["--checkpoint"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_unknown_argument() {
+        assert!(parse_args(args(&["--gibberish"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_solver() {
+        let options = parse_args(args(&["--solver", "PBIL"])).unwrap().unwrap();
+        assert_eq!(options.solver, Solver::Pbil);
+        let options = parse_args(args(&["--solver", "1+1-ea"])).unwrap().unwrap();
+        assert_eq!(options.solver, Solver::OnePlusOneEa);
+        assert!(parse_args(args(&["--solver", "simplex"])).is_err());
+        assert!(parse_args(args(&["--solver"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_scaling() {
+        let options = parse_args(args(&["--scaling", "10, 20,40"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.scaling, Some(vec![10, 20, 40]));
+        assert!(parse_args(args(&["--scaling", "10,0"])).is_err());
+        assert!(parse_args(args(&["--scaling", "ten"])).is_err());
+        assert!(parse_args(args(&["--scaling"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_logging() {
+        let options = parse_args(args(&["--log-level", "debug", "--log-json", "run.jsonl"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.log_level, "debug");
+        let config = options.log_config(None);
+        assert_eq!(config.filter, "debug");
+        assert_eq!(
+            config.sinks,
+            vec![
+                LogSink::Stderr,
+                LogSink::File {
+                    path: PathBuf::from("run.jsonl"),
+                    format: LogFormat::Json
+                }
+            ]
+        );
+        assert_eq!(
+            options.log_config(Some("trace".to_string())).filter,
+            "trace"
+        );
+        assert!(parse_args(args(&["--log-level"])).is_err());
+        assert!(parse_args(args(&["--log-file"])).is_err());
+        assert!(parse_args(args(&["--log-json"])).is_err());
+    }
+
+    #[test]
+    fn test_log_config_default_and_dashboard() {
+        let config = CliOptions::default().log_config(None);
+        assert_eq!(config.filter, "warn");
+        assert_eq!(config.sinks, vec![LogSink::Stderr]);
+
+        let options = parse_args(args(&["--dashboard", "--log-file", "run.log"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            options.log_config(None).sinks,
+            vec![LogSink::File {
+                path: PathBuf::from("run.log"),
+                format: LogFormat::Text
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_format_and_seed() {
+        assert_eq!(CliOptions::default().format, OutputFormat::Text);
+        let options = parse_args(args(&["--format", "JSON", "--seed", "42"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.format, OutputFormat::Json);
+        assert_eq!(options.seed, Some(42));
+        assert!(parse_args(args(&["--format", "yaml"])).is_err());
+        assert!(parse_args(args(&["--format"])).is_err());
+        assert!(parse_args(args(&["--seed", "-1"])).is_err());
+        assert!(parse_args(args(&["--seed"])).is_err());
+        // Both write to stdout
+        assert!(parse_args(args(&["--format", "json", "--dashboard"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_jobs_and_pinning() {
+        let defaults = CliOptions::default();
+        assert_eq!(defaults.jobs, Jobs::AllMinus(2));
+        assert!(!defaults.pin_cores);
+
+        let options = parse_args(args(&["--jobs", "3", "--pin-cores"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.jobs, Jobs::Count(3));
+        assert!(options.pin_cores);
+        let options = parse_args(args(&["--jobs", "all-1"])).unwrap().unwrap();
+        assert_eq!(options.jobs, Jobs::AllMinus(1));
+        assert!(parse_args(args(&["--jobs", "0"])).is_err());
+        assert!(parse_args(args(&["--jobs"])).is_err());
+        assert!(usage().contains("all-2"));
+    }
+
+    #[test]
+    fn test_parse_args_help() {
+        assert_eq!(parse_args(args(&["--help"])), Ok(None));
+        assert!(usage().contains("--resume"));
+    }
+}
@@ -0,0 +1,165 @@
+use one_max_genetic_algorithm_rust::config::*;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::{GaState, GenerationStats};
+use one_max_genetic_algorithm_rust::operators::Mutation;
+use rand::RngCore;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct AllOnesMutation;
+
+    impl Mutation for AllOnesMutation {
+        fn mutate(&self, genome: &[u8], _rng: &mut dyn RngCore) -> Vec<u8> {
+            vec![1; genome.len()]
+        }
+    }
+
+    struct CountingObserver {
+        generations: Arc<Mutex<Vec<u32>>>,
+    }
+
+    impl Observer for CountingObserver {
+        fn on_generation(&mut self, _state: &GaState, stats: &GenerationStats) {
+            self.generations.lock().unwrap().push(stats.generation);
+        }
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = GaConfig::builder().build().unwrap();
+        let parameters = config.parameters();
+        assert_eq!(parameters.population_size, DEFAULT_POPULATION_SIZE);
+        assert_eq!(parameters.genome_length, DEFAULT_GENOME_LENGTH);
+        assert_eq!(parameters.max_generations, DEFAULT_MAX_GENERATIONS);
+        assert_eq!(parameters.mutation_rate, DEFAULT_MUTATION_RATE);
+        assert_eq!(parameters.crossover_rate, DEFAULT_CROSSOVER_RATE);
+        assert_eq!(parameters.select_parent_mode, DEFAULT_SELECT_PARENT_MODE);
+    }
+
+    #[test]
+    fn test_builder_zero_population() {
+        let error = GaConfig::builder().population_size(0).build().err();
+        assert_eq!(error, Some(ConfigError::ZeroPopulation));
+    }
+
+    #[test]
+    fn test_builder_genome_too_short() {
+        let error = GaConfig::builder().genome_length(1).build().err();
+        assert_eq!(error, Some(ConfigError::GenomeTooShort(1)));
+    }
+
+    #[test]
+    fn test_builder_zero_generations() {
+        let error = GaConfig::builder().max_generations(0).build().err();
+        assert_eq!(error, Some(ConfigError::ZeroGenerations));
+    }
+
+    #[test]
+    fn test_builder_rates_out_of_range() {
+        let error = GaConfig::builder().mutation_rate(1.5).build().err();
+        assert_eq!(
+            error,
+            Some(ConfigError::RateOutOfRange {
+                name: "mutation rate",
+                value: 1.5
+            })
+        );
+        let error = GaConfig::builder().crossover_rate(-0.1).build().err();
+        assert!(matches!(
+            error,
+            Some(ConfigError::RateOutOfRange {
+                name: "crossover rate",
+                ..
+            })
+        ));
+        assert!(GaConfig::builder().mutation_rate(f64::NAN).build().is_err());
+    }
+
+    #[test]
+    fn test_builder_target_fitness_out_of_range() {
+        let error = GaConfig::builder()
+            .target_generation_fitness(1.1)
+            .build()
+            .err();
+        assert_eq!(error, Some(ConfigError::TargetFitnessOutOfRange(1.1)));
+    }
+
+    #[test]
+    fn test_builder_unknown_select_parent_mode() {
+        let error = GaConfig::builder()
+            .select_parent_mode("gibberish")
+            .build()
+            .err();
+        assert_eq!(
+            error,
+            Some(ConfigError::UnknownSelectParentMode(
+                "gibberish".to_string()
+            ))
+        );
+        assert!(GaConfig::builder()
+            .select_parent_mode("Roulette")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_zero_evaluation_threads() {
+        let error = GaConfig::builder().evaluation_threads(0).build().err();
+        assert_eq!(error, Some(ConfigError::ZeroEvaluationThreads));
+    }
+
+    #[test]
+    fn test_run_with_seed_is_reproducible() {
+        let build = || {
+            GaConfig::builder()
+                .population_size(20)
+                .genome_length(30)
+                .max_generations(50)
+                .seed(11)
+                .build()
+                .unwrap()
+        };
+        let outcome1 = build().run();
+        let outcome2 = build().run();
+
+        assert_eq!(outcome1, outcome2);
+        assert_eq!(outcome1.seed, 11);
+        assert!(outcome1.generation <= 50);
+        assert_eq!(outcome1.best_genome.len(), 30);
+    }
+
+    #[test]
+    fn test_run_with_custom_mutation() {
+        let outcome = GaConfig::builder()
+            .mutation(AllOnesMutation)
+            .target_generation_fitness(1.0)
+            .build()
+            .unwrap()
+            .run();
+
+        assert!(outcome.solved);
+        assert_eq!(outcome.generation, 0);
+        assert_eq!(outcome.best_genome, vec![1; DEFAULT_GENOME_LENGTH]);
+    }
+
+    #[test]
+    fn test_run_with_observer_and_termination() {
+        let generations = Arc::new(Mutex::new(Vec::new()));
+        let outcome = GaConfig::builder()
+            .target_generation_fitness(1.0)
+            .mutation_rate(0.5) // Keeps the run from being solved early
+            .observer(CountingObserver {
+                generations: Arc::clone(&generations),
+            })
+            .terminate_when(|_, stats| stats.generation >= 4)
+            .build()
+            .unwrap()
+            .run();
+
+        assert!(!outcome.solved);
+        assert_eq!(outcome.generation, 5);
+        assert_eq!(*generations.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,186 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::constraints::*;
+use one_max_genetic_algorithm_rust::fitness::{Evaluator, Fitness, OneMax};
+use one_max_genetic_algorithm_rust::operators::Selection;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::Arc;
+
+    /// OneMax with at most `capacity` ones allowed.
+    struct CappedOneMax {
+        capacity: usize,
+    }
+
+    impl Fitness for CappedOneMax {
+        fn evaluate(&self, genome: &[u8]) -> f64 {
+            OneMax.evaluate(genome)
+        }
+
+        fn violation(&self, genome: &[u8]) -> f64 {
+            let ones = genome.iter().filter(|&&gene| gene == 1).count();
+            ones.saturating_sub(self.capacity) as f64
+        }
+    }
+
+    fn ones(genome: &[u8]) -> usize {
+        genome.iter().filter(|&&gene| gene == 1).count()
+    }
+
+    #[test]
+    fn test_static_penalty_is_proportional() {
+        let penalty = StaticPenalty { coefficient: 0.5 };
+        assert_eq!(penalty.penalty(0.0), 0.0);
+        assert_eq!(penalty.penalty(3.0), 1.5);
+    }
+
+    #[test]
+    fn test_dynamic_penalty_grows_with_generations() {
+        let mut penalty = DynamicPenalty::new(0.5, 2.0, 1.0);
+        assert_approx_eq!(penalty.penalty(2.0), 0.5);
+        penalty.update(true);
+        penalty.update(false);
+        assert_approx_eq!(penalty.penalty(2.0), 4.5);
+    }
+
+    #[test]
+    fn test_adaptive_penalty_follows_feasibility() {
+        let mut penalty = AdaptivePenalty::new(1.0, 2);
+        penalty.update(false);
+        // The window is not full yet
+        assert_eq!(penalty.coefficient, 1.0);
+        penalty.update(false);
+        assert_eq!(penalty.coefficient, 2.0);
+        penalty.update(true);
+        // Mixed window keeps the coefficient
+        assert_eq!(penalty.coefficient, 2.0);
+        penalty.update(true);
+        assert_approx_eq!(penalty.coefficient, 2.0 / 1.5);
+    }
+
+    #[test]
+    fn test_evaluator_subtracts_penalty() {
+        let evaluator = Evaluator::new(Arc::new(CappedOneMax { capacity: 2 }))
+            .with_penalty(Box::new(StaticPenalty { coefficient: 0.25 }));
+        assert_eq!(evaluator.evaluate(&[1, 1, 0, 0]), 0.5);
+        assert_eq!(evaluator.evaluate(&[1, 1, 1, 1]), 1.0 - 0.5);
+    }
+
+    #[test]
+    fn test_evaluator_updates_penalty_with_best_individual() {
+        let mut evaluator = Evaluator::new(Arc::new(CappedOneMax { capacity: 1 }))
+            .with_penalty(Box::new(DynamicPenalty::new(1.0, 1.0, 1.0)));
+        let population = vec![vec![1, 1], vec![0, 0]];
+        let mut evaluations = 0;
+        let before = evaluator.evaluate_population(&population, &mut evaluations);
+        evaluator.update_penalty(&population, &before);
+        let after = evaluator.evaluate_population(&population, &mut evaluations);
+        assert_eq!(before, vec![0.0, 0.0]);
+        assert_eq!(after, vec![-1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_feasibility_tournament_prefers_feasible() {
+        let fitness = Arc::new(CappedOneMax { capacity: 1 });
+        let tournament = FeasibilityTournament::new(2, fitness);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        // The infeasible genome has the higher fitness but loses
+        let population = vec![vec![1, 1, 1], vec![1, 0, 0]];
+        for _ in 0..10 {
+            assert_eq!(
+                tournament.select_index(&population, &[1.0, 0.3], &mut rng),
+                Some(1)
+            );
+        }
+    }
+
+    #[test]
+    fn test_feasibility_tournament_compares_within_class() {
+        let fitness = Arc::new(CappedOneMax { capacity: 1 });
+        let tournament = FeasibilityTournament::new(2, fitness);
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let feasible = vec![vec![0, 0, 0], vec![1, 0, 0]];
+        assert_eq!(
+            tournament.select(&feasible, &[0.0, 0.3], &mut rng),
+            vec![1, 0, 0]
+        );
+        let infeasible = vec![vec![1, 1, 1], vec![1, 1, 0]];
+        assert_eq!(
+            tournament.select(&infeasible, &[1.0, 0.6], &mut rng),
+            vec![1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_penalised_run_ends_feasible() {
+        let outcome = GaConfig::builder()
+            .population_size(20)
+            .genome_length(20)
+            .max_generations(80)
+            .fitness(CappedOneMax { capacity: 10 })
+            .penalty(StaticPenalty { coefficient: 1.0 })
+            .seed(5)
+            .build()
+            .unwrap()
+            .run();
+        assert!(ones(&outcome.best_genome) <= 10);
+    }
+
+    #[test]
+    fn test_infeasible_genome_at_the_target_does_not_solve() {
+        // All ones scores 1.0 but breaks the capacity, and nothing penalises or repairs it
+        let outcome = GaConfig::builder()
+            .population_size(20)
+            .genome_length(8)
+            .max_generations(100)
+            .target_generation_fitness(0.5)
+            .fitness(CappedOneMax { capacity: 7 })
+            .seed(5)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(outcome.best_fitness, 1.0);
+        assert!(!outcome.solved);
+        assert_eq!(outcome.generation, 100);
+    }
+
+    #[test]
+    fn test_repair_keeps_every_child_feasible() {
+        let capacity = 6;
+        let drop_extra_ones = move |genome: &mut [u8], rng: &mut dyn RngCore| {
+            let mut changed = false;
+            while ones(genome) > capacity {
+                let index = rng.next_u32() as usize % genome.len();
+                if genome[index] == 1 {
+                    genome[index] = 0;
+                    changed = true;
+                }
+            }
+            changed
+        };
+        let mut config = GaConfig::builder()
+            .population_size(16)
+            .genome_length(12)
+            .max_generations(30)
+            .mutation_rate(0.2)
+            .fitness(CappedOneMax { capacity })
+            .repair(drop_extra_ones)
+            .seed(9)
+            .build()
+            .unwrap();
+        let mut evolution = config.evolution();
+        for _ in 0..10 {
+            evolution.next();
+            assert!(evolution
+                .state()
+                .population
+                .iter()
+                .all(|genome| ones(genome) <= capacity));
+        }
+        assert_eq!(ones(&config.run().best_genome), capacity);
+    }
+}
@@ -0,0 +1,155 @@
+use one_max_genetic_algorithm_rust::dashboard::*;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GenerationStats;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::{ExperimentConfig, SweepObserver};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use crossterm::style::Color;
+
+    fn config() -> ExperimentConfig {
+        ExperimentConfig {
+            run_times: 2,
+            generations: 50,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1,
+            mutation_rate_values: vec![0.01, 0.001],
+            crossover_rate_values: vec![0.3, 0.6],
+            solver: Solver::Ga,
+            seed: None,
+        }
+    }
+
+    fn stats(generation: u32, best_fitness: f64, generation_fitness: f64) -> GenerationStats {
+        GenerationStats {
+            generation,
+            generation_fitness,
+            best_fitness,
+        }
+    }
+
+    fn text(lines: &[Vec<Span>]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|span| span.text.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[0.0, 0.5, 1.0], 10, (0.0, 1.0)), "▁▅█");
+        // Longer series are averaged into buckets
+        assert_eq!(sparkline(&[0.0, 0.0, 1.0, 1.0], 2, (0.0, 1.0)), "▁█");
+        assert_eq!(sparkline(&[0.3, 0.3], 5, (0.3, 0.3)), "▅▅");
+        assert_eq!(sparkline(&[], 5, (0.0, 1.0)), "");
+    }
+
+    #[test]
+    fn test_score_color_goes_from_red_to_green() {
+        assert_eq!(
+            score_color(0.0),
+            Color::Rgb {
+                r: 200,
+                g: 60,
+                b: 60
+            }
+        );
+        assert_eq!(
+            score_color(0.5),
+            Color::Rgb {
+                r: 200,
+                g: 200,
+                b: 60
+            }
+        );
+        assert_eq!(
+            score_color(1.0),
+            Color::Rgb {
+                r: 60,
+                g: 200,
+                b: 60
+            }
+        );
+        assert_eq!(score_color(2.0), score_color(1.0));
+    }
+
+    #[test]
+    fn test_dashboard_tracks_workers_and_convergence() {
+        let dashboard = Dashboard::new(&config());
+        dashboard.cell_started(1, 0, 0.001, 0.3);
+        dashboard.run_started(0);
+        dashboard.run_started(1);
+        dashboard.generation(0, &stats(1, 0.5, 0.25));
+        dashboard.generation(1, &stats(1, 0.7, 0.35));
+        dashboard.generation(1, &stats(2, 0.9, 0.45));
+        dashboard.run_finished(1);
+
+        let state = dashboard.snapshot();
+        assert_eq!(state.current_cell, Some((1, 0)));
+        assert!(matches!(
+            state.workers[0],
+            WorkerStatus::Running { generation: 1, .. }
+        ));
+        assert_eq!(
+            state.workers[1],
+            WorkerStatus::Finished {
+                generation: 2,
+                best_fitness: 0.9
+            }
+        );
+        let (best, mean) = state.convergence();
+        assert_eq!(best.len(), 2);
+        assert_approx_eq!(best[0], 0.6);
+        assert_approx_eq!(mean[0], 0.3);
+        assert_approx_eq!(best[1], 0.9);
+
+        // A new cell starts a new convergence curve
+        dashboard.cell_started(1, 1, 0.001, 0.6);
+        assert!(dashboard.snapshot().convergence().0.is_empty());
+    }
+
+    #[test]
+    fn test_dashboard_fills_the_heatmap_and_keeps_the_best() {
+        let dashboard = Dashboard::new(&config());
+        dashboard.cell_finished(0, 0, 0.8);
+        dashboard.cell_finished(0, 1, 0.6);
+        dashboard.cell_finished(1, 0, 0.8);
+
+        let state = dashboard.snapshot();
+        assert_eq!(state.finished_cells(), 3);
+        assert_eq!(state.scores[1][1], None);
+        let best = state.best.unwrap();
+        assert_eq!((best.mutation_rate, best.crossover_rate), (0.001, 0.3));
+
+        let lines = state.lines(100);
+        let heatmap_cells: Vec<&Span> = lines
+            .iter()
+            .flatten()
+            .filter(|span| span.background.is_some())
+            .collect();
+        assert_eq!(heatmap_cells.len(), 3);
+        assert_eq!(heatmap_cells[1].text.trim(), "0.600");
+        let text = text(&lines);
+        assert!(text.contains("cells 3/4"));
+        assert!(text.contains("crossover rate 0.3000, score 0.8000"));
+    }
+
+    #[test]
+    fn test_abort_is_reported_to_the_sweep() {
+        let dashboard = Dashboard::new(&config());
+        assert!(!dashboard.should_abort());
+        dashboard.abort();
+        assert!(dashboard.should_abort());
+        assert!(text(&dashboard.snapshot().lines(80)).contains("Aborting"));
+    }
+}
@@ -0,0 +1,85 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::diversity::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    #[test]
+    fn test_converged_population_has_no_diversity() {
+        let population = vec![vec![1, 0, 1]; 4];
+        let diversity = population_diversity(&population);
+        assert_eq!(diversity.mean_hamming_distance, 0.0);
+        assert_eq!(diversity.mean_locus_entropy, 0.0);
+        assert_eq!(diversity.unique_genomes, 1);
+        assert_approx_eq!(diversity.allele_frequency_spread, (2.0f64 / 9.0).sqrt());
+    }
+
+    #[test]
+    fn test_complementary_genomes() {
+        let population = vec![vec![0, 0, 0, 0], vec![1, 1, 1, 1]];
+        let diversity = population_diversity(&population);
+        assert_eq!(diversity.mean_hamming_distance, 4.0);
+        assert_eq!(diversity.mean_locus_entropy, 1.0);
+        assert_eq!(diversity.unique_genomes, 2);
+        assert_eq!(diversity.allele_frequency_spread, 0.0);
+    }
+
+    #[test]
+    fn test_mean_hamming_distance_matches_pairwise_count() {
+        let population = vec![vec![0, 1, 1], vec![1, 1, 0], vec![0, 0, 0], vec![0, 1, 1]];
+        let mut total = 0;
+        let mut pairs = 0;
+        for i in 0..population.len() {
+            for j in i + 1..population.len() {
+                total += population[i]
+                    .iter()
+                    .zip(&population[j])
+                    .filter(|(a, b)| a != b)
+                    .count();
+                pairs += 1;
+            }
+        }
+        let diversity = population_diversity(&population);
+        assert_approx_eq!(diversity.mean_hamming_distance, total as f64 / pairs as f64);
+        assert_eq!(diversity.unique_genomes, 3);
+    }
+
+    #[test]
+    fn test_allele_frequencies_and_locus_entropy() {
+        let population = vec![vec![1, 0], vec![1, 1], vec![1, 0], vec![1, 1]];
+        assert_eq!(allele_frequencies(&population), vec![1.0, 0.5]);
+        assert_eq!(locus_entropy(&population), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_empty_and_single_genome_populations() {
+        assert_eq!(population_diversity(&[]), Diversity::default());
+        let diversity = population_diversity(&[vec![1, 0]]);
+        assert_eq!(diversity.mean_hamming_distance, 0.0);
+        assert_eq!(diversity.unique_genomes, 1);
+    }
+
+    #[test]
+    fn test_run_outcome_reports_diversity_per_generation() {
+        let outcome = GaConfig::builder()
+            .population_size(10)
+            .genome_length(8)
+            .max_generations(15)
+            .seed(5)
+            .build()
+            .unwrap()
+            .run();
+        let generations_run = if outcome.solved {
+            outcome.generation + 1
+        } else {
+            outcome.generation
+        };
+        assert_eq!(outcome.diversity.len(), generations_run as usize);
+        assert!(outcome
+            .diversity
+            .iter()
+            .all(|diversity| (1..=10).contains(&diversity.unique_genomes)));
+    }
+}
@@ -0,0 +1,197 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::eda::*;
+use one_max_genetic_algorithm_rust::fitness::Evaluator;
+use one_max_genetic_algorithm_rust::operators::{Operators, Replacement};
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::GaParameters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use one_max_genetic_algorithm_rust::checkpoint::{load_run_state, save_run_state};
+    use one_max_genetic_algorithm_rust::evolution::Evolution;
+    use one_max_genetic_algorithm_rust::termination::{MaxEvaluations, StopReason};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::env;
+    use std::fs;
+    use std::sync::Arc;
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 20,
+            genome_length: 16,
+            max_generations: 300,
+            mutation_rate: 0.01,
+            crossover_rate: 0.5,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+        }
+    }
+
+    fn solve(replacement: impl Replacement + 'static) -> (bool, Vec<f64>) {
+        let mut config = GaConfig::builder()
+            .population_size(20)
+            .genome_length(16)
+            .max_generations(300)
+            .target_generation_fitness(0.9)
+            .replacement(replacement)
+            .seed(3)
+            .build()
+            .unwrap();
+        let outcome = config.run();
+        let mut evolution = config.evolution();
+        evolution.by_ref().take(5).for_each(drop);
+        (outcome.solved, evolution.state().model.clone())
+    }
+
+    #[test]
+    fn test_sample_genome_follows_the_model() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        assert_eq!(
+            sample_genome(&[0.0, 1.0, 1.0, 0.0], &mut rng),
+            vec![0, 1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_every_solver_solves_one_max() {
+        for (name, (solved, model)) in [
+            ("umda", solve(Umda::new(0.5))),
+            ("cga", solve(CompactGa::new(20))),
+            ("pbil", solve(Pbil::new(0.2))),
+        ] {
+            assert!(solved, "{name} did not solve One-Max");
+            assert_eq!(model.len(), 16, "{name} kept no model");
+            assert!(model.iter().all(|p| (0.0..=1.0).contains(p)));
+        }
+    }
+
+    #[test]
+    fn test_clamped_models_solve_at_the_sweep_target() {
+        // The parameters of the sweep in main.rs, whose target the clamped mean never reaches
+        let parameters = GaParameters {
+            population_size: 50,
+            genome_length: 35,
+            max_generations: 400,
+            mutation_rate: 0.001,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.998,
+        };
+        for solver in [Solver::Umda, Solver::Cga] {
+            let operators = Arc::new(solver.operators(&parameters));
+            let solved = (0..4)
+                .filter(|&seed| {
+                    let mut evolution =
+                        Evolution::with_operators(parameters.clone(), seed, Arc::clone(&operators))
+                            .unwrap();
+                    let (_, _, best_fitness) = evolution.run(false, |_, _| true);
+                    evolution.stop_reason() == Some(StopReason::Solved) && best_fitness == 1.0
+                })
+                .count();
+            assert_eq!(solved, 4, "{solver} solved {solved} of 4 runs");
+        }
+    }
+
+    #[test]
+    fn test_umda_estimates_from_the_fittest() {
+        let population = vec![
+            vec![1, 1, 0, 0],
+            vec![1, 0, 1, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 1],
+        ];
+        let fitness_values = [0.5, 0.5, 0.0, 0.25];
+        let mut model = Vec::new();
+        let mut evaluations = 0;
+        let (offspring, offspring_fitness) = Umda::new(0.5).replace_with_model(
+            &mut model,
+            6,
+            &population,
+            &fitness_values,
+            &Operators::from_parameters(&parameters()),
+            &mut Evaluator::default(),
+            &mut evaluations,
+            &mut ChaCha8Rng::seed_from_u64(2),
+        );
+        // Frequencies of the two best genomes, kept within [1/4, 3/4]
+        assert_eq!(model, vec![0.75, 0.5, 0.5, 0.25]);
+        assert_eq!(offspring.len(), 6);
+        assert_eq!(offspring_fitness.len(), 6);
+        assert_eq!(evaluations, 6);
+    }
+
+    #[test]
+    fn test_compact_ga_moves_towards_the_winner() {
+        let population = vec![vec![0, 1, 0, 1], vec![1, 0, 1, 0]];
+        let mut model = Vec::new();
+        let mut evaluations = 0;
+        let (offspring, _) = CompactGa::new(10).replace_with_model(
+            &mut model,
+            2,
+            &population,
+            &[0.5, 0.5],
+            &Operators::from_parameters(&parameters()),
+            &mut Evaluator::default(),
+            &mut evaluations,
+            &mut ChaCha8Rng::seed_from_u64(5),
+        );
+        let ones = |genome: &[u8]| genome.iter().filter(|&&gene| gene == 1).count();
+        let (winner, loser) = if ones(&offspring[0]) >= ones(&offspring[1]) {
+            (&offspring[0], &offspring[1])
+        } else {
+            (&offspring[1], &offspring[0])
+        };
+        // The model starts from the frequencies of the population, 0.5 everywhere
+        let expected: Vec<f64> = winner
+            .iter()
+            .zip(loser)
+            .map(
+                |(&winner_gene, &loser_gene)| match (winner_gene, loser_gene) {
+                    (1, 0) => 0.6,
+                    (0, 1) => 0.4,
+                    _ => 0.5,
+                },
+            )
+            .collect();
+        for (p, expected) in model.iter().zip(expected) {
+            assert!((p - expected).abs() < 1e-9);
+        }
+        assert_eq!(evaluations, 2);
+    }
+
+    #[test]
+    fn test_eda_runs_stop_on_termination_criteria() {
+        let outcome = GaConfig::builder()
+            .population_size(10)
+            .genome_length(200)
+            .replacement(Pbil::new(0.05))
+            .termination(MaxEvaluations(100))
+            .seed(1)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(outcome.stop_reason, StopReason::Evaluations(100));
+        assert!(outcome.evaluations >= 100);
+    }
+
+    #[test]
+    fn test_model_is_checkpointed() {
+        let operators = Arc::new(Solver::Pbil.operators(&parameters()));
+        let mut evolution =
+            Evolution::with_operators(parameters(), 9, Arc::clone(&operators)).unwrap();
+        evolution.by_ref().take(3).for_each(drop);
+        let path = env::temp_dir().join("eda_model_checkpoint.json");
+        save_run_state(evolution.state(), &path).unwrap();
+        let restored = load_run_state(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(restored.model, evolution.state().model);
+
+        let mut resumed = Evolution::from_state_with(restored, operators, Evaluator::default());
+        evolution.by_ref().take(3).for_each(drop);
+        resumed.by_ref().take(3).for_each(drop);
+        assert_eq!(resumed.population(), evolution.population());
+        assert_eq!(resumed.state().model, evolution.state().model);
+    }
+}
@@ -0,0 +1,49 @@
+use one_max_genetic_algorithm_rust::config::ConfigError;
+use one_max_genetic_algorithm_rust::error::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+    use std::io;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_from_config_error() {
+        let error: Error = ConfigError::ZeroRuns.into();
+        assert!(matches!(error, Error::Config(ConfigError::ZeroRuns)));
+        assert_eq!(
+            error.to_string(),
+            "invalid configuration: runs per configuration must be at least 1"
+        );
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            ConfigError::ZeroRuns.to_string()
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_errors() {
+        let error = Error::Checkpoint {
+            path: PathBuf::from("sweep.json"),
+            source: io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+        };
+        assert_eq!(
+            error.to_string(),
+            "could not access checkpoint sweep.json: denied"
+        );
+        assert!(error.source().is_some());
+
+        let error = Error::IncompatibleCheckpoint(PathBuf::from("sweep.json"));
+        assert!(error.to_string().contains("different experiment config"));
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_config_error_messages() {
+        assert_eq!(
+            ConfigError::EmptyValues("mutation rates").to_string(),
+            "mutation rates must not be empty"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use one_max_genetic_algorithm_rust::config::{ConfigError, GaConfig};
+use one_max_genetic_algorithm_rust::evolution::Evolution;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GaParameters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 20,
+            genome_length: 30,
+            max_generations: 25,
+            mutation_rate: 0.01,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 1.0, // Never reached so every generation runs
+        }
+    }
+
+    #[test]
+    fn test_iterates_every_generation() {
+        let evolution = Evolution::new(parameters(), 1).unwrap();
+        let generations: Vec<u32> = evolution.map(|stats| stats.generation).collect();
+        assert_eq!(generations, (0..25).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_invalid_parameters_are_rejected() {
+        for (parameters, expected) in [
+            (
+                GaParameters {
+                    population_size: 0,
+                    ..parameters()
+                },
+                ConfigError::ZeroPopulation,
+            ),
+            (
+                GaParameters {
+                    genome_length: 1,
+                    ..parameters()
+                },
+                ConfigError::GenomeTooShort(1),
+            ),
+            (
+                GaParameters {
+                    genome_length: 0,
+                    ..parameters()
+                },
+                ConfigError::GenomeTooShort(0),
+            ),
+        ] {
+            assert_eq!(Evolution::new(parameters, 1).err(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_stops_when_asked() {
+        let mut evolution = Evolution::new(parameters(), 1).unwrap();
+        let stats: Vec<_> = evolution.by_ref().take(3).collect();
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(evolution.generation(), 3);
+        assert!(!evolution.is_finished());
+        assert_eq!(evolution.outcome().0, 3);
+    }
+
+    #[test]
+    fn test_finished_evolution_yields_nothing() {
+        let mut evolution = Evolution::new(parameters(), 1).unwrap();
+        evolution.run(false, |_, _| true);
+
+        assert!(evolution.is_finished());
+        assert_eq!(evolution.next(), None);
+    }
+
+    #[test]
+    fn test_set_population() {
+        let mut parameters = parameters();
+        parameters.mutation_rate = 0.0;
+        parameters.target_generation_fitness = 1.0;
+        let mut evolution = Evolution::new(parameters, 1).unwrap();
+        evolution.next();
+
+        evolution.set_population(vec![vec![1; 30]; 20]).unwrap();
+        assert_eq!(evolution.fitness_values(), vec![1.0; 20].as_slice());
+
+        let stats = evolution.next().unwrap();
+        assert_eq!(stats.generation, 1);
+        assert_eq!(stats.best_fitness, 1.0);
+        assert!(evolution.state().solved);
+        assert_eq!(evolution.next(), None);
+    }
+
+    #[test]
+    fn test_set_population_resizes_the_run() {
+        let mut evolution = Evolution::new(parameters(), 1).unwrap();
+        evolution.next();
+        assert_eq!(
+            evolution.set_population(Vec::new()),
+            Err(ConfigError::ZeroPopulation)
+        );
+        assert_eq!(evolution.population().len(), 20);
+
+        evolution.set_population(vec![vec![0; 30]; 8]).unwrap();
+        assert_eq!(evolution.state().parameters.population_size, 8);
+        evolution.next();
+        assert_eq!(evolution.population().len(), 8);
+        assert_eq!(evolution.fitness_values().len(), 8);
+    }
+
+    #[test]
+    fn test_run_stops_when_on_generation_returns_false() {
+        let mut evolution = Evolution::new(parameters(), 1).unwrap();
+        let (generation, _, _) = evolution.run(false, |_, stats| stats.generation < 9);
+        assert_eq!(generation, 10);
+    }
+
+    #[test]
+    fn test_config_evolution_is_reproducible() {
+        let config = GaConfig::builder().seed(5).build().unwrap();
+        let stats1: Vec<_> = config.evolution().collect();
+        let stats2: Vec<_> = config.evolution().collect();
+        assert_eq!(stats1, stats2);
+    }
+}
@@ -0,0 +1,293 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::fitness::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    struct CountingFitness {
+        calls: Arc<AtomicU64>,
+    }
+
+    impl Fitness for CountingFitness {
+        fn evaluate(&self, genome: &[u8]) -> f64 {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            OneMax.evaluate(genome)
+        }
+    }
+
+    #[test]
+    fn test_cache_hits_and_misses() {
+        let mut cache = FitnessCache::new(2);
+        assert_eq!(cache.get(&[1, 0]), None);
+        cache.insert(&[1, 0], 0.5);
+        assert_eq!(cache.get(&[1, 0]), Some(0.5));
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = FitnessCache::new(2);
+        cache.insert(&[0, 0], 0.0);
+        cache.insert(&[1, 0], 0.5);
+        // Touch the oldest entry so the other one gets evicted
+        assert_eq!(cache.get(&[0, 0]), Some(0.0));
+        cache.insert(&[1, 1], 1.0);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&[1, 0]), None);
+        assert_eq!(cache.get(&[0, 0]), Some(0.0));
+        assert_eq!(cache.get(&[1, 1]), Some(1.0));
+    }
+
+    #[test]
+    fn test_cache_with_zero_capacity_stores_nothing() {
+        let mut cache = FitnessCache::new(0);
+        cache.insert(&[1], 1.0);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&[1]), None);
+    }
+
+    #[test]
+    fn test_evaluator_skips_duplicates() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let fitness = CountingFitness {
+            calls: Arc::clone(&calls),
+        };
+        let mut evaluator = Evaluator::new(Arc::new(fitness)).with_cache(10);
+        let population = vec![vec![1, 1], vec![1, 0], vec![1, 1], vec![1, 1]];
+        let mut evaluations = 0;
+        let fitness_values = evaluator.evaluate_population(&population, &mut evaluations);
+
+        assert_eq!(fitness_values, vec![1.0, 0.5, 1.0, 1.0]);
+        assert_eq!(evaluations, 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            evaluator.cache_stats(),
+            Some(CacheStats { hits: 2, misses: 2 })
+        );
+    }
+
+    #[test]
+    fn test_evaluator_without_cache_counts_every_genome() {
+        let mut evaluator = Evaluator::default();
+        let mut evaluations = 0;
+        evaluator.evaluate_population(&[vec![1], vec![1]], &mut evaluations);
+        assert_eq!(evaluations, 2);
+        assert_eq!(evaluator.cache_stats(), None);
+    }
+
+    #[test]
+    fn test_parallel_evaluation_matches_sequential() {
+        let population: Vec<Vec<u8>> = (0..37u8)
+            .map(|i| (0..8).map(|bit| (i >> (bit % 6)) & 1).collect())
+            .collect();
+        let mut sequential_evaluations = 0;
+        let sequential =
+            Evaluator::default().evaluate_population(&population, &mut sequential_evaluations);
+        let mut parallel_evaluations = 0;
+        let parallel = Evaluator::default()
+            .with_threads(4)
+            .evaluate_population(&population, &mut parallel_evaluations);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel_evaluations, sequential_evaluations);
+    }
+
+    #[test]
+    fn test_parallel_evaluation_with_cache_matches_sequential() {
+        let population = vec![vec![1, 1], vec![1, 0], vec![1, 1], vec![0, 0], vec![1, 0]];
+        let evaluate = |threads: usize| {
+            let calls = Arc::new(AtomicU64::new(0));
+            let fitness = CountingFitness {
+                calls: Arc::clone(&calls),
+            };
+            let mut evaluator = Evaluator::new(Arc::new(fitness))
+                .with_cache(2)
+                .with_threads(threads);
+            let mut evaluations = 0;
+            let values = evaluator.evaluate_population(&population, &mut evaluations);
+            (
+                values,
+                evaluations,
+                evaluator.cache_stats(),
+                calls.load(Ordering::SeqCst),
+            )
+        };
+        let (sequential, sequential_evaluations, sequential_stats, _) = evaluate(1);
+        let (parallel, parallel_evaluations, parallel_stats, parallel_calls) = evaluate(3);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel_evaluations, sequential_evaluations);
+        assert_eq!(parallel_stats, sequential_stats);
+        // Duplicates are evaluated once even when they miss the cache
+        assert_eq!(parallel_calls, 3);
+    }
+
+    #[test]
+    fn test_zero_threads_means_one() {
+        assert_eq!(Evaluator::default().with_threads(0).threads(), 1);
+    }
+
+    #[test]
+    fn test_parallel_run_matches_sequential_run() {
+        let run = |threads: usize| {
+            GaConfig::builder()
+                .population_size(20)
+                .genome_length(10)
+                .max_generations(50)
+                .evaluation_threads(threads)
+                .seed(3)
+                .build()
+                .unwrap()
+                .run()
+        };
+        let sequential = run(1);
+        let parallel = run(4);
+
+        assert_eq!(parallel.best_genome, sequential.best_genome);
+        assert_eq!(parallel.generation, sequential.generation);
+        assert_eq!(parallel.evaluations, sequential.evaluations);
+    }
+
+    #[test]
+    fn test_cached_run_matches_uncached_run() {
+        let run = |cache: bool| {
+            let builder = GaConfig::builder()
+                .population_size(20)
+                .genome_length(10)
+                .max_generations(50)
+                // Without delta evaluation every child goes through the cache
+                .fitness(CountingFitness {
+                    calls: Arc::new(AtomicU64::new(0)),
+                })
+                .seed(7);
+            let builder = if cache {
+                builder.fitness_cache(100)
+            } else {
+                builder
+            };
+            builder.build().unwrap().run()
+        };
+        let uncached = run(false);
+        let cached = run(true);
+
+        assert_eq!(cached.best_genome, uncached.best_genome);
+        assert_eq!(cached.generation, uncached.generation);
+        assert!(cached.evaluations < uncached.evaluations);
+        let stats = cached.cache_stats.unwrap();
+        assert_eq!(stats.misses, cached.evaluations);
+        assert!(stats.hits > 0);
+        assert_eq!(uncached.cache_stats, None);
+    }
+
+    #[test]
+    fn test_one_max_flips_match_full_evaluation() {
+        let parent = [1, 0, 1, 1, 0, 0, 1];
+        let flipped = [0, 1, 5];
+        let child = [0, 1, 1, 1, 0, 1, 1];
+        let change = Change::Flips {
+            parent: &parent,
+            parent_fitness: OneMax.evaluate(&parent),
+            flipped: &flipped,
+        };
+        assert_eq!(
+            OneMax.evaluate_change(&change),
+            Some(OneMax.evaluate(&child))
+        );
+    }
+
+    #[test]
+    fn test_one_max_crossover_matches_full_evaluation() {
+        let first = [1, 1, 1, 0, 0, 1, 0];
+        let second = [0, 0, 1, 1, 1, 1, 1];
+        for point in 0..=first.len() {
+            let child: Vec<u8> = first[..point]
+                .iter()
+                .chain(&second[point..])
+                .copied()
+                .collect();
+            let change = Change::OnePointCrossover {
+                first: &first,
+                first_fitness: OneMax.evaluate(&first),
+                second: &second,
+                second_fitness: OneMax.evaluate(&second),
+                point,
+            };
+            assert_eq!(
+                OneMax.evaluate_change(&change),
+                Some(OneMax.evaluate(&child))
+            );
+        }
+    }
+
+    #[test]
+    fn test_one_max_rejects_inconsistent_changes() {
+        let first = [1, 1, 0, 0];
+        let change = |second, point| Change::OnePointCrossover {
+            first: &first,
+            first_fitness: 0.5,
+            second,
+            second_fitness: 0.5,
+            point,
+        };
+        // Out of range points and parents of different lengths fall back to a full evaluation
+        assert_eq!(OneMax.evaluate_change(&change(&[0, 0, 1, 1], 5)), None);
+        assert_eq!(OneMax.evaluate_change(&change(&[0, 0, 1], 2)), None);
+        assert_eq!(OneMax.evaluate_change(&change(&[0, 0, 1, 1, 1], 2)), None);
+        let flips = Change::Flips {
+            parent: &first,
+            parent_fitness: 0.5,
+            flipped: &[1, 4],
+        };
+        assert_eq!(OneMax.evaluate_change(&flips), None);
+    }
+
+    #[test]
+    fn test_evaluate_offspring_reuses_known_fitness() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let fitness = CountingFitness {
+            calls: Arc::clone(&calls),
+        };
+        let mut evaluator = Evaluator::new(Arc::new(fitness));
+        let population = vec![vec![1, 1], vec![1, 0], vec![0, 0]];
+        let mut evaluations = 0;
+        let fitness_values = evaluator.evaluate_offspring(
+            &population,
+            &[Some(1.0), None, Some(0.0)],
+            &mut evaluations,
+        );
+
+        assert_eq!(fitness_values, vec![1.0, 0.5, 0.0]);
+        assert_eq!(evaluations, 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_delta_run_matches_full_evaluation_run() {
+        let run = |delta: bool| {
+            let builder = GaConfig::builder()
+                .population_size(21)
+                .genome_length(30)
+                .max_generations(60)
+                .seed(11);
+            let builder = if delta {
+                builder
+            } else {
+                builder.fitness(CountingFitness {
+                    calls: Arc::new(AtomicU64::new(0)),
+                })
+            };
+            builder.build().unwrap().run()
+        };
+        let delta = run(true);
+        let full = run(false);
+
+        assert_eq!(delta.best_genome, full.best_genome);
+        assert_eq!(delta.generation, full.generation);
+        assert_eq!(delta.best_fitness, full.best_fitness);
+        assert_eq!(delta.evaluations, full.evaluations);
+    }
+}
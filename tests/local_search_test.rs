@@ -0,0 +1,237 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::fitness::{Evaluator, Fitness, OneMax};
+use one_max_genetic_algorithm_rust::local_search::*;
+use one_max_genetic_algorithm_rust::operators::{Operators, Replacement};
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::GaParameters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use one_max_genetic_algorithm_rust::evolution::Evolution;
+    use one_max_genetic_algorithm_rust::termination::StopReason;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::sync::Arc;
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 10,
+            genome_length: 16,
+            max_generations: 300,
+            mutation_rate: 0.01,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+        }
+    }
+
+    /// Runs one generation of `replacement` from `population` and returns it with the number of
+    /// evaluations it used.
+    fn generation(
+        replacement: &dyn Replacement,
+        population: &[Vec<u8>],
+        population_size: usize,
+        evaluator: &mut Evaluator,
+    ) -> (Vec<Vec<u8>>, Vec<f64>, u64) {
+        let fitness_values: Vec<f64> = population
+            .iter()
+            .map(|genome| evaluator.evaluate(genome))
+            .collect();
+        let mut evaluations = 0;
+        let (population, fitness_values) = replacement.replace(
+            population_size,
+            population,
+            &fitness_values,
+            &Operators::from_parameters(&parameters()),
+            evaluator,
+            &mut evaluations,
+            &mut ChaCha8Rng::seed_from_u64(7),
+        );
+        (population, fitness_values, evaluations)
+    }
+
+    fn solves<R: Replacement + 'static>(replacement: R) -> bool {
+        GaConfig::builder()
+            .population_size(10)
+            .genome_length(16)
+            .max_generations(300)
+            .target_generation_fitness(0.9)
+            .replacement(replacement)
+            .seed(2)
+            .build()
+            .unwrap()
+            .run()
+            .solved
+    }
+
+    fn is_sorted(values: &[f64]) -> bool {
+        values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    /// Gets worse with every one, except for the all-ones optimum.
+    struct Trap;
+
+    impl Fitness for Trap {
+        fn evaluate(&self, genome: &[u8]) -> f64 {
+            let ones = OneMax.evaluate(genome);
+            if ones == 1.0 {
+                2.0
+            } else {
+                1.0 - ones
+            }
+        }
+
+        fn target(&self) -> f64 {
+            2.0
+        }
+    }
+
+    #[test]
+    fn test_every_baseline_solves_one_max() {
+        assert!(solves(RandomizedLocalSearch));
+        assert!(solves(OnePlusOneEa::new()));
+        assert!(solves(OnePlusLambdaEa::new(4)));
+        assert!(solves(MuPlusOneEa::new()));
+        assert!(solves(HillClimbing::first_improvement()));
+        assert!(solves(HillClimbing::best_improvement()));
+        assert!(solves(SimulatedAnnealing::new(
+            0.05,
+            CoolingSchedule::Geometric { alpha: 0.99 }
+        )));
+    }
+
+    #[test]
+    fn test_trajectory_is_elitist_and_spends_the_budget() {
+        let start = vec![vec![0; 16]];
+        for replacement in [
+            &RandomizedLocalSearch as &dyn Replacement,
+            &OnePlusOneEa::new(),
+            &HillClimbing::first_improvement(),
+        ] {
+            let (population, fitness_values, evaluations) =
+                generation(replacement, &start, 10, &mut Evaluator::default());
+            assert_eq!(population.len(), 10);
+            assert!(evaluations >= 10);
+            assert!(is_sorted(&fitness_values));
+            assert!(fitness_values[9] > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_one_plus_lambda_spends_whole_iterations() {
+        let (population, fitness_values, evaluations) = generation(
+            &OnePlusLambdaEa::new(4),
+            &[vec![0; 16]],
+            10,
+            &mut Evaluator::default(),
+        );
+        assert_eq!(evaluations, 12);
+        assert_eq!(population.len(), 10);
+        assert!(is_sorted(&fitness_values));
+    }
+
+    #[test]
+    fn test_mu_plus_one_never_loses_its_best() {
+        let population: Vec<Vec<u8>> = (0..6u8)
+            .map(|i| (0..16).map(|bit| ((i + bit) % 3 == 0) as u8).collect())
+            .collect();
+        let before: Vec<f64> = population.iter().map(|g| OneMax.evaluate(g)).collect();
+        let (after, fitness_values, evaluations) = generation(
+            &MuPlusOneEa::new(),
+            &population,
+            6,
+            &mut Evaluator::default(),
+        );
+        assert_eq!(after.len(), 6);
+        assert_eq!(evaluations, 6);
+        let min = |values: &[f64]| values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = |values: &[f64]| values.iter().copied().fold(0.0, f64::max);
+        assert!(min(&fitness_values) >= min(&before));
+        assert!(max(&fitness_values) >= max(&before));
+    }
+
+    #[test]
+    fn test_hill_climbing_restarts_from_local_optima() {
+        let mut evaluator = Evaluator::new(Arc::new(Trap));
+        // All zeros is a local optimum: every neighbour is worse
+        let (population, _, evaluations) = generation(
+            &HillClimbing::best_improvement(),
+            &[vec![0; 16]],
+            17,
+            &mut evaluator,
+        );
+        assert_eq!(evaluations, 17);
+        assert_ne!(population[16], vec![0; 16]);
+    }
+
+    #[test]
+    fn test_hill_climbing_stays_at_the_optimum() {
+        let mut evaluator = Evaluator::default();
+        for hill_climbing in [
+            HillClimbing::first_improvement(),
+            HillClimbing::best_improvement(),
+        ] {
+            let (population, _, _) = generation(&hill_climbing, &[vec![1; 16]], 40, &mut evaluator);
+            assert!(population.iter().all(|genome| genome == &vec![1; 16]));
+        }
+    }
+
+    #[test]
+    fn test_trajectories_solve_at_the_sweep_target() {
+        // The parameters of the sweep in main.rs, whose target the trajectory mean lags behind
+        let parameters = GaParameters {
+            population_size: 50,
+            genome_length: 35,
+            max_generations: 400,
+            mutation_rate: 0.001,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.998,
+        };
+        for solver in [Solver::HcFirst, Solver::HcBest] {
+            let operators = Arc::new(solver.operators(&parameters));
+            let solved = (0..4)
+                .filter(|&seed| {
+                    let mut evolution =
+                        Evolution::with_operators(parameters.clone(), seed, Arc::clone(&operators))
+                            .unwrap();
+                    let (_, _, best_fitness) = evolution.run(false, |_, _| true);
+                    evolution.stop_reason() == Some(StopReason::Solved) && best_fitness == 1.0
+                })
+                .count();
+            assert_eq!(solved, 4, "{solver} solved {solved} of 4 runs");
+        }
+    }
+
+    #[test]
+    fn test_annealing_temperature_controls_acceptance() {
+        let start = vec![vec![1; 16]];
+        let (_, frozen, _) = generation(
+            &SimulatedAnnealing::new(0.0, CoolingSchedule::Logarithmic),
+            &start,
+            20,
+            &mut Evaluator::default(),
+        );
+        assert!(frozen.iter().all(|&fitness| fitness == 1.0));
+
+        let (_, hot, _) = generation(
+            &SimulatedAnnealing::new(1e9, CoolingSchedule::Linear { rate: 0.0 }),
+            &start,
+            20,
+            &mut Evaluator::default(),
+        );
+        assert!(hot.iter().any(|&fitness| fitness < 1.0));
+    }
+
+    #[test]
+    fn test_cooling_schedules() {
+        let geometric = CoolingSchedule::Geometric { alpha: 0.5 };
+        assert_approx_eq!(geometric.temperature(8.0, 3), 1.0);
+        let linear = CoolingSchedule::Linear { rate: 2.0 };
+        assert_approx_eq!(linear.temperature(8.0, 3), 2.0);
+        assert_eq!(linear.temperature(8.0, 10), 0.0);
+        assert_approx_eq!(CoolingSchedule::Logarithmic.temperature(8.0, 0), 8.0);
+    }
+}
@@ -0,0 +1,208 @@
+use one_max_genetic_algorithm_rust::checkpoint::SweepCheckpoint;
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::logging::*;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::{
+    process_genetic_algorithm, CheckpointOptions, ExperimentConfig,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn log_path(name: &str) -> PathBuf {
+        env::temp_dir().join(name)
+    }
+
+    fn json_lines(path: &PathBuf) -> Vec<Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_json_sink_records_run_span_and_generations() {
+        let path = log_path("one_max_logging_run_test.jsonl");
+        let config = LogConfig {
+            filter: "debug".to_string(),
+            sinks: vec![LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Json,
+            }],
+        };
+        let subscriber = subscriber(&config).unwrap();
+        let outcome = tracing::subscriber::with_default(subscriber, || {
+            GaConfig::builder()
+                .population_size(10)
+                .genome_length(8)
+                .max_generations(5)
+                .target_generation_fitness(1.0)
+                .seed(7)
+                .build()
+                .unwrap()
+                .run()
+        });
+
+        let events = json_lines(&path);
+        let generations: Vec<&Value> = events
+            .iter()
+            .filter(|event| event["fields"]["generation"].is_u64())
+            .collect();
+        assert_eq!(
+            generations.last().unwrap()["fields"]["generation"],
+            outcome.generation
+        );
+        for event in &generations {
+            assert_eq!(event["level"], "DEBUG");
+            assert_eq!(event["span"]["name"], "run");
+            assert_eq!(event["span"]["seed"], 7);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_filter_drops_lower_levels() {
+        let path = log_path("one_max_logging_filter_test.log");
+        let config = LogConfig {
+            filter: "warn".to_string(),
+            sinks: vec![LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Text,
+            }],
+        };
+        let subscriber = subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            GaConfig::builder()
+                .population_size(10)
+                .genome_length(8)
+                .max_generations(5)
+                .seed(7)
+                .build()
+                .unwrap()
+                .run()
+        });
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_filter_is_rejected() {
+        let config = LogConfig {
+            filter: "info,=nonsense=".to_string(),
+            ..LogConfig::default()
+        };
+        let error = subscriber(&config).err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_resuming_a_checkpoint_without_seed_warns() {
+        let checkpoint_path = log_path("one_max_logging_unseeded_checkpoint.json");
+        let path = log_path("one_max_logging_unseeded_test.jsonl");
+        let config = ExperimentConfig {
+            run_times: 1,
+            generations: 5,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1,
+            mutation_rate_values: vec![0.01],
+            crossover_rate_values: vec![0.6],
+            solver: Solver::Ga,
+            seed: None,
+        };
+        let options = CheckpointOptions {
+            path: checkpoint_path.clone(),
+            resume: false,
+        };
+        process_genetic_algorithm(&config, Some(&options)).unwrap();
+        // Drop the seed like a checkpoint written before sweeps were seeded
+        let mut checkpoint = SweepCheckpoint::load(&checkpoint_path).unwrap();
+        checkpoint.config.seed = None;
+        checkpoint.save(&checkpoint_path).unwrap();
+
+        let subscriber = subscriber(&LogConfig {
+            filter: "warn".to_string(),
+            sinks: vec![LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Json,
+            }],
+        })
+        .unwrap();
+        let options = CheckpointOptions {
+            resume: true,
+            ..options
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            process_genetic_algorithm(&config, Some(&options)).unwrap()
+        });
+
+        let events = json_lines(&path);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["level"], "WARN");
+        assert_eq!(events[0]["fields"]["completed_cells"], 1);
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&checkpoint_path).unwrap();
+    }
+
+    #[test]
+    fn test_init_records_sweep_cells_across_threads() {
+        let path = log_path("one_max_logging_sweep_test.jsonl");
+        init(&LogConfig {
+            filter: "debug".to_string(),
+            sinks: vec![LogSink::File {
+                path: path.clone(),
+                format: LogFormat::Json,
+            }],
+        })
+        .unwrap();
+        // Only one global subscriber can be installed
+        assert!(init(&LogConfig::default()).is_err());
+
+        let config = ExperimentConfig {
+            run_times: 2,
+            generations: 20,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1,
+            mutation_rate_values: vec![0.01],
+            crossover_rate_values: vec![0.6],
+            solver: Solver::Ga,
+            seed: None,
+        };
+        process_genetic_algorithm(&config, None).unwrap();
+
+        let events = json_lines(&path);
+        let cell = events
+            .iter()
+            .find(|event| event["fields"]["message"] == "cell finished")
+            .unwrap();
+        assert_eq!(cell["span"]["name"], "sweep");
+        assert_eq!(cell["span"]["solver"], "ga");
+        let runs: Vec<&Value> = events
+            .iter()
+            .filter(|event| event["span"]["name"] == "run")
+            .collect();
+        assert!(!runs.is_empty());
+        for run in runs {
+            let spans = run["spans"].as_array().unwrap();
+            let names: Vec<&str> = spans
+                .iter()
+                .map(|span| span["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(names, ["sweep", "cell", "run"]);
+            assert_eq!(spans[1]["mutation_rate"], 0.01);
+            assert!(run["span"]["seed"].is_u64());
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use one_max_genetic_algorithm_rust::config::{ConfigError, GaConfig};
+use one_max_genetic_algorithm_rust::fitness::{Fitness, OneMax};
+use one_max_genetic_algorithm_rust::multi_objective::*;
+use one_max_genetic_algorithm_rust::results::Results;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use std::sync::Arc;
+
+    struct NegatedOnes;
+
+    impl Fitness for NegatedOnes {
+        fn evaluate(&self, genome: &[u8]) -> f64 {
+            -(genome.iter().filter(|&&gene| gene == 1).count() as f64)
+        }
+    }
+
+    #[test]
+    fn test_benchmarks() {
+        assert_eq!(Lotz.evaluate(&[1, 1, 0, 1, 0, 0]), vec![2.0, 2.0]);
+        assert_eq!(Lotz.evaluate(&[0, 0, 0]), vec![0.0, 3.0]);
+        assert_eq!(OneMinMax.evaluate(&[1, 0, 1, 1]), vec![1.0, 3.0]);
+        let objectives = FitnessObjectives::new(vec![Arc::new(OneMax), Arc::new(NegatedOnes)]);
+        assert_eq!(objectives.evaluate(&[1, 0]), vec![0.5, -1.0]);
+        assert_eq!(objectives.reference_point(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[2.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_non_dominated_sort() {
+        let objectives = vec![
+            vec![1.0, 1.0],
+            vec![3.0, 1.0],
+            vec![1.0, 3.0],
+            vec![2.0, 2.0],
+            vec![0.0, 0.0],
+        ];
+        let fronts = non_dominated_sort(&objectives);
+        assert_eq!(fronts, vec![vec![1, 2, 3], vec![0], vec![4]]);
+    }
+
+    #[test]
+    fn test_crowding_distance() {
+        let objectives = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 3.0],
+            vec![3.0, 1.0],
+            vec![4.0, 0.0],
+        ];
+        let distances = crowding_distance(&objectives, &[0, 1, 2, 3]);
+        assert_eq!(distances[0], f64::INFINITY);
+        assert_eq!(distances[3], f64::INFINITY);
+        assert_approx_eq!(distances[1], 1.5);
+        assert_approx_eq!(distances[2], 1.5);
+    }
+
+    #[test]
+    fn test_hypervolume() {
+        let points = vec![vec![1.0, 3.0], vec![2.0, 2.0], vec![3.0, 1.0]];
+        assert_approx_eq!(hypervolume(&points, &[0.0, 0.0]), 6.0);
+        // Dominated points and points outside the reference box do not count
+        let points = vec![vec![2.0, 2.0], vec![1.0, 1.0], vec![-1.0, 5.0]];
+        assert_approx_eq!(hypervolume(&points, &[0.0, 0.0]), 4.0);
+        let points = vec![vec![1.0, 1.0, 2.0], vec![2.0, 2.0, 1.0]];
+        assert_approx_eq!(hypervolume(&points, &[0.0, 0.0, 0.0]), 5.0);
+        assert_eq!(hypervolume(&[], &[0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_nsga2_finds_the_one_min_max_front() {
+        let mut nsga2 = Nsga2::new(30, 6, 100);
+        nsga2.seed = Some(4);
+        let outcome = nsga2.run(&OneMinMax).unwrap();
+
+        // The front covers every number of ones from 0 to 6
+        let mut ones: Vec<f64> = outcome
+            .front
+            .iter()
+            .map(|point| point.objectives[1])
+            .collect();
+        ones.dedup();
+        assert_eq!(ones, vec![6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 0.0]);
+        let best: Vec<Vec<f64>> = (0..=6)
+            .map(|ones| vec![(6 - ones) as f64, ones as f64])
+            .collect();
+        assert_approx_eq!(outcome.hypervolume, hypervolume(&best, &[-1.0, -1.0]));
+        assert_eq!(outcome.evaluations, 30 * 101);
+    }
+
+    #[test]
+    fn test_nsga2_front_is_non_dominated_on_lotz() {
+        let mut nsga2 = Nsga2::new(20, 8, 60);
+        nsga2.seed = Some(9);
+        let outcome = nsga2.run(&Lotz).unwrap();
+        for a in &outcome.front {
+            assert_eq!(Lotz.evaluate(&a.genome), a.objectives);
+            for b in &outcome.front {
+                assert!(!dominates(&a.objectives, &b.objectives));
+            }
+        }
+        assert_eq!(nsga2.run(&Lotz).unwrap(), outcome);
+    }
+
+    #[test]
+    fn test_nsga2_validation() {
+        let error = Nsga2::new(10, 1, 10).run(&OneMinMax).err();
+        assert_eq!(error, Some(ConfigError::GenomeTooShort(1)));
+        let mut nsga2 = Nsga2::new(10, 8, 10);
+        nsga2.crossover_rate = 1.5;
+        assert_eq!(
+            nsga2.run(&OneMinMax).err(),
+            Some(ConfigError::RateOutOfRange {
+                name: "crossover rate",
+                value: 1.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_pareto_front_keeps_distinct_non_dominated_genomes() {
+        let population = vec![vec![1, 1], vec![0, 0], vec![1, 0], vec![1, 1]];
+        let objectives: Vec<Vec<f64>> = population.iter().map(|g| Lotz.evaluate(g)).collect();
+        let front = pareto_front(&population, &objectives);
+        let genomes: Vec<Vec<u8>> = front.iter().map(|point| point.genome.clone()).collect();
+        assert_eq!(genomes, vec![vec![0, 0], vec![1, 0], vec![1, 1]]);
+        assert_approx_eq!(
+            population_hypervolume(&population, &Lotz),
+            front_hypervolume(&front, &Lotz)
+        );
+    }
+
+    #[test]
+    fn test_nsga2_replacement_runs_through_ga_config() {
+        let mut config = GaConfig::builder()
+            .population_size(20)
+            .genome_length(10)
+            .max_generations(200)
+            .target_generation_fitness(0.998)
+            .replacement(Nsga2Replacement::new(Arc::new(OneMinMax)))
+            .seed(5)
+            .build()
+            .unwrap();
+        let outcome = config.run();
+        assert!(outcome.solved);
+
+        // The survivors spread along the whole OneMinMax front
+        let mut evolution = config.evolution();
+        evolution.by_ref().take(60).for_each(drop);
+        let mut ones: Vec<usize> = evolution
+            .population()
+            .iter()
+            .map(|genome| genome.iter().filter(|&&gene| gene == 1).count())
+            .collect();
+        ones.sort();
+        ones.dedup();
+        assert_eq!(ones, (0..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_results_hypervolume() {
+        let mut results = Results::new(100, 1.0);
+        results.add_hypervolume(10.0);
+        results.add_hypervolume(20.0);
+        assert_eq!(results.hypervolumes, vec![10.0, 20.0]);
+        assert_approx_eq!(results.avg_hypervolume, 15.0);
+    }
+}
@@ -0,0 +1,119 @@
+use one_max_genetic_algorithm_rust::config::{GaConfig, RunOutcome};
+use one_max_genetic_algorithm_rust::fitness::Evaluator;
+use one_max_genetic_algorithm_rust::niching::*;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::calculate_population_fitness;
+use one_max_genetic_algorithm_rust::operators::{Operators, Replacement};
+use one_max_genetic_algorithm_rust::GaParameters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 10,
+            genome_length: 12,
+            max_generations: 100,
+            mutation_rate: 0.05,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+        }
+    }
+
+    fn population() -> Vec<Vec<u8>> {
+        (0..10u16)
+            .map(|i| (0..12).map(|bit| ((i * 7 + bit) % 3 == 0) as u8).collect())
+            .collect()
+    }
+
+    fn replace_with(replacement: &dyn Replacement) -> (Vec<Vec<u8>>, Vec<f64>, u64) {
+        let population = population();
+        let fitness_values = calculate_population_fitness(&population);
+        let operators = Operators::from_parameters(&parameters());
+        let mut evaluations = 0;
+        let (next_population, next_fitness) = replacement.replace(
+            population.len(),
+            &population,
+            &fitness_values,
+            &operators,
+            &mut Evaluator::default(),
+            &mut evaluations,
+            &mut ChaCha8Rng::seed_from_u64(3),
+        );
+        (next_population, next_fitness, evaluations)
+    }
+
+    #[test]
+    fn test_shared_fitness() {
+        let population = vec![vec![0, 0, 0, 0], vec![0, 0, 0, 1], vec![1, 1, 1, 1]];
+        let fitness_values = vec![0.0, 0.25, 1.0];
+        let shared = shared_fitness(&population, &fitness_values, 2.0, 1.0);
+        // The first two share a niche at distance 1, the last one is alone
+        assert_approx_eq!(shared[0], 0.0);
+        assert_approx_eq!(shared[1], 0.25 / 1.5);
+        assert_approx_eq!(shared[2], 1.0);
+    }
+
+    #[test]
+    fn test_replacements_keep_fitness_consistent() {
+        let replacements: Vec<Box<dyn Replacement>> = vec![
+            Box::new(FitnessSharing::new(3.0)),
+            Box::new(DeterministicCrowding),
+            Box::new(ProbabilisticCrowding),
+            Box::new(RestrictedTournament { window_size: 4 }),
+        ];
+        for replacement in replacements {
+            let (next_population, next_fitness, evaluations) = replace_with(replacement.as_ref());
+            assert_eq!(next_population.len(), 10);
+            assert_eq!(next_fitness, calculate_population_fitness(&next_population));
+            assert_eq!(evaluations, 10);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_crowding_never_loses_fitness() {
+        let population = population();
+        let fitness_values = calculate_population_fitness(&population);
+        let (_, next_fitness, _) = replace_with(&DeterministicCrowding);
+        let total: f64 = fitness_values.iter().sum();
+        let next_total: f64 = next_fitness.iter().sum();
+        assert!(next_total >= total);
+    }
+
+    #[test]
+    fn test_restricted_tournament_never_lowers_best_fitness() {
+        let population = population();
+        let best = calculate_population_fitness(&population)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let (_, next_fitness, _) = replace_with(&RestrictedTournament { window_size: 10 });
+        assert!(next_fitness.into_iter().fold(f64::NEG_INFINITY, f64::max) >= best);
+    }
+
+    fn run<R: Replacement + 'static>(replacement: R) -> RunOutcome {
+        GaConfig::builder()
+            .replacement(replacement)
+            .seed(1)
+            .build()
+            .unwrap()
+            .run()
+    }
+
+    #[test]
+    fn test_niching_runs_on_one_max() {
+        let outcomes = [
+            run(FitnessSharing::new(2.0)),
+            run(DeterministicCrowding),
+            run(ProbabilisticCrowding),
+            run(RestrictedTournament { window_size: 10 }),
+        ];
+        for outcome in outcomes {
+            assert!(outcome.best_fitness > 0.5);
+            assert_eq!(outcome.best_genome.len(), 35);
+        }
+    }
+}
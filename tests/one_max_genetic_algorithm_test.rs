@@ -1,9 +1,7 @@
-#[path = "../src/one_max_genetic_algorithm.rs"]
-mod one_max_genetic_algorithm;
-use one_max_genetic_algorithm::*;
+use one_max_genetic_algorithm_rust::config::ConfigError;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::*;
 
 #[cfg(test)]
-
 mod unit_tests {
     use super::*;
 
@@ -205,6 +203,39 @@ mod unit_tests {
         assert!(population.contains(&selected_individual)); // Ensure the selected individual is from the population
     }
 
+    #[test]
+    fn test_fitness_cmp_ranks_nan_lowest() {
+        use std::cmp::Ordering;
+        assert_eq!(fitness_cmp(0.5, 0.25), Ordering::Greater);
+        assert_eq!(fitness_cmp(f64::NAN, f64::NEG_INFINITY), Ordering::Less);
+        assert_eq!(fitness_cmp(0.0, f64::NAN), Ordering::Greater);
+        assert_eq!(fitness_cmp(f64::NAN, f64::NAN), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_selection_never_picks_nan_fitness() {
+        let population = vec![vec![0, 0], vec![1, 1], vec![0, 1]];
+        let fitness_values = [f64::NAN, 1.0, f64::NAN];
+        for _ in 0..20 {
+            assert_eq!(
+                select_parent_tournament(&population, &fitness_values, 3),
+                vec![1, 1]
+            );
+            assert_eq!(
+                select_parent_roulette(&population, &fitness_values),
+                vec![1, 1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_crossover_single_gene_copies_the_parents() {
+        let (child1, child2) = crossover(&[1], &[0], 1.0);
+        assert_eq!((child1, child2), (vec![1], vec![0]));
+        let (child1, child2) = crossover(&[], &[], 1.0);
+        assert!(child1.is_empty() && child2.is_empty());
+    }
+
     #[test]
     fn test_crossover_no_crossover() {
         // Given
@@ -237,6 +268,31 @@ mod unit_tests {
         assert_ne!(child1, child2);
     }
 
+    #[test]
+    fn test_crossover_point_reports_the_point() {
+        let parent1 = vec![1, 2, 3, 4, 5];
+        let parent2 = vec![6, 7, 8, 9, 10];
+        let mut rng = rand::thread_rng();
+
+        let (child1, child2, point) = crossover_point_with_rng(&parent1, &parent2, 1.0, &mut rng);
+        assert!((1..parent1.len()).contains(&point));
+        assert_eq!(child1[..point], parent1[..point]);
+        assert_eq!(child1[point..], parent2[point..]);
+        assert_eq!(child2[..point], parent2[..point]);
+
+        let (_, _, point) = crossover_point_with_rng(&parent1, &parent2, 0.0, &mut rng);
+        assert_eq!(point, parent1.len());
+    }
+
+    #[test]
+    fn test_mutate_flips_reports_flipped_positions() {
+        let genome = [0, 1, 0, 1, 1];
+        let (mutated, flipped) = mutate_flips_with_rng(&genome, 0.5, &mut rand::thread_rng());
+        for (position, (gene, mutated_gene)) in genome.iter().zip(&mutated).enumerate() {
+            assert_eq!(flipped.contains(&position), gene != mutated_gene);
+        }
+    }
+
     #[test]
     fn test_mutate_zeros_with_rate_1() {
         assert_eq!(mutate(&[0, 0, 0, 0], 1.0), vec![1, 1, 1, 1]);
@@ -348,7 +404,8 @@ mod unit_tests {
             select_parent_mode,
             target_generation_fitness,
             verbose,
-        );
+        )
+        .unwrap();
 
         // Check if generation is less than or equal to max_generations
         assert!(generation <= max_generations);
@@ -359,6 +416,18 @@ mod unit_tests {
         assert!(best_fitness >= 0.0);
         assert!(best_fitness <= 1.0);
     }
+
+    #[test]
+    fn test_genetic_algorithm_rejects_invalid_parameters() {
+        assert_eq!(
+            genetic_algorithm(0, 8, 10, 0.01, 0.6, "tournament", 0.9, false),
+            Err(ConfigError::ZeroPopulation)
+        );
+        assert_eq!(
+            genetic_algorithm(10, 1, 10, 0.01, 0.6, "tournament", 0.9, false),
+            Err(ConfigError::GenomeTooShort(1))
+        );
+    }
 }
 
 mod integration_tests {
@@ -403,7 +472,7 @@ mod integration_tests {
             new_population.push(mutate(&offspring1, mutation_rate));
             new_population.push(mutate(&offspring2, mutation_rate));
         }
-        if population_size % 2 != 0 {
+        if !population_size.is_multiple_of(2) {
             let parent = select_parent(&population, &fitness_values, "roulette");
             new_population.push(mutate(&parent, mutation_rate));
         }
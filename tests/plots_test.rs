@@ -0,0 +1,136 @@
+use one_max_genetic_algorithm_rust::checkpoint::SweepCell;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GenerationStats;
+use one_max_genetic_algorithm_rust::plots::*;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::{process_genetic_algorithm, ExperimentConfig};
+use one_max_genetic_algorithm_rust::Results;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+    use std::env;
+    use std::fs;
+
+    fn cell(mutation_index: usize, crossover_index: usize, generations: &[u32]) -> SweepCell {
+        let mut results = Results::new(100, 1.0);
+        for &generation in generations {
+            results.add_result(generation, 0.9, 1.0);
+        }
+        SweepCell {
+            mutation_index,
+            crossover_index,
+            mutation_rate: [0.01, 0.001][mutation_index],
+            crossover_rate: [0.3, 0.6][crossover_index],
+            results,
+        }
+    }
+
+    fn count(svg: &str, pattern: &str) -> usize {
+        svg.matches(pattern).count()
+    }
+
+    #[test]
+    fn test_nice_ticks() {
+        assert_eq!(nice_ticks(0.0, 1.0, 5), vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+        assert_eq!(
+            nice_ticks(0.0, 400.0, 5),
+            vec![0.0, 100.0, 200.0, 300.0, 400.0]
+        );
+        assert_eq!(nice_ticks(1.0, 1.0, 5), vec![1.0]);
+        let ticks = nice_ticks(0.63, 0.97, 5);
+        assert!(ticks.first().unwrap() >= &0.63 && ticks.last().unwrap() <= &0.97);
+    }
+
+    #[test]
+    fn test_box_stats() {
+        let stats = BoxStats::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 100.0]).unwrap();
+        assert_approx_eq!(stats.q1, 2.25);
+        assert_approx_eq!(stats.median, 3.5);
+        assert_approx_eq!(stats.q3, 4.75);
+        assert_eq!(stats.low_whisker, 1.0);
+        assert_eq!(stats.high_whisker, 5.0);
+        assert_eq!(stats.outliers, vec![100.0]);
+        assert!(BoxStats::new(&[]).is_none());
+    }
+
+    #[test]
+    fn test_convergence_svg_draws_bands_and_lines() {
+        let mut results = Results::new(4, 1.0);
+        let run = |offset: f64| -> Vec<GenerationStats> {
+            (1..=3)
+                .map(|generation| GenerationStats {
+                    generation,
+                    generation_fitness: 0.2 * generation as f64 + offset,
+                    best_fitness: 0.25 * generation as f64 + offset,
+                })
+                .collect()
+        };
+        results.add_convergence(&run(0.0));
+        results.add_convergence(&run(0.1));
+        let svg = convergence_svg(&results, "Runs <1 & 2>");
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Runs &lt;1 &amp; 2&gt;"));
+        assert_eq!(count(&svg, "<polygon class=\"band"), 2);
+        assert_eq!(count(&svg, "<polyline class=\"line"), 2);
+
+        let empty = convergence_svg(&Results::new(4, 1.0), "Empty");
+        assert!(empty.contains("No convergence data"));
+    }
+
+    #[test]
+    fn test_heatmap_svg_marks_missing_cells() {
+        let cells = vec![cell(0, 0, &[10]), cell(0, 1, &[50]), cell(1, 0, &[90])];
+        let svg = heatmap_svg(&cells, &[0.01, 0.001], &[0.3, 0.6], "Scores");
+        assert_eq!(count(&svg, "<rect class=\"cell\""), 3);
+        assert_eq!(count(&svg, "<rect class=\"missing\""), 1);
+        // The fastest cell has the highest score and gets the green end of the scale
+        let (r, g, b) = score_rgb(1.0);
+        assert!(svg.contains(&format!("fill=\"#{r:02x}{g:02x}{b:02x}\"")));
+    }
+
+    #[test]
+    fn test_box_plot_svg_has_a_box_per_cell() {
+        let cells = vec![
+            cell(1, 0, &[10, 20, 30, 40, 95]),
+            cell(0, 0, &[5, 6, 7]),
+            cell(0, 1, &[]),
+        ];
+        let svg = box_plot_svg(&cells, "Generations");
+        assert_eq!(count(&svg, "class=\"box\""), 2);
+        assert_eq!(count(&svg, "class=\"median\""), 2);
+        // Labels follow the grid order whatever the order of the cells
+        let first = svg.find("m 0.0100 c 0.300").unwrap();
+        let last = svg.find("m 0.0010 c 0.300").unwrap();
+        assert!(first < last);
+    }
+
+    #[test]
+    fn test_write_sweep_plots() {
+        let config = ExperimentConfig {
+            run_times: 2,
+            generations: 30,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1,
+            mutation_rate_values: vec![0.01, 0.001],
+            crossover_rate_values: vec![0.6],
+            solver: Solver::Ga,
+            seed: None,
+        };
+        let outcome = process_genetic_algorithm(&config, None).unwrap();
+        let directory = env::temp_dir().join("one_max_plots_test");
+        let paths = write_sweep_plots(&directory, &config, &outcome).unwrap();
+        assert_eq!(paths.len(), 3);
+        for path in &paths {
+            let svg = fs::read_to_string(path).unwrap();
+            assert!(svg.trim_end().ends_with("</svg>"));
+        }
+        let convergence = fs::read_to_string(&paths[0]).unwrap();
+        assert!(convergence.contains("class=\"line best\""));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,180 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::constraints::Repair;
+use one_max_genetic_algorithm_rust::fitness::Fitness;
+use one_max_genetic_algorithm_rust::problems::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::io;
+    use std::path::PathBuf;
+
+    fn data(file: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/data")
+            .join(file)
+    }
+
+    fn genome(length: usize, mask: u32) -> Vec<u8> {
+        (0..length).map(|bit| (mask >> bit & 1) as u8).collect()
+    }
+
+    /// Highest profit over every feasible packing.
+    fn brute_force_knapsack(knapsack: &Knapsack) -> f64 {
+        (0..1u32 << knapsack.len())
+            .map(|mask| genome(knapsack.len(), mask))
+            .filter(|genome| knapsack.is_feasible(genome))
+            .map(|genome| knapsack.profit(&genome))
+            .fold(0.0, f64::max)
+    }
+
+    /// Lowest cost over every cover.
+    fn brute_force_set_cover(set_cover: &SetCover) -> f64 {
+        let columns = set_cover.costs.len();
+        (0..1u32 << columns)
+            .map(|mask| genome(columns, mask))
+            .filter(|genome| set_cover.uncovered_rows(genome) == 0)
+            .map(|genome| set_cover.cost(&genome))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_pisinger_instances_match_known_optima() {
+        let instances = Knapsack::load_pisinger(&data("knapsack_pisinger.txt")).unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].name, "knapPI_1_12_100_1");
+        assert_eq!(instances[0].len(), 12);
+        assert_eq!(instances[0].capacities, vec![336.0]);
+        assert_eq!(instances[0].optimum, Some(479.0));
+        assert_eq!(instances[1].len(), 15);
+        for knapsack in &instances {
+            assert_eq!(Some(brute_force_knapsack(knapsack)), knapsack.optimum);
+        }
+    }
+
+    #[test]
+    fn test_or_library_knapsack_instances_match_known_optima() {
+        let instances = Knapsack::load_or_library(&data("knapsack_mknap.txt")).unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].capacities, vec![104.0, 126.0]);
+        assert_eq!(instances[1].weights.len(), 3);
+        assert_eq!(instances[0].optimum, Some(245.0));
+        assert_eq!(instances[1].optimum, Some(243.0));
+        for knapsack in &instances {
+            assert_eq!(Some(brute_force_knapsack(knapsack)), knapsack.optimum);
+        }
+    }
+
+    #[test]
+    fn test_or_library_set_cover_matches_known_optimum() {
+        let set_cover = SetCover::load_or_library(&data("set_cover_scp.txt")).unwrap();
+        assert_eq!(set_cover.name, "set_cover_scp");
+        assert_eq!(set_cover.rows, 12);
+        assert_eq!(set_cover.costs.len(), 15);
+        // Row 1 is covered by columns 3 and 14
+        assert!(set_cover.columns[2].contains(&0));
+        assert!(set_cover.columns[13].contains(&0));
+        assert_eq!(brute_force_set_cover(&set_cover), 25.0);
+    }
+
+    #[test]
+    fn test_truncated_files_are_invalid_data() {
+        let error = Knapsack::parse_or_library("1\n3 1 10\n1 2 3\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error =
+            Knapsack::parse_pisinger("knapPI\nn 2\nc 5\nz 3\ntime 0\n1,3,4,1\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = SetCover::parse_or_library("1 2\n1 1\n1\n3\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_knapsack_fitness_and_violation() {
+        let knapsack =
+            Knapsack::new(vec![3.0, 4.0, 5.0], vec![2.0, 3.0, 4.0], 5.0).with_optimum(7.0);
+        assert_eq!(knapsack.evaluate(&[1, 1, 0]), 1.0);
+        assert_eq!(knapsack.violation(&[1, 1, 0]), 0.0);
+        assert_eq!(knapsack.violation(&[1, 1, 1]), 4.0 / 5.0);
+        // Overloaded knapsacks stay below the optimum, however much profit they pack
+        assert_eq!(knapsack.evaluate(&[1, 1, 1]), 0.5);
+        assert_eq!(knapsack.evaluate(&[0, 1, 1]), 0.5);
+        assert_eq!(knapsack.evaluate(&[1, 0, 1]), 0.5);
+        assert_eq!(knapsack.target(), 1.0);
+        assert_eq!(
+            Knapsack::new(vec![1.0], vec![1.0], 1.0).target(),
+            f64::INFINITY
+        );
+    }
+
+    #[test]
+    fn test_set_cover_fitness_and_violation() {
+        let set_cover = SetCover::new(
+            3,
+            vec![1.0, 2.0, 1.0],
+            vec![vec![0, 1], vec![1, 2], vec![2]],
+        )
+        .with_optimum(2.0);
+        assert_eq!(set_cover.evaluate(&[1, 0, 1]), 1.0);
+        assert_eq!(set_cover.evaluate(&[1, 1, 1]), 0.5);
+        assert_eq!(set_cover.violation(&[1, 0, 0]), 1.0 / 3.0);
+        // Leaving rows uncovered never pays off, not even for the empty selection
+        assert_eq!(set_cover.evaluate(&[0, 0, 0]), 0.5);
+        assert_eq!(set_cover.evaluate(&[0, 1, 0]), 0.5);
+    }
+
+    #[test]
+    fn test_repairs_make_genomes_feasible() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let knapsack = Knapsack::load_or_library(&data("knapsack_mknap.txt")).unwrap()[1].clone();
+        let mut packed_everything = vec![1; knapsack.len()];
+        assert!(KnapsackRepair::new(knapsack.clone()).repair(&mut packed_everything, &mut rng));
+        assert!(knapsack.is_feasible(&packed_everything));
+
+        let set_cover = SetCover::load_or_library(&data("set_cover_scp.txt")).unwrap();
+        let repair = SetCoverRepair::new(set_cover.clone());
+        let mut empty = vec![0; 15];
+        assert!(repair.repair(&mut empty, &mut rng));
+        assert_eq!(set_cover.uncovered_rows(&empty), 0);
+        // Feasible genomes are left alone
+        let mut cover = empty.clone();
+        assert!(!repair.repair(&mut cover, &mut rng));
+        assert_eq!(cover, empty);
+    }
+
+    #[test]
+    fn test_repaired_runs_reach_known_optima() {
+        let knapsack = Knapsack::load_pisinger(&data("knapsack_pisinger.txt")).unwrap()[0].clone();
+        let outcome = GaConfig::builder()
+            .population_size(30)
+            .genome_length(knapsack.len())
+            .max_generations(200)
+            .mutation_rate(0.1)
+            .repair(KnapsackRepair::new(knapsack.clone()))
+            .fitness(knapsack.clone())
+            .seed(4)
+            .build()
+            .unwrap()
+            .run();
+        assert!(knapsack.is_feasible(&outcome.best_genome));
+        assert_eq!(knapsack.profit(&outcome.best_genome), 479.0);
+
+        let set_cover = SetCover::load_or_library(&data("set_cover_scp.txt"))
+            .unwrap()
+            .with_optimum(25.0);
+        let outcome = GaConfig::builder()
+            .population_size(30)
+            .genome_length(15)
+            .max_generations(200)
+            .mutation_rate(0.1)
+            .repair(SetCoverRepair::new(set_cover.clone()))
+            .fitness(set_cover.clone())
+            .seed(4)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(set_cover.uncovered_rows(&outcome.best_genome), 0);
+        assert_eq!(set_cover.cost(&outcome.best_genome), 25.0);
+    }
+}
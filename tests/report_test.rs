@@ -0,0 +1,113 @@
+use one_max_genetic_algorithm_rust::report::*;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::{process_genetic_algorithm, ExperimentConfig};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::time::Duration;
+
+    fn config() -> ExperimentConfig {
+        ExperimentConfig {
+            run_times: 2,
+            generations: 30,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1,
+            mutation_rate_values: vec![0.01, 0.001],
+            crossover_rate_values: vec![0.6],
+            solver: Solver::Ga,
+            seed: None,
+        }
+    }
+
+    fn environment() -> Environment {
+        Environment {
+            crate_version: "0.1.0".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            cpus: 4,
+            threads: 2,
+            command_line: "one-max --report <here>".to_string(),
+            generated_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_utc(1_700_000_000), "2023-11-14 22:13:20 UTC");
+        assert_eq!(format_utc(951_782_400), "2000-02-29 00:00:00 UTC");
+    }
+
+    #[test]
+    fn test_html_report_is_self_contained() {
+        let config = config();
+        let outcome = process_genetic_algorithm(&config, None).unwrap();
+        let html = html_report(&config, &outcome, &environment());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        // Nothing is loaded from elsewhere
+        assert!(!html.contains("<script") && !html.contains("<link") && !html.contains("src="));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(html.contains("2023-11-14 22:13:20 UTC"));
+        assert!(html.contains("one-max --report &lt;here&gt;"));
+        assert!(html.contains("<th>Genome length</th><td>8</td>"));
+        assert_eq!(html.matches("<tr class=\"best\">").count(), 1);
+        assert!(!html.contains("aborted"));
+    }
+
+    #[test]
+    fn test_html_report_marks_cells_that_did_not_run() {
+        let config = config();
+        let mut outcome = process_genetic_algorithm(&config, None).unwrap();
+        outcome.cells.retain(|cell| cell.mutation_index == 0);
+        outcome.aborted = true;
+        let html = html_report(&config, &outcome, &environment());
+        assert_eq!(html.matches("not run").count(), 1);
+        assert!(html.contains("The sweep was aborted"));
+    }
+
+    #[test]
+    fn test_json_report() {
+        let config = config();
+        let outcome = process_genetic_algorithm(&config, None).unwrap();
+        let json = json_report(&config, &outcome, Duration::from_millis(1500));
+
+        assert_eq!(json["seed"], outcome.seed);
+        // The config reproduces the sweep
+        assert_eq!(json["config"]["seed"], outcome.seed);
+        assert_eq!(json["config"]["genome_length"], 8);
+        assert_eq!(json["best"]["mutation_rate"], outcome.best_mutation_rate);
+        assert_eq!(json["best"]["crossover_rate"], outcome.best_crossover_rate);
+        assert_eq!(json["best"]["results"]["score"], outcome.best_result.score);
+        assert_eq!(
+            json["best"]["results"]["generations"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(json["aborted"], false);
+        assert_eq!(json["completed_cells"], outcome.cells.len());
+        assert_eq!(json["elapsed_seconds"], 1.5);
+    }
+
+    #[test]
+    fn test_write_html_report() {
+        let config = config();
+        let outcome = process_genetic_algorithm(&config, None).unwrap();
+        let path = env::temp_dir().join("one_max_report_test.html");
+        write_html_report(&path, &config, &outcome).unwrap();
+        let html = fs::read_to_string(&path).unwrap();
+        assert!(html.contains("One-Max sweep report: ga"));
+        let threads = format!("<th>Concurrent runs</th><td>{}</td>", outcome.threads);
+        assert!(html.contains(&threads));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,118 @@
+use one_max_genetic_algorithm_rust::config::{ConfigError, GaConfig};
+use one_max_genetic_algorithm_rust::restart::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population() -> (Vec<Vec<u8>>, Vec<f64>) {
+        let population = vec![
+            vec![0, 0, 0, 0],
+            vec![1, 1, 1, 0],
+            vec![1, 0, 0, 0],
+            vec![1, 1, 0, 0],
+        ];
+        let fitness_values = vec![0.0, 0.75, 0.25, 0.5];
+        (population, fitness_values)
+    }
+
+    #[test]
+    fn test_full_restart_keeps_the_best() {
+        let (population, fitness_values) = population();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let new_population = FullRestart.restart(&population, &fitness_values, 4, &mut rng);
+        assert_eq!(new_population.len(), 4);
+        assert_eq!(new_population[0], vec![1, 1, 1, 0]);
+        assert!(new_population.iter().all(|genome| genome.len() == 4));
+    }
+
+    #[test]
+    fn test_partial_restart_replaces_the_worst() {
+        let (population, fitness_values) = population();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut restart = PartialRestart {
+            elite: 1,
+            fraction: 0.5,
+            mutation_rate: 0.0,
+        };
+        let new_population = restart.restart(&population, &fitness_values, 4, &mut rng);
+        // The two worst genomes become copies of the best, the others are kept
+        assert_eq!(
+            new_population,
+            vec![
+                vec![1, 1, 1, 0],
+                vec![1, 1, 1, 0],
+                vec![1, 1, 1, 0],
+                vec![1, 1, 0, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ipop_restart_grows_the_population() {
+        let (population, fitness_values) = population();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut restart = IpopRestart {
+            growth_factor: 1.5,
+            max_population_size: 100,
+        };
+        let new_population = restart.restart(&population, &fitness_values, 4, &mut rng);
+        assert_eq!(new_population.len(), 6);
+        assert_eq!(new_population[0], vec![1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_run_records_restarts() {
+        let outcome = GaConfig::builder()
+            .population_size(4)
+            .genome_length(40)
+            .max_generations(60)
+            .mutation_rate(0.0)
+            .crossover_rate(0.0)
+            .restart_on_stagnation(
+                5,
+                IpopRestart {
+                    growth_factor: 2.0,
+                    max_population_size: 64,
+                },
+            )
+            .seed(2)
+            .build()
+            .unwrap()
+            .run();
+
+        assert!(!outcome.restarts.is_empty());
+        let mut population_size = 4;
+        for event in &outcome.restarts {
+            population_size = (population_size * 2).min(64);
+            assert_eq!(event.strategy, "IPOP");
+            assert_eq!(event.population_size, population_size);
+        }
+        assert!(outcome
+            .restarts
+            .windows(2)
+            .all(|pair| pair[0].generation < pair[1].generation));
+    }
+
+    #[test]
+    fn test_run_without_restart_policy_never_restarts() {
+        let outcome = GaConfig::builder()
+            .max_generations(30)
+            .seed(2)
+            .build()
+            .unwrap()
+            .run();
+        assert!(outcome.restarts.is_empty());
+    }
+
+    #[test]
+    fn test_zero_stagnation_is_rejected() {
+        let error = GaConfig::builder()
+            .restart_on_stagnation(0, FullRestart)
+            .build()
+            .err();
+        assert_eq!(error, Some(ConfigError::ZeroRestartStagnation));
+    }
+}
@@ -1,7 +1,7 @@
-#[path = "../src/results.rs"]
-mod results;
 use assert_approx_eq::assert_approx_eq;
-use results::*;
+use one_max_genetic_algorithm_rust::diversity::Diversity;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GenerationStats;
+use one_max_genetic_algorithm_rust::results::*;
 
 #[cfg(test)]
 mod tests {
@@ -43,4 +43,54 @@ mod tests {
 
         assert_approx_eq!(results.get_score(), expected_score);
     }
+
+    #[test]
+    fn test_add_diversity() {
+        let mut results = Results::new(100, 1.0);
+        results.add_result(60, 0.8, 0.9);
+        let score = results.get_score();
+        results.add_diversity(Diversity {
+            mean_hamming_distance: 2.0,
+            mean_locus_entropy: 0.5,
+            unique_genomes: 10,
+            allele_frequency_spread: 0.1,
+        });
+        results.add_diversity(Diversity {
+            mean_hamming_distance: 4.0,
+            mean_locus_entropy: 0.7,
+            unique_genomes: 5,
+            allele_frequency_spread: 0.3,
+        });
+
+        assert_eq!(results.diversities.len(), 2);
+        assert_approx_eq!(results.avg_hamming_distance, 3.0);
+        assert_approx_eq!(results.avg_locus_entropy, 0.6);
+        assert_approx_eq!(results.avg_unique_genomes, 7.5);
+        assert_approx_eq!(results.avg_allele_frequency_spread, 0.2);
+        assert_eq!(results.get_score(), score);
+    }
+
+    #[test]
+    fn test_add_convergence() {
+        let stats = |best_fitness: f64, generation_fitness: f64| GenerationStats {
+            generation: 0,
+            generation_fitness,
+            best_fitness,
+        };
+        let mut results = Results::new(3, 1.0);
+        results.add_convergence(&[stats(0.5, 0.4), stats(0.7, 0.6), stats(0.9, 0.8)]);
+        // Stopped after one generation, so it counts with its final values afterwards
+        results.add_convergence(&[stats(1.0, 0.9)]);
+        results.add_convergence(&[]);
+
+        assert_eq!(results.convergence.len(), 3);
+        let first = results.convergence[0];
+        assert_eq!(first.runs, 2);
+        assert_approx_eq!(first.avg_best(), 0.75);
+        assert_approx_eq!(first.avg_mean(), 0.65);
+        assert_eq!((first.best_min, first.best_max), (0.5, 1.0));
+        let last = results.convergence[2];
+        assert_eq!((last.mean_min, last.mean_max), (0.8, 0.9));
+        assert_eq!(results.total_generations, 0);
+    }
 }
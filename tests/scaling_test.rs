@@ -0,0 +1,175 @@
+use one_max_genetic_algorithm_rust::config::ConfigError;
+use one_max_genetic_algorithm_rust::scaling::*;
+use one_max_genetic_algorithm_rust::solver::Solver;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn synthetic(model: ComplexityModel, coefficient: f64) -> Vec<(f64, f64)> {
+        [10.0, 20.0, 40.0, 80.0, 160.0]
+            .iter()
+            .map(|&n| (n, coefficient * model.growth(n)))
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_model_recovers_the_coefficient() {
+        let fit = fit_model(
+            ComplexityModel::NLogN,
+            &synthetic(ComplexityModel::NLogN, 2.5),
+        );
+        assert_approx_eq!(fit.coefficient, 2.5);
+        assert!(fit.relative_error < 1e-9);
+    }
+
+    #[test]
+    fn test_report_picks_the_generating_model() {
+        for model in [
+            ComplexityModel::Linear,
+            ComplexityModel::NLogN,
+            ComplexityModel::Quadratic,
+        ] {
+            let points = synthetic(model, 3.0)
+                .into_iter()
+                .map(|(n, y)| ScalingPoint::new(n as usize, vec![y.round() as u64], 0))
+                .collect();
+            let report = ScalingReport::new(Solver::Ga, points);
+            assert_eq!(report.best_fit().unwrap().model, model);
+            assert_eq!(report.fits.len(), ComplexityModel::ALL.len());
+        }
+    }
+
+    #[test]
+    fn test_log_log_slope() {
+        assert_approx_eq!(
+            log_log_slope(&synthetic(ComplexityModel::Quadratic, 7.0)),
+            2.0
+        );
+        assert_approx_eq!(log_log_slope(&synthetic(ComplexityModel::Linear, 0.5)), 1.0);
+    }
+
+    #[test]
+    fn test_scaling_point_statistics() {
+        let point = ScalingPoint::new(10, vec![10, 20, 30], 1);
+        assert_approx_eq!(point.mean, 20.0);
+        assert_approx_eq!(point.std_dev, 10.0);
+        let half_width = 4.303 * 10.0 / 3f64.sqrt();
+        assert_approx_eq!(point.ci_low, 20.0 - half_width);
+        assert_approx_eq!(point.ci_high, 20.0 + half_width);
+
+        let single = ScalingPoint::new(10, vec![42], 0);
+        assert_eq!((single.ci_low, single.ci_high), (42.0, 42.0));
+    }
+
+    #[test]
+    fn test_t_critical_values_shrink_towards_the_normal() {
+        assert_approx_eq!(t_critical_95(1), 12.706);
+        assert_approx_eq!(t_critical_95(30), 2.042);
+        assert_approx_eq!(t_critical_95(1000), 1.96);
+        assert!(t_critical_95(0).is_infinite());
+    }
+
+    #[test]
+    fn test_report_needs_two_solved_lengths() {
+        let points = vec![
+            ScalingPoint::new(10, vec![100], 0),
+            ScalingPoint::new(20, vec![], 3),
+        ];
+        let report = ScalingReport::new(Solver::Ga, points);
+        assert!(report.best_fit().is_none());
+        assert!(report.log_log_slope.is_nan());
+    }
+
+    #[test]
+    fn test_run_scaling_is_reproducible_and_grows() {
+        let config = ScalingConfig {
+            genome_lengths: vec![8, 32],
+            runs: 5,
+            solver: Solver::OnePlusOneEa,
+            population_size: 10,
+            max_generations: 2000,
+            seed: Some(3),
+            threads: 2,
+            ..ScalingConfig::default()
+        };
+        let report = run_scaling(&config).unwrap();
+        assert_eq!(report, run_scaling(&config).unwrap());
+        assert_eq!(report.points.len(), 2);
+        for point in &report.points {
+            assert_eq!(point.evaluations.len() + point.failures, 5);
+            assert_eq!(point.failures, 0);
+            assert!(point.ci_low <= point.mean && point.mean <= point.ci_high);
+        }
+        assert!(report.points[1].mean > report.points[0].mean);
+        assert_eq!(report.fits.len(), ComplexityModel::ALL.len());
+    }
+
+    #[test]
+    fn test_trajectory_solvers_start_from_one_genome() {
+        let config = ScalingConfig {
+            solver: Solver::Rls,
+            population_size: 50,
+            max_generations: 100,
+            ..ScalingConfig::default()
+        };
+        let parameters = config.parameters(16);
+        assert_eq!(parameters.population_size, 1);
+        assert_eq!(parameters.max_generations, 5000);
+        let parameters = ScalingConfig {
+            solver: Solver::Ga,
+            ..config
+        }
+        .parameters(16);
+        assert_eq!(parameters.population_size, 50);
+        assert_eq!(parameters.max_generations, 100);
+    }
+
+    #[test]
+    fn test_fitted_models_match_the_known_run_times() {
+        let fit = |solver| {
+            let report = run_scaling(&ScalingConfig {
+                genome_lengths: vec![16, 32, 64, 128],
+                runs: 40,
+                solver,
+                seed: Some(11),
+                threads: 1,
+                ..ScalingConfig::default()
+            })
+            .unwrap();
+            *report.best_fit().unwrap()
+        };
+        // RLS collects the missing bits like coupons, n H(n/2) ~ n ln n evaluations from a random
+        // genome
+        let rls = fit(Solver::Rls);
+        assert_eq!(rls.model, ComplexityModel::NLogN);
+        assert_approx_eq!(rls.coefficient, 1.0, 0.15);
+        // The (1+1) EA needs e n ln n - O(n), about 2.3 n ln n at these lengths
+        let ea = fit(Solver::OnePlusOneEa);
+        assert_eq!(ea.model, ComplexityModel::NLogN);
+        assert!(ea.coefficient > 2.0 && ea.coefficient < std::f64::consts::E);
+    }
+
+    #[test]
+    fn test_run_scaling_rejects_invalid_configs() {
+        let config = |genome_lengths: Vec<usize>, runs| ScalingConfig {
+            genome_lengths,
+            runs,
+            ..ScalingConfig::default()
+        };
+        assert_eq!(
+            run_scaling(&config(vec![8, 1], 2)).err(),
+            Some(ConfigError::GenomeTooShort(1))
+        );
+        assert_eq!(
+            run_scaling(&config(vec![], 2)).err(),
+            Some(ConfigError::EmptyValues("genome lengths"))
+        );
+        assert_eq!(
+            run_scaling(&config(vec![8], 0)).err(),
+            Some(ConfigError::ZeroRuns)
+        );
+        assert!(ScalingConfig::default().threads >= 1);
+    }
+}
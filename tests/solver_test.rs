@@ -0,0 +1,41 @@
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::GaParameters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> GaParameters {
+        GaParameters {
+            population_size: 10,
+            genome_length: 16,
+            max_generations: 50,
+            mutation_rate: 0.01,
+            crossover_rate: 0.5,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_solver_names_round_trip() {
+        for solver in Solver::ALL {
+            assert_eq!(solver.to_string().parse::<Solver>(), Ok(solver));
+            let json = serde_json::to_string(&solver).unwrap();
+            assert_eq!(json, format!("\"{solver}\""));
+            assert_eq!(serde_json::from_str::<Solver>(&json).unwrap(), solver);
+        }
+        assert_eq!("PBIL".parse::<Solver>(), Ok(Solver::Pbil));
+        assert!("nsga".parse::<Solver>().is_err());
+    }
+
+    #[test]
+    fn test_only_the_ga_keeps_the_default_replacement() {
+        for solver in Solver::ALL {
+            assert_eq!(
+                solver.replacement(&parameters()).is_none(),
+                solver == Solver::Ga
+            );
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use one_max_genetic_algorithm_rust::checkpoint::SweepCheckpoint;
+use one_max_genetic_algorithm_rust::config::ConfigError;
+use one_max_genetic_algorithm_rust::error::Error;
+use one_max_genetic_algorithm_rust::solver::Solver;
+use one_max_genetic_algorithm_rust::sweep::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::GenerationStats;
+
+    /// Counts the events of a sweep and asks it to abort after `abort_after` cells.
+    #[derive(Default)]
+    struct Counter {
+        cells: AtomicUsize,
+        runs: AtomicUsize,
+        generations: AtomicUsize,
+        abort_after: Option<usize>,
+    }
+
+    impl SweepObserver for Counter {
+        fn run_finished(&self, _worker: usize) {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn generation(&self, _worker: usize, _stats: &GenerationStats) {
+            self.generations.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn cell_finished(&self, _mutation_index: usize, _crossover_index: usize, _score: f64) {
+            self.cells.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn should_abort(&self) -> bool {
+            self.abort_after
+                .is_some_and(|cells| self.cells.load(Ordering::SeqCst) >= cells)
+        }
+    }
+
+    fn config() -> ExperimentConfig {
+        ExperimentConfig {
+            run_times: 2,
+            generations: 50,
+            population_size: 10,
+            genome_length: 8,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+            target_problem_fitness: 1.1, // Never reached so the whole grid is considered
+            mutation_rate_values: vec![0.01, 0.001],
+            crossover_rate_values: vec![0.6],
+            solver: Solver::Ga,
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn test_run_cell_with_every_solver() {
+        for solver in Solver::ALL {
+            let config = ExperimentConfig { solver, ..config() };
+            let result = run_cell(&config, 0.01, 0.5, &[2]);
+            assert_eq!(result.total_generations, 2);
+            assert!(result.get_score() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_multi_objective_cells_record_hypervolume() {
+        let nsga2 = ExperimentConfig {
+            solver: Solver::Nsga2,
+            ..config()
+        };
+        let result = run_cell(&nsga2, 0.01, 0.5, &[2]);
+        assert_eq!(result.hypervolumes.len(), 2);
+        assert!(result.avg_hypervolume > 0.0);
+        assert!(run_cell(&config(), 0.01, 0.5, &[2]).hypervolumes.is_empty());
+    }
+
+    #[test]
+    fn test_process_genetic_algorithm_validates_config() {
+        let invalid = [
+            (
+                ExperimentConfig {
+                    genome_length: 1,
+                    ..config()
+                },
+                ConfigError::GenomeTooShort(1),
+            ),
+            (
+                ExperimentConfig {
+                    population_size: 0,
+                    ..config()
+                },
+                ConfigError::ZeroPopulation,
+            ),
+            (
+                ExperimentConfig {
+                    run_times: 0,
+                    ..config()
+                },
+                ConfigError::ZeroRuns,
+            ),
+            (
+                ExperimentConfig {
+                    crossover_rate_values: vec![],
+                    ..config()
+                },
+                ConfigError::EmptyValues("crossover rates"),
+            ),
+            (
+                ExperimentConfig {
+                    mutation_rate_values: vec![0.01, 1.5],
+                    ..config()
+                },
+                ConfigError::RateOutOfRange {
+                    name: "mutation rate",
+                    value: 1.5,
+                },
+            ),
+        ];
+        for (config, expected) in invalid {
+            assert_eq!(config.validate(), Err(expected.clone()));
+            assert!(matches!(
+                process_genetic_algorithm(&config, None),
+                Err(Error::Config(error)) if error == expected
+            ));
+        }
+        assert_eq!(config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_sweep_threads_is_at_least_one() {
+        assert!(sweep_threads() >= 1);
+    }
+
+    #[test]
+    fn test_jobs_threads_for() {
+        assert_eq!(Jobs::Count(3).threads_for(8), 3);
+        assert_eq!(Jobs::Count(16).threads_for(8), 16);
+        assert_eq!(Jobs::All.threads_for(8), 8);
+        assert_eq!(Jobs::AllMinus(2).threads_for(8), 6);
+        // Never less than one, even on small machines
+        assert_eq!(Jobs::AllMinus(2).threads_for(2), 1);
+        assert_eq!(Jobs::AllMinus(2).threads_for(1), 1);
+        assert_eq!(Jobs::Count(0).threads_for(8), 1);
+        assert_eq!(Jobs::default(), Jobs::AllMinus(2));
+    }
+
+    #[test]
+    fn test_jobs_parse_and_display() {
+        for (text, jobs) in [
+            ("4", Jobs::Count(4)),
+            ("all", Jobs::All),
+            ("ALL", Jobs::All),
+            ("all-2", Jobs::AllMinus(2)),
+            ("all-0", Jobs::AllMinus(0)),
+        ] {
+            assert_eq!(text.parse::<Jobs>(), Ok(jobs));
+        }
+        for jobs in [Jobs::Count(4), Jobs::All, Jobs::AllMinus(2)] {
+            assert_eq!(jobs.to_string().parse::<Jobs>(), Ok(jobs));
+        }
+        for invalid in ["0", "-1", "all-", "all-x", "allx", "some", ""] {
+            assert!(invalid.parse::<Jobs>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_process_genetic_algorithm_with_workers() {
+        let workers = Workers {
+            jobs: Jobs::Count(1),
+            pin_cores: true,
+        };
+        let outcome = process_genetic_algorithm_observed(&config(), None, None, &workers).unwrap();
+        assert_eq!(outcome.threads, 1);
+        assert_eq!(outcome.cells.len(), 2);
+        assert_eq!(outcome.cells[0].results.total_generations, 2);
+    }
+
+    #[test]
+    fn test_run_cell_pinned_to_cores() {
+        let cores = core_affinity::get_core_ids().unwrap_or_default();
+        let result = run_cell_observed(&config(), 0.01, 0.6, &[2, 1], None, &cores);
+        assert_eq!(result.total_generations, 3);
+    }
+
+    #[test]
+    fn test_run_seed() {
+        assert_eq!(run_seed(1, 0.01, 0.6, 0), run_seed(1, 0.01, 0.6, 0));
+        assert_ne!(run_seed(1, 0.01, 0.6, 0), run_seed(2, 0.01, 0.6, 0));
+        assert_ne!(run_seed(1, 0.01, 0.6, 0), run_seed(1, 0.01, 0.6, 1));
+        assert_ne!(run_seed(1, 0.01, 0.6, 0), run_seed(1, 0.001, 0.6, 0));
+        assert_ne!(run_seed(1, 0.01, 0.6, 0), run_seed(1, 0.01, 0.5, 0));
+    }
+
+    #[test]
+    fn test_seeded_run_cell_is_reproducible() {
+        let config = ExperimentConfig {
+            seed: Some(42),
+            ..config()
+        };
+        // One run per batch so the results are added in the same order
+        let first = run_cell(&config, 0.01, 0.6, &[1, 1, 1]);
+        let second = run_cell(&config, 0.01, 0.6, &[1, 1, 1]);
+        assert_eq!(first.generations, second.generations);
+        assert_eq!(first.best_fitnesses, second.best_fitnesses);
+        assert_eq!(first.generation_fitnesses, second.generation_fitnesses);
+    }
+
+    #[test]
+    fn test_process_genetic_algorithm_reports_seed() {
+        let config = ExperimentConfig {
+            seed: Some(7),
+            ..config()
+        };
+        assert_eq!(process_genetic_algorithm(&config, None).unwrap().seed, 7);
+    }
+
+    #[test]
+    fn test_run_cell() {
+        let result = run_cell(&config(), 0.01, 0.6, &[2, 1]);
+        assert_eq!(result.total_generations, 3);
+        assert!(result.get_score() > 0.0);
+    }
+
+    #[test]
+    fn test_process_genetic_algorithm_best_cell() {
+        let config = config();
+        let outcome = process_genetic_algorithm(&config, None).unwrap();
+
+        assert!(config
+            .mutation_rate_values
+            .contains(&outcome.best_mutation_rate));
+        assert_eq!(outcome.best_crossover_rate, 0.6);
+        assert_eq!(outcome.best_result.total_generations, config.run_times);
+        assert_eq!(outcome.cells.len(), 2);
+        assert_eq!(
+            outcome.best_result.convergence.len(),
+            config.generations as usize
+        );
+        assert_eq!(outcome.best_result.convergence[0].runs, config.run_times);
+    }
+
+    #[test]
+    fn test_process_genetic_algorithm_resume() {
+        let path = env::temp_dir().join("one_max_sweep_test_resume.json");
+        let options = CheckpointOptions {
+            path: path.clone(),
+            resume: false,
+        };
+        let outcome = process_genetic_algorithm(&config(), Some(&options)).unwrap();
+        assert_eq!(SweepCheckpoint::load(&path).unwrap().cells.len(), 2);
+
+        // Every cell is replayed from the checkpoint so the outcome is identical
+        let options = CheckpointOptions {
+            path: path.clone(),
+            resume: true,
+        };
+        let resumed = process_genetic_algorithm(&config(), Some(&options)).unwrap();
+        assert_eq!(resumed.best_mutation_rate, outcome.best_mutation_rate);
+        assert_eq!(resumed.best_result.score, outcome.best_result.score);
+        // The random seed of the first sweep is kept in the checkpoint
+        assert_eq!(resumed.seed, outcome.seed);
+
+        let mut other_config = config();
+        other_config.genome_length = 9;
+        assert!(matches!(
+            process_genetic_algorithm(&other_config, Some(&options)),
+            Err(Error::IncompatibleCheckpoint(checkpoint)) if checkpoint == path
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_observer_sees_every_cell_and_run() {
+        let counter = Arc::new(Counter::default());
+        let observer: Arc<dyn SweepObserver> = counter.clone();
+        let outcome = process_genetic_algorithm_observed(
+            &config(),
+            None,
+            Some(observer),
+            &Workers::default(),
+        )
+        .unwrap();
+        assert!(!outcome.aborted);
+        assert_eq!(counter.cells.load(Ordering::SeqCst), 2);
+        assert_eq!(counter.runs.load(Ordering::SeqCst), 4);
+        assert!(counter.generations.load(Ordering::SeqCst) >= 4);
+    }
+
+    #[test]
+    fn test_abort_keeps_only_the_completed_cells() {
+        let path = env::temp_dir().join("one_max_sweep_test_abort.json");
+        let options = CheckpointOptions {
+            path: path.clone(),
+            resume: false,
+        };
+        let counter = Arc::new(Counter {
+            abort_after: Some(1),
+            ..Counter::default()
+        });
+        let observer: Arc<dyn SweepObserver> = counter.clone();
+        let outcome = process_genetic_algorithm_observed(
+            &config(),
+            Some(&options),
+            Some(observer),
+            &Workers::default(),
+        )
+        .unwrap();
+        assert!(outcome.aborted);
+        assert_eq!(counter.cells.load(Ordering::SeqCst), 1);
+        assert_eq!(SweepCheckpoint::load(&path).unwrap().cells.len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+}
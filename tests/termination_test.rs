@@ -0,0 +1,192 @@
+use one_max_genetic_algorithm_rust::config::GaConfig;
+use one_max_genetic_algorithm_rust::one_max_genetic_algorithm::{
+    GaParameters, GaState, GenerationStats,
+};
+use one_max_genetic_algorithm_rust::termination::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn state() -> GaState {
+        let parameters = GaParameters {
+            population_size: 4,
+            genome_length: 6,
+            max_generations: 10,
+            mutation_rate: 0.01,
+            crossover_rate: 0.6,
+            select_parent_mode: "tournament".to_string(),
+            target_generation_fitness: 0.9,
+        };
+        GaState::new(parameters, 1)
+    }
+
+    fn stats(generation: u32, best_fitness: f64) -> GenerationStats {
+        GenerationStats {
+            generation,
+            generation_fitness: best_fitness / 2.0,
+            best_fitness,
+        }
+    }
+
+    #[test]
+    fn test_max_generations() {
+        let mut criterion = MaxGenerations(3);
+        assert_eq!(criterion.check(&state(), &stats(1, 0.5)), None);
+        assert_eq!(
+            criterion.check(&state(), &stats(2, 0.5)),
+            Some(StopReason::Generations(3))
+        );
+    }
+
+    #[test]
+    fn test_max_evaluations() {
+        let mut state = state();
+        assert_eq!(state.evaluations, 4);
+        assert_eq!(MaxEvaluations(8).check(&state, &stats(0, 0.5)), None);
+
+        state.evaluations = 8;
+        assert_eq!(
+            MaxEvaluations(8).check(&state, &stats(0, 0.5)),
+            Some(StopReason::Evaluations(8))
+        );
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let mut criterion = TimeLimit::new(Duration::ZERO);
+        criterion.reset();
+        assert_eq!(
+            criterion.check(&state(), &stats(0, 0.5)),
+            Some(StopReason::TimeLimit(Duration::ZERO))
+        );
+        let mut criterion = TimeLimit::new(Duration::from_secs(3600));
+        criterion.reset();
+        assert_eq!(criterion.check(&state(), &stats(0, 0.5)), None);
+    }
+
+    #[test]
+    fn test_stagnation() {
+        let mut criterion = Stagnation::new(2);
+        assert_eq!(criterion.check(&state(), &stats(0, 0.5)), None);
+        assert_eq!(criterion.check(&state(), &stats(1, 0.5)), None);
+        assert_eq!(
+            criterion.check(&state(), &stats(2, 0.5)),
+            Some(StopReason::Stagnation(2))
+        );
+
+        criterion.reset();
+        assert_eq!(criterion.check(&state(), &stats(0, 0.5)), None);
+        assert_eq!(criterion.check(&state(), &stats(1, 0.6)), None);
+    }
+
+    #[test]
+    fn test_converged() {
+        let mut state = state();
+        state.population = vec![vec![0, 1, 0, 1, 0, 1]; 4];
+        assert_eq!(
+            Converged.check(&state, &stats(0, 0.5)),
+            Some(StopReason::Converged)
+        );
+
+        state.population[3] = vec![1; 6];
+        assert_eq!(Converged.check(&state, &stats(0, 0.5)), None);
+    }
+
+    #[test]
+    fn test_target_fitness() {
+        assert_eq!(TargetFitness(0.8).check(&state(), &stats(0, 0.5)), None);
+        assert_eq!(
+            TargetFitness(0.8).check(&state(), &stats(0, 0.9)),
+            Some(StopReason::TargetFitness(0.8))
+        );
+    }
+
+    #[test]
+    fn test_closure() {
+        let mut criterion = |_: &GaState, stats: &GenerationStats| stats.generation == 2;
+        assert_eq!(criterion.check(&state(), &stats(1, 0.5)), None);
+        assert!(matches!(
+            criterion.check(&state(), &stats(2, 0.5)),
+            Some(StopReason::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_any_of() {
+        let mut criterion = TargetFitness(0.8).or(MaxGenerations(3));
+        assert_eq!(criterion.check(&state(), &stats(0, 0.5)), None);
+        assert_eq!(
+            criterion.check(&state(), &stats(0, 0.9)),
+            Some(StopReason::TargetFitness(0.8))
+        );
+        assert_eq!(
+            criterion.check(&state(), &stats(2, 0.5)),
+            Some(StopReason::Generations(3))
+        );
+    }
+
+    #[test]
+    fn test_all_of() {
+        let mut criterion = TargetFitness(0.8).and(MaxGenerations(3));
+        assert_eq!(criterion.check(&state(), &stats(0, 0.9)), None);
+        assert_eq!(criterion.check(&state(), &stats(2, 0.5)), None);
+        assert_eq!(
+            criterion.check(&state(), &stats(2, 0.9)),
+            Some(StopReason::All(vec![
+                StopReason::TargetFitness(0.8),
+                StopReason::Generations(3)
+            ]))
+        );
+        assert_eq!(AllOf(vec![]).check(&state(), &stats(2, 0.9)), None);
+    }
+
+    #[test]
+    fn test_stop_reason_display() {
+        assert_eq!(
+            StopReason::Stagnation(5).to_string(),
+            "no improvement for 5 generations"
+        );
+        assert_eq!(
+            StopReason::All(vec![StopReason::Converged, StopReason::Solved]).to_string(),
+            "population converged and solved"
+        );
+    }
+
+    #[test]
+    fn test_run_reports_stop_reason() {
+        let outcome = GaConfig::builder()
+            .max_generations(10)
+            .mutation_rate(0.5)
+            .seed(2)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(outcome.stop_reason, StopReason::MaxGenerations);
+        assert_eq!(outcome.evaluations, 50 * 11);
+
+        let outcome = GaConfig::builder()
+            .mutation_rate(0.5)
+            .termination(MaxEvaluations(200))
+            .seed(2)
+            .build()
+            .unwrap()
+            .run();
+        assert_eq!(outcome.stop_reason, StopReason::Evaluations(200));
+        assert_eq!(outcome.generation, 3);
+    }
+
+    #[test]
+    fn test_run_resets_criteria() {
+        let mut config = GaConfig::builder()
+            .mutation_rate(0.5)
+            .termination(Stagnation::new(3).or(MaxGenerations(50)))
+            .build()
+            .unwrap();
+        let outcome1 = config.run();
+        let outcome2 = config.run();
+        assert!(outcome1.generation >= 3);
+        assert!(outcome2.generation >= 3);
+    }
+}
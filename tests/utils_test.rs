@@ -1,9 +1,6 @@
-#[path = "../src/utils.rs"]
-mod utils;
-use utils::*;
+use one_max_genetic_algorithm_rust::utils::*;
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 